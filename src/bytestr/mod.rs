@@ -1,9 +1,10 @@
 use std::{fmt, ops::Range};
 
 use crate::{
+    dedent::dedent,
     err::{perr, ParseErrorKind::*},
-    escape::{scan_raw_string, unescape_string},
-    Buffer, ParseError,
+    escape::{scan_raw_string, unescape_string, EscapeContainer, RawChunks},
+    Buffer, ByteChunks, DedentMode, ParseError,
 };
 
 
@@ -33,13 +34,7 @@ impl<B: Buffer> ByteStringLit<B> {
     /// Parses the input as a (raw) byte string literal. Returns an error if the
     /// input is invalid or represents a different kind of literal.
     pub fn parse(input: B) -> Result<Self, ParseError> {
-        if input.is_empty() {
-            return Err(perr(None, Empty));
-        }
-        if !input.starts_with(r#"b""#) && !input.starts_with("br") {
-            return Err(perr(None, InvalidByteStringLiteralStart));
-        }
-
+        check_start(&input)?;
         let (value, num_hashes, start_suffix) = parse_impl(&input)?;
         Ok(Self { raw: input, value, num_hashes, start_suffix })
     }
@@ -64,6 +59,40 @@ impl<B: Buffer> ByteStringLit<B> {
             .unwrap_or_else(|| raw.cut(inner_range).into_byte_cow())
     }
 
+    /// Returns the value with indentation removed like the `indoc!` macro
+    /// does. See [`StringLit::value_dedented`][crate::StringLit::value_dedented].
+    pub fn value_dedented(&self) -> Vec<u8> {
+        self.value_dedented_with(DedentMode::Indoc)
+    }
+
+    /// Returns the value with indentation removed as described by `mode`. See
+    /// [`DedentMode`].
+    pub fn value_dedented_with(&self, mode: DedentMode) -> Vec<u8> {
+        dedent(self.value(), mode)
+    }
+
+    /// Returns an iterator over the value of this literal, in chunks taken
+    /// directly from the raw input: verbatim parts and single unescaped
+    /// bytes. Concatenating all chunks results in [`value`][Self::value].
+    /// To not build the unescaped value in the first place, use
+    /// [`parse_chunks`][ByteStringLit::parse_chunks].
+    ///
+    /// ```
+    /// use litrs::{ByteChunk, ByteStringLit};
+    ///
+    /// let lit = ByteStringLit::parse(r#"b"ab\xFFc""#).unwrap();
+    /// let chunks = lit.chunks().collect::<Vec<_>>();
+    /// assert_eq!(chunks, [
+    ///     ByteChunk::Verbatim(b"ab"),
+    ///     ByteChunk::Byte(0xFF),
+    ///     ByteChunk::Verbatim(b"c"),
+    /// ]);
+    /// ```
+    pub fn chunks(&self) -> ByteChunks<'_> {
+        let content = &self.raw[self.inner_range()];
+        ByteChunks::new(RawChunks::new(content, self.is_raw_byte_string(), false, true))
+    }
+
     /// The optional suffix. Returns `""` if the suffix is empty/does not exist.
     pub fn suffix(&self) -> &str {
         &(*self.raw)[self.start_suffix..]
@@ -87,14 +116,22 @@ impl<B: Buffer> ByteStringLit<B> {
 
     /// The range within `self.raw` that excludes the quotes and potential `r#`.
     fn inner_range(&self) -> Range<usize> {
-        match self.num_hashes {
-            None => 2..self.start_suffix - 1,
-            Some(n) => 2 + n as usize + 1..self.start_suffix - n as usize - 1,
-        }
+        inner_range(self.num_hashes, self.start_suffix)
     }
 }
 
-impl ByteStringLit<&str> {
+impl<'a> ByteStringLit<&'a str> {
+    /// Checks that `input` is a valid (raw) byte string literal exactly like
+    /// [`parse`][Self::parse] and returns an iterator over its value like
+    /// [`chunks`][Self::chunks] does. Unlike `parse`, this never allocates,
+    /// even if the literal contains escapes.
+    pub fn parse_chunks(input: &'a str) -> Result<ByteChunks<'a>, ParseError> {
+        check_start(input)?;
+        let (_, num_hashes, start_suffix) = parse_impl::<()>(input)?;
+        let content = &input[inner_range(num_hashes, start_suffix)];
+        Ok(ByteChunks::new(RawChunks::new(content, num_hashes.is_some(), false, true)))
+    }
+
     /// Makes a copy of the underlying buffer and returns the owned version of
     /// `Self`.
     pub fn into_owned(self) -> ByteStringLit<String> {
@@ -114,17 +151,44 @@ impl<B: Buffer> fmt::Display for ByteStringLit<B> {
 }
 
 
+/// The range within the raw input that excludes the quotes and potential `br#`.
+fn inner_range(num_hashes: Option<u8>, start_suffix: usize) -> Range<usize> {
+    match num_hashes {
+        None => 2..start_suffix - 1,
+        Some(n) => 2 + n as usize + 1..start_suffix - n as usize - 1,
+    }
+}
+
 /// Precondition: input has to start with either `b"` or `br`.
 #[inline(never)]
-fn parse_impl(input: &str) -> Result<(Option<Vec<u8>>, Option<u8>, usize), ParseError> {
+fn check_start(input: &str) -> Result<(), ParseError> {
+    if input.is_empty() {
+        return Err(perr(None, Empty));
+    }
+    if !input.starts_with(r#"b""#) && !input.starts_with("br") {
+        return Err(perr(None, InvalidByteStringLiteralStart));
+    }
+    Ok(())
+}
+
+fn parse_impl<C: EscapeContainer>(
+    input: &str,
+) -> Result<(Option<C>, Option<u8>, usize), ParseError> {
     if input.starts_with("br") {
         scan_raw_string(input, 2, false, true)
             .map(|(num, start_suffix)| (None, Some(num), start_suffix))
     } else {
-        unescape_string::<Vec<u8>>(input, 2, false, true, true)
+        unescape_string::<C>(input, 2, false, true, true)
             .map(|(v, start_suffix)| (v, None, start_suffix))
     }
 }
 
+/// Checks whether `input` is a valid byte string literal, exactly like
+/// [`ByteStringLit::parse`], but without allocating.
+pub(crate) fn validate(input: &str) -> Result<(), ParseError> {
+    check_start(input)?;
+    parse_impl::<()>(input).map(|_| ())
+}
+
 #[cfg(test)]
 mod tests;
@@ -1,6 +1,6 @@
 use crate::{
-    test_util::{assert_parse_ok_eq, assert_roundtrip},
-    ByteStringLit, Literal,
+    test_util::{assert_parse_ok_eq, assert_roundtrip, count_allocations},
+    ByteStringLit, DedentMode, EmitOptions, Literal,
 };
 
 // ===== Utility functions =======================================================================
@@ -130,6 +130,10 @@ bar", true, None);
     let lit = ByteStringLit::parse("b\"foo\\\n\t\n \n\tbar\"").expect("failed to parse");
     assert_eq!(lit.value(), b"foobar");
 
+    // Only a string continue
+    let lit = ByteStringLit::parse("b\"\\\n  \"").expect("failed to parse");
+    assert_eq!(lit.value(), b"");
+
     // Raw strings do not handle "string continues"
     check!(br"foo\
         bar", false, Some(0));
@@ -235,3 +239,151 @@ fn unicode_escape_not_allowed() {
     assert_err!(ByteStringLit, r#"b"\u{1f602}""#, UnicodeEscapeInByteLiteral, 2..4);
     assert_err!(ByteStringLit, r#"b"\u{1F602}""#, UnicodeEscapeInByteLiteral, 2..4);
 }
+
+#[test]
+fn chunks() {
+    use crate::ByteChunk;
+
+    #[track_caller]
+    fn check(input: &str, expected: &[ByteChunk<'_>]) {
+        let lit = ByteStringLit::parse(input).unwrap();
+        let chunks = lit.chunks().collect::<Vec<_>>();
+        assert_eq!(chunks, expected, "for `{input}`");
+
+        // `parse_chunks` yields the same chunks without allocating
+        let (same, allocations) = count_allocations(|| {
+            ByteStringLit::parse_chunks(input).unwrap().eq(expected.iter().copied())
+        });
+        assert!(same, "`parse_chunks` differs from `chunks` for `{input}`");
+        assert_eq!(allocations, 0, "`parse_chunks` allocated for `{input}`");
+
+        let mut value = Vec::new();
+        for chunk in chunks {
+            match chunk {
+                ByteChunk::Verbatim(s) => value.extend_from_slice(s),
+                ByteChunk::Byte(b) => value.push(b),
+                ByteChunk::Char(_) => panic!("unexpected char chunk"),
+            }
+        }
+        assert_eq!(value, lit.value());
+    }
+
+    check(r#"b"""#, &[]);
+    check(r#"b"foo"_suffix"#, &[ByteChunk::Verbatim(b"foo")]);
+    check(r#"b"a\xFF\0b""#, &[
+        ByteChunk::Verbatim(b"a"),
+        ByteChunk::Byte(0xFF),
+        ByteChunk::Byte(0),
+        ByteChunk::Verbatim(b"b"),
+    ]);
+    check("b\"foo\\\n    bar\"", &[ByteChunk::Verbatim(b"foo"), ByteChunk::Verbatim(b"bar")]);
+
+    check(r#"br"""#, &[]);
+    check(r##"br#"a\n"b"#"##, &[ByteChunk::Verbatim(br#"a\n"b"#)]);
+}
+
+#[test]
+fn from_bytes_with() {
+    #[track_caller]
+    fn check(data: &[u8], options: &EmitOptions, expected: &str) {
+        let lit = ByteStringLit::from_bytes_with(data, options);
+        assert_eq!(lit.raw_input(), expected);
+        assert_eq!(lit.value(), data);
+    }
+
+    let default = EmitOptions::new();
+    check(b"", &default, r#"b"""#);
+    check(b"foo bar", &default, r#"b"foo bar""#);
+    check(b"\"\\\n\r\t\0\x7F\x80\xFF", &default, r#"b"\"\\\n\r\t\0\x7F\x80\xFF""#);
+
+    let raw = EmitOptions::new().prefer_raw(true);
+    check(b"", &raw, r#"br"""#);
+    check(b"a\\b\n\tc", &raw, "br\"a\\b\n\tc\"");
+    check(br#"a"b"#, &raw, r##"br#"a"b"#"##);
+    check(br##"a"#b"##, &raw, r###"br##"a"#b"##"###);
+    check(b"a\rb", &raw, r#"b"a\rb""#);
+    check(b"a\x01b", &raw, r#"b"a\x01b""#);
+    check(b"\xFF", &raw, r#"b"\xFF""#);
+    check(b"1234567\n123456789", &raw.clone().max_line_len(10), "br\"1234567\n123456789\"");
+    check(b"12345678\n1", &raw.clone().max_line_len(10), "b\"1234567\\\n8\\n1\"");
+    check(b"1\n1234567890", &raw.clone().max_line_len(10), "b\"1\\n1234\\\n567890\"");
+
+    let wrap = EmitOptions::new().max_line_len(10);
+    check(b"123456", &wrap, r#"b"123456""#);
+    check(b"1234567", &wrap, r#"b"1234567""#);
+    check(b"12345678", &wrap, "b\"1234567\\\n8\"");
+    check(b"12345\xFF", &wrap, "b\"12345\\\n\\xFF\"");
+    check(b"123456 78", &wrap, "b\"123456 \\\n78\"");
+    check(b"1234567 8", &wrap, "b\"1234567\\\n\\x208\"");
+    check(b"\xFF\xFF\xFF", &wrap, "b\"\\xFF\\\n\\xFF\\xFF\"");
+
+    // Lines are only longer than the limit if a single escape does not fit.
+    check(b"\xFF\xFF", &EmitOptions::new().max_line_len(3), "b\"\\\n\\xFF\\\n\\xFF\"");
+}
+
+#[test]
+fn from_bytes_with_roundtrip() {
+    let data = (0..=255u8).cycle().take(2000).collect::<Vec<_>>();
+    let text = b"Lorem ipsum  dolor \"sit\" amet,\n\tconsectetur   adipiscing\\elit ".repeat(20);
+
+    for data in [&data[..], &text[..]] {
+        for max in [None, Some(0), Some(5), Some(7), Some(40), Some(100)] {
+            for prefer_raw in [false, true] {
+                let mut options = EmitOptions::new().prefer_raw(prefer_raw);
+                if let Some(max) = max {
+                    options = options.max_line_len(max);
+                }
+
+                let lit = ByteStringLit::from_bytes_with(data, &options);
+                assert_eq!(lit.value(), data, "with {options:?}");
+                if let Some(max) = max.filter(|&max| max >= 5) {
+                    let longest = lit.raw_input().lines().map(str::len).max().unwrap();
+                    assert!(longest <= max, "line too long with {options:?}");
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn write_bytes_with() {
+    #[track_caller]
+    fn check(data: &[u8], options: &EmitOptions, expected: &str) {
+        let mut out = Vec::new();
+        ByteStringLit::write_bytes_with(data, options, &mut out).unwrap();
+        assert_eq!(std::str::from_utf8(&out).unwrap(), expected);
+    }
+
+    let array = EmitOptions::new().array_fallback(true);
+    check(b"", &array, r#"b"""#);
+    check(b"\xFF", &array, "[0xFF]");
+    check(b"a\xFF", &array, r#"b"a\xFF""#);
+    check(b"\x01\x02a", &array, "[0x01, 0x02, 0x61]");
+    check(b"\x01\x02a", &array.clone().prefer_raw(true), "[0x01, 0x02, 0x61]");
+    check(b"\x01\x02a", &array.clone().max_line_len(18), "[0x01, 0x02, 0x61]");
+    check(
+        b"\x01\x02\x03\x04\x05",
+        &array.clone().max_line_len(16),
+        "[\n    0x01, 0x02,\n    0x03, 0x04,\n    0x05,\n]",
+    );
+
+    // Large outputs are written in multiple chunks.
+    let data = vec![0xAB; 100_000];
+    let mut out = Vec::new();
+    ByteStringLit::write_bytes_with(&data, &EmitOptions::new(), &mut out).unwrap();
+    assert_eq!(out.len(), 3 + 4 * data.len());
+    let lit = ByteStringLit::parse(std::str::from_utf8(&out).unwrap()).unwrap();
+    assert_eq!(lit.value(), data);
+}
+
+#[test]
+fn value_dedented() {
+    let lit = ByteStringLit::parse("b\"\n    a\\xFF\n      b\n    \"").unwrap();
+    assert_eq!(lit.value_dedented(), b"a\xFF\n  b\n");
+    assert_eq!(lit.value_dedented_with(DedentMode::Indoc), b"a\xFF\n  b\n");
+    assert_eq!(lit.value_dedented_with(DedentMode::Unindent), b"\na\xFF\n  b\n");
+
+    let lit = ByteStringLit::parse("br\"  x\n    a\"").unwrap();
+    assert_eq!(lit.value_dedented(), b"  x\na");
+    assert_eq!(lit.value_dedented_with(DedentMode::Unindent), b"x\n  a");
+}
@@ -0,0 +1,82 @@
+use crate::escape::{RawChunk, RawChunks, Unescape};
+
+
+/// A piece of the value of a [`StringLit`][crate::StringLit], yielded by
+/// [`StringLit::chunks`][crate::StringLit::chunks].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Chunk<'a> {
+    /// A part of the raw input that does not contain escapes and is part of
+    /// the value as is.
+    Verbatim(&'a str),
+
+    /// A single character resulting from an escape like `\n` or `\u{1F980}`.
+    Char(char),
+}
+
+/// A piece of the value of a [`ByteStringLit`][crate::ByteStringLit] or
+/// [`CStringLit`][crate::CStringLit], yielded by their `chunks` methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteChunk<'a> {
+    /// A part of the raw input that does not contain escapes and is part of
+    /// the value as is.
+    Verbatim(&'a [u8]),
+
+    /// A single byte resulting from an escape like `\n` or `\xFF`.
+    Byte(u8),
+
+    /// A character resulting from a `\u{...}` escape, which is part of the
+    /// value as UTF-8. Only C string literals can contain these.
+    Char(char),
+}
+
+/// Iterator over the value of a string literal. See
+/// [`StringLit::chunks`][crate::StringLit::chunks].
+#[derive(Debug, Clone)]
+pub struct Chunks<'a> {
+    inner: RawChunks<'a>,
+}
+
+impl<'a> Chunks<'a> {
+    pub(crate) fn new(inner: RawChunks<'a>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<'a> Iterator for Chunks<'a> {
+    type Item = Chunk<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk = match self.inner.next()? {
+            RawChunk::Verbatim(s) => Chunk::Verbatim(s),
+            RawChunk::Escape(v) => Chunk::Char(v.unwrap_char()),
+        };
+        Some(chunk)
+    }
+}
+
+/// Iterator over the value of a byte string or C string literal. See
+/// [`ByteStringLit::chunks`][crate::ByteStringLit::chunks] and
+/// [`CStringLit::chunks`][crate::CStringLit::chunks].
+#[derive(Debug, Clone)]
+pub struct ByteChunks<'a> {
+    inner: RawChunks<'a>,
+}
+
+impl<'a> ByteChunks<'a> {
+    pub(crate) fn new(inner: RawChunks<'a>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<'a> Iterator for ByteChunks<'a> {
+    type Item = ByteChunk<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk = match self.inner.next()? {
+            RawChunk::Verbatim(s) => ByteChunk::Verbatim(s.as_bytes()),
+            RawChunk::Escape(Unescape::Byte(b)) => ByteChunk::Byte(b),
+            RawChunk::Escape(Unescape::Unicode(c)) => ByteChunk::Char(c),
+        };
+        Some(chunk)
+    }
+}
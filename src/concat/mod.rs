@@ -0,0 +1,226 @@
+//! Helpers implementing the rules of `concat!` and `concat_bytes!`.
+//!
+//! Reimplementing `concat!` in a proc macro is easy to get subtly wrong. The
+//! functions in this module follow the rules of the std macros:
+//!
+//! - [`to_string`] accepts string, char, integer, float and bool literals.
+//!   Integers are written as decimal number, floats as written (without
+//!   underscores) and both without suffix. Byte, byte string and C string
+//!   literals are rejected.
+//! - [`to_bytes`] accepts byte and byte string literals as well as integer
+//!   literals that fit into `u8` and have no suffix or the suffix `u8`.
+//!
+//! All rejected inputs are reported, each with the index of the literal.
+//!
+//! ```
+//! use litrs::{concat, Literal};
+//!
+//! let lits = ["\"x = \"", "0x1F", "'/'", "2.5e1f32", "true"]
+//!     .iter()
+//!     .map(|s| Literal::parse(*s).unwrap())
+//!     .collect::<Vec<_>>();
+//! assert_eq!(concat::to_string(&lits).unwrap(), "x = 31/2.5e1true");
+//!
+//! let lits = [Literal::parse("b\"ab\"").unwrap(), Literal::parse("1.0").unwrap()];
+//! let errors = concat::to_bytes(&lits).unwrap_err();
+//! assert_eq!(errors.len(), 1);
+//! assert_eq!(errors[0].index(), 1);
+//! assert_eq!(errors[0].to_string(), "cannot concatenate a float literal");
+//! ```
+//!
+//! Note that `concat_bytes!` only accepts integers inside byte arrays like
+//! `[1, 2]` or `[0; 4]`. Arrays are not literals, so splitting them up into
+//! their elements (and rejecting integers outside of arrays, if desired) is
+//! left to the caller.
+
+use std::fmt;
+
+use crate::{Buffer, FloatType, IntegerBase, IntegerLit, IntegerType, Literal, LiteralKind};
+
+
+#[cfg(test)]
+mod tests;
+
+
+/// Concatenates the values of the given literals like `concat!` does.
+///
+/// Returns an error for each literal that is rejected by `concat!`, see the
+/// [module docs][self] for the rules.
+pub fn to_string<B: Buffer>(lits: &[Literal<B>]) -> Result<String, Vec<ConcatError>> {
+    concat(lits, false, String::new(), push_str)
+}
+
+/// Concatenates the values of the given literals like `concat_bytes!` does.
+///
+/// Returns an error for each literal that is rejected by `concat_bytes!`, see
+/// the [module docs][self] for the rules.
+pub fn to_bytes<B: Buffer>(lits: &[Literal<B>]) -> Result<Vec<u8>, Vec<ConcatError>> {
+    concat(lits, true, Vec::new(), push_bytes)
+}
+
+fn concat<B: Buffer, T>(
+    lits: &[Literal<B>],
+    bytes: bool,
+    mut out: T,
+    push: fn(&mut T, &Literal<B>) -> Result<(), ConcatErrorKind>,
+) -> Result<T, Vec<ConcatError>> {
+    let errors = lits.iter()
+        .enumerate()
+        .filter_map(|(index, lit)| {
+            let res = check_suffix(lit).and_then(|_| push(&mut out, lit));
+            res.err().map(|kind| ConcatError { index, kind, bytes })
+        })
+        .collect::<Vec<_>>();
+
+    if errors.is_empty() {
+        Ok(out)
+    } else {
+        Err(errors)
+    }
+}
+
+fn push_str<B: Buffer>(out: &mut String, lit: &Literal<B>) -> Result<(), ConcatErrorKind> {
+    match lit {
+        Literal::Bool(b) => out.push_str(b.as_str()),
+        Literal::Char(c) => out.push(c.value()),
+        Literal::String(s) => out.push_str(s.value()),
+        Literal::Integer(i) if has_float_suffix(i) => {
+            push_without_underscores(out, i.raw_main_part());
+        }
+        Literal::Integer(i) => {
+            let v = i.value::<u128>().ok_or(ConcatErrorKind::OutOfRange)?;
+            out.push_str(&v.to_string());
+        }
+        Literal::Float(f) => push_without_underscores(out, f.number_part()),
+        _ => return Err(ConcatErrorKind::UnsupportedLiteral(lit.kind())),
+    }
+    Ok(())
+}
+
+fn push_bytes<B: Buffer>(out: &mut Vec<u8>, lit: &Literal<B>) -> Result<(), ConcatErrorKind> {
+    match lit {
+        Literal::Byte(b) => out.push(b.value()),
+        Literal::ByteString(s) => out.extend_from_slice(s.value()),
+        Literal::Integer(i) if has_float_suffix(i) => {
+            return Err(ConcatErrorKind::UnsupportedLiteral(LiteralKind::Float));
+        }
+        Literal::Integer(i) => match IntegerType::from_suffix(i.suffix()) {
+            None | Some(IntegerType::U8) => {
+                out.push(i.value::<u8>().ok_or(ConcatErrorKind::OutOfRange)?);
+            }
+            Some(_) => return Err(ConcatErrorKind::NotU8),
+        },
+        _ => return Err(ConcatErrorKind::UnsupportedLiteral(lit.kind())),
+    }
+    Ok(())
+}
+
+/// Checks that the literal has no suffix or a type suffix valid for its kind.
+fn check_suffix<B: Buffer>(lit: &Literal<B>) -> Result<(), ConcatErrorKind> {
+    let valid = match lit {
+        Literal::Integer(i) => {
+            i.suffix().is_empty()
+                || IntegerType::from_suffix(i.suffix()).is_some()
+                || has_float_suffix(i)
+        }
+        Literal::Float(f) => f.suffix().is_empty() || FloatType::from_suffix(f.suffix()).is_some(),
+        _ => lit.suffix().is_empty(),
+    };
+
+    if valid {
+        Ok(())
+    } else {
+        Err(ConcatErrorKind::InvalidSuffix)
+    }
+}
+
+/// Returns whether the integer has a float suffix, e.g. `3f32`. rustc treats
+/// these as float literals. Float suffixes are only valid on decimal integers.
+fn has_float_suffix<B: Buffer>(i: &IntegerLit<B>) -> bool {
+    i.base() == IntegerBase::Decimal && FloatType::from_suffix(i.suffix()).is_some()
+}
+
+fn push_without_underscores(out: &mut String, s: &str) {
+    out.extend(s.chars().filter(|&c| c != '_'));
+}
+
+
+// ==============================================================================================
+// ===== Errors
+// ==============================================================================================
+
+/// A literal rejected by [`to_string`] or [`to_bytes`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConcatError {
+    index: usize,
+    kind: ConcatErrorKind,
+    bytes: bool,
+}
+
+/// The different kinds of [`ConcatError`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ConcatErrorKind {
+    /// Literals of this kind cannot be concatenated, e.g. byte strings in
+    /// `concat!` or strings in `concat_bytes!`.
+    UnsupportedLiteral(LiteralKind),
+
+    /// The literal has a suffix that is not a type suffix valid for its kind,
+    /// e.g. `"foo"bar` or `1.0u8`.
+    InvalidSuffix,
+
+    /// The integer literal is too large: it does not fit into `u128` for
+    /// `concat!` or into `u8` for `concat_bytes!`.
+    OutOfRange,
+
+    /// The integer literal in `concat_bytes!` has a type suffix other than
+    /// `u8`, e.g. `1u16`.
+    NotU8,
+}
+
+impl ConcatError {
+    /// The index of the rejected literal in the input slice.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The kind of this error.
+    pub fn kind(&self) -> ConcatErrorKind {
+        self.kind
+    }
+}
+
+impl std::error::Error for ConcatError {}
+
+impl fmt::Display for ConcatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ConcatErrorKind::UnsupportedLiteral(kind) => {
+                let desc = match kind {
+                    LiteralKind::Bool => "a boolean",
+                    LiteralKind::Integer => "a numeric",
+                    LiteralKind::Float => "a float",
+                    LiteralKind::Char => "a character",
+                    LiteralKind::String => "a string",
+                    LiteralKind::Byte => "a byte",
+                    LiteralKind::ByteString => "a byte string",
+                    LiteralKind::CString => "a C string",
+                };
+                write!(f, "cannot concatenate {} literal", desc)?;
+                match (self.bytes, kind) {
+                    (true, LiteralKind::Char) => f.write_str(", try using a byte literal"),
+                    (true, LiteralKind::String) => {
+                        f.write_str(", try using a byte string literal")
+                    }
+                    _ => Ok(()),
+                }
+            }
+            ConcatErrorKind::InvalidSuffix => f.write_str("invalid suffix on literal"),
+            ConcatErrorKind::OutOfRange if self.bytes => {
+                f.write_str("numeric literal is out of bounds")
+            }
+            ConcatErrorKind::OutOfRange => f.write_str("integer literal is too large"),
+            ConcatErrorKind::NotU8 => f.write_str("numeric literal is not a `u8`"),
+        }
+    }
+}
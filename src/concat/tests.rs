@@ -0,0 +1,129 @@
+use super::{to_bytes, to_string, ConcatErrorKind};
+use crate::{Literal, LiteralKind};
+
+
+// ===== Utility functions =======================================================================
+
+#[track_caller]
+fn lits<'a>(inputs: &[&'a str]) -> Vec<Literal<&'a str>> {
+    inputs.iter()
+        .map(|s| Literal::parse(*s).unwrap_or_else(|e| panic!("failed to parse {s}: {e}")))
+        .collect()
+}
+
+#[track_caller]
+fn string_errs(inputs: &[&str]) -> Vec<(usize, ConcatErrorKind)> {
+    to_string(&lits(inputs)).unwrap_err().iter().map(|e| (e.index(), e.kind())).collect()
+}
+
+#[track_caller]
+fn bytes_errs(inputs: &[&str]) -> Vec<(usize, ConcatErrorKind)> {
+    to_bytes(&lits(inputs)).unwrap_err().iter().map(|e| (e.index(), e.kind())).collect()
+}
+
+
+// ===== Actual tests ============================================================================
+
+#[test]
+fn string_values() {
+    assert_eq!(to_string::<&str>(&[]).unwrap(), "");
+    let input = lits(&[r#""a\tb""#, r##"r#"c"#"##, "'d'", r"'\n'"]);
+    assert_eq!(to_string(&input).unwrap(), "a\tbcd\n");
+    assert_eq!(to_string(&lits(&["true", "false"])).unwrap(), "truefalse");
+}
+
+#[test]
+fn string_numbers() {
+    // Integers are written in decimal, without suffix and underscores.
+    let input = lits(&["0", "1_000", "0x1F", "0o17", "0b101"]);
+    assert_eq!(to_string(&input).unwrap(), "0100031155");
+    assert_eq!(to_string(&lits(&["7u8", "300u8", "5i64", "0xFFusize"])).unwrap(), "73005255");
+    assert_eq!(
+        to_string(&lits(&["340282366920938463463374607431768211455"])).unwrap(),
+        "340282366920938463463374607431768211455",
+    );
+
+    // Floats are written as is, without suffix and underscores.
+    let input = lits(&["1.5", "1.", "2.5e1", "1_0.0_1E-3_f32"]);
+    assert_eq!(to_string(&input).unwrap(), "1.51.2.5e110.01E-3");
+    assert_eq!(to_string(&lits(&["3f32", "1_2f64"])).unwrap(), "312");
+
+    // `0x1f32` is a hex integer.
+    assert_eq!(to_string(&lits(&["0x1f32"])).unwrap(), "7986");
+}
+
+#[test]
+fn string_rejected() {
+    use ConcatErrorKind::*;
+
+    assert_eq!(
+        string_errs(&["\"a\"", "b'a'", r#"b"a""#, r#"c"a""#, "1"]),
+        [
+            (1, UnsupportedLiteral(LiteralKind::Byte)),
+            (2, UnsupportedLiteral(LiteralKind::ByteString)),
+            (3, UnsupportedLiteral(LiteralKind::CString)),
+        ],
+    );
+    assert_eq!(
+        string_errs(&["340282366920938463463374607431768211456", "1"]),
+        [(0, OutOfRange)],
+    );
+    assert_eq!(
+        string_errs(&[r#""a"foo"#, "'a'x", "1px", "1.0u8", "0b1f32", "1.0f32", "2u128"]),
+        [0, 1, 2, 3, 4].map(|i| (i, InvalidSuffix)),
+    );
+}
+
+#[test]
+fn bytes_values() {
+    assert_eq!(to_bytes::<&str>(&[]).unwrap(), b"");
+    let input = lits(&[r#"b"a\xFF""#, r##"br#"c"#"##, "b'd'", r"b'\n'"]);
+    assert_eq!(to_bytes(&input).unwrap(), b"a\xFFcd\n");
+    assert_eq!(to_bytes(&lits(&["0", "255", "0xFFu8", "1_0"])).unwrap(), b"\x00\xFF\xFF\x0A");
+}
+
+#[test]
+fn bytes_rejected() {
+    use ConcatErrorKind::*;
+
+    assert_eq!(
+        bytes_errs(&["b'a'", "\"a\"", "'a'", r#"c"a""#, "1.0", "true", "3f32"]),
+        [
+            (1, UnsupportedLiteral(LiteralKind::String)),
+            (2, UnsupportedLiteral(LiteralKind::Char)),
+            (3, UnsupportedLiteral(LiteralKind::CString)),
+            (4, UnsupportedLiteral(LiteralKind::Float)),
+            (5, UnsupportedLiteral(LiteralKind::Bool)),
+            (6, UnsupportedLiteral(LiteralKind::Float)),
+        ],
+    );
+    assert_eq!(bytes_errs(&["256", "1_000u8", "255u8"]), [(0, OutOfRange), (1, OutOfRange)]);
+    assert_eq!(bytes_errs(&["1u16", "1i8", "1usize", "1"]), [0, 1, 2].map(|i| (i, NotU8)));
+    assert_eq!(bytes_errs(&[r#"b"a"x"#, "b'a'_x", "1px"]), [0, 1, 2].map(|i| (i, InvalidSuffix)));
+}
+
+#[test]
+fn display() {
+    let msgs = |errors: Vec<super::ConcatError>| {
+        errors.iter().map(|e| e.to_string()).collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        msgs(to_string(&lits(&[r#"b"a""#, r#"c"a""#, "1px"])).unwrap_err()),
+        [
+            "cannot concatenate a byte string literal",
+            "cannot concatenate a C string literal",
+            "invalid suffix on literal",
+        ],
+    );
+    assert_eq!(
+        msgs(to_bytes(&lits(&["\"a\"", "'a'", "1.0", "256", "1u16"])).unwrap_err()),
+        [
+            "cannot concatenate a string literal, try using a byte string literal",
+            "cannot concatenate a character literal, try using a byte literal",
+            "cannot concatenate a float literal",
+            "numeric literal is out of bounds",
+            "numeric literal is not a `u8`",
+        ],
+    );
+}
@@ -0,0 +1,495 @@
+//! `const fn` parsing of literals, for use in constants.
+//!
+//! The functions in this module can be evaluated at compile time. This is
+//! useful to check or convert the text of literals in `macro_rules!` macros
+//! (e.g. via `stringify!`) without writing a proc macro. On invalid input,
+//! they panic with the description of the parse error, which results in a
+//! compile error when evaluated in a const context.
+//!
+//! ```
+//! use litrs::const_parse;
+//!
+//! const MASK: u64 = const_parse::u64("0xFF_FF");
+//! const LEN: usize = const_parse::string_len(r#""Grüße\n""#);
+//! const BYTES: [u8; 3] = const_parse::byte_string(r#"b"a\x00c""#);
+//!
+//! assert_eq!(MASK, 0xFFFF);
+//! assert_eq!(LEN, "Grüße\n".len());
+//! assert_eq!(BYTES, *b"a\0c");
+//! ```
+//!
+//! ```compile_fail
+//! // error: integer literal is out of range for its type
+//! const X: u8 = litrs::const_parse::u8("256");
+//! assert_eq!(X, 0);
+//! ```
+//!
+//! Compared to [`Literal::parse`][crate::Literal::parse], there are some
+//! limitations:
+//! - There is one function per integer type instead of a generic one, as
+//!   trait methods cannot be called in `const fn`.
+//! - Like [`IntegerLit::value`][crate::IntegerLit::value], the integer
+//!   functions ignore the type suffix, so `const_parse::u64("3u8")` is `3`.
+//! - Non-ASCII characters in suffixes are not checked, as if the
+//!   `check_suffix` feature was disabled.
+
+use crate::{
+    err::ParseErrorKind::{self, *},
+    parse::hex_digit_value,
+};
+
+
+/// Like `?`, which cannot be used in `const fn`.
+macro_rules! tri {
+    ($e:expr) => {
+        match $e {
+            Ok(v) => v,
+            Err(e) => return Err(e),
+        }
+    };
+}
+
+macro_rules! integer_fns {
+    ($($ty:ident),*) => {
+        $(
+            #[doc = concat!(
+                "Parses an integer literal and returns its value as `",
+                stringify!($ty),
+                "`. Panics if the input is invalid or the value does not fit.",
+            )]
+            pub const fn $ty(input: &str) -> $ty {
+                match integer(input, $ty::MAX as u128) {
+                    Ok(v) => v as $ty,
+                    Err(kind) => fail(kind),
+                }
+            }
+        )*
+    };
+}
+
+integer_fns!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Parses a (raw) string literal and returns the length of its value in
+/// bytes. Panics if the input is invalid.
+///
+/// ```
+/// use litrs::const_parse;
+///
+/// const LEN: usize = const_parse::string_len(r#""a\u{1F980}\t""#);
+/// assert_eq!(LEN, 6);
+/// ```
+pub const fn string_len(input: &str) -> usize {
+    match scan_string::<0>(input, false) {
+        Ok((_, len)) => len,
+        Err(kind) => fail(kind),
+    }
+}
+
+/// Parses a (raw) byte string literal and returns the length of its value.
+/// Panics if the input is invalid.
+pub const fn byte_string_len(input: &str) -> usize {
+    match scan_string::<0>(input, true) {
+        Ok((_, len)) => len,
+        Err(kind) => fail(kind),
+    }
+}
+
+/// Parses a (raw) byte string literal and returns its value. Panics if the
+/// input is invalid or if the length of the value is not `N`. Use
+/// [`byte_string_len`] to obtain `N`.
+///
+/// ```
+/// use litrs::const_parse;
+///
+/// const LIT: &str = r#"b"\x7FELF""#;
+/// const MAGIC: [u8; const_parse::byte_string_len(LIT)] = const_parse::byte_string(LIT);
+/// assert_eq!(&MAGIC, b"\x7FELF");
+/// ```
+pub const fn byte_string<const N: usize>(input: &str) -> [u8; N] {
+    match scan_string::<N>(input, true) {
+        Ok((out, len)) if len == N => out,
+        Ok(_) => panic!("length of byte string literal does not match array length"),
+        Err(kind) => fail(kind),
+    }
+}
+
+
+/// Panics with the description of the error. In a const context, this results
+/// in a compile error.
+const fn fail(kind: ParseErrorKind) -> ! {
+    panic!("{}", kind.description())
+}
+
+/// Same logic as `integer::parse_impl` and `IntegerLit::value`. Errors if the
+/// value is larger than `max`.
+const fn integer(input: &str, max: u128) -> Result<u128, ParseErrorKind> {
+    let bytes = input.as_bytes();
+    if bytes.is_empty() {
+        return Err(Empty);
+    }
+    if !bytes[0].is_ascii_digit() {
+        return Err(DoesNotStartWithDigit);
+    }
+
+    let (base, start) = match (bytes[0], if bytes.len() > 1 { bytes[1] } else { 0 }) {
+        (b'0', b'b') => (2, 2),
+        (b'0', b'o') => (8, 2),
+        (b'0', b'x') => (16, 2),
+        _ => (10, 0),
+    };
+
+    let mut end = start;
+    while end < bytes.len() && is_digit(bytes[end], base) {
+        end += 1;
+    }
+
+    if let Err(kind) = check_suffix(bytes, end) {
+        // Same special case as in `parse_impl` for a nicer error.
+        return Err(if bytes[end].is_ascii_digit() { InvalidDigit } else { kind });
+    }
+    if end < bytes.len() && (bytes[end] == b'e' || bytes[end] == b'E') {
+        return Err(IntegerSuffixStartingWithE);
+    }
+
+    let mut value: u128 = 0;
+    let mut has_digits = false;
+    let mut i = start;
+    while i < end {
+        let digit = match hex_digit_value(bytes[i]) {
+            Some(digit) => digit as u128,
+            None => {
+                // Underscore
+                i += 1;
+                continue;
+            }
+        };
+        has_digits = true;
+        value = match value.checked_mul(base) {
+            Some(v) => match v.checked_add(digit) {
+                Some(v) => v,
+                None => return Err(IntegerOutOfRange),
+            },
+            None => return Err(IntegerOutOfRange),
+        };
+        i += 1;
+    }
+
+    if !has_digits {
+        return Err(NoDigits);
+    }
+    if value > max {
+        return Err(IntegerOutOfRange);
+    }
+    Ok(value)
+}
+
+const fn is_digit(b: u8, base: u128) -> bool {
+    match base {
+        2 => matches!(b, b'0' | b'1' | b'_'),
+        8 => matches!(b, b'0'..=b'7' | b'_'),
+        10 => matches!(b, b'0'..=b'9' | b'_'),
+        _ => b.is_ascii_hexdigit() || b == b'_',
+    }
+}
+
+/// Same as `parse::check_suffix` without the `check_suffix` feature. `start`
+/// is the index where the suffix starts.
+const fn check_suffix(bytes: &[u8], start: usize) -> Result<(), ParseErrorKind> {
+    if start == bytes.len() {
+        return Ok(());
+    }
+
+    let first = bytes[start];
+    if first == b'_' && start + 1 == bytes.len() {
+        return Err(InvalidSuffix);
+    }
+    if first.is_ascii() && !(first.is_ascii_alphabetic() || first == b'_') {
+        return Err(UnexpectedChar);
+    }
+
+    // Bytes of non-ASCII characters are never ASCII, so we can check bytes
+    // instead of chars.
+    let mut i = start + 1;
+    while i < bytes.len() {
+        let b = bytes[i];
+        if b.is_ascii() && !(b.is_ascii_alphanumeric() || b == b'_') {
+            return Err(InvalidSuffix);
+        }
+        i += 1;
+    }
+
+    Ok(())
+}
+
+/// A part of the value of a string literal.
+enum Unit {
+    Byte(u8),
+    Char(u32),
+    Nothing,
+    End,
+}
+
+/// Parses a (byte) string literal, writing the first `N` bytes of its value
+/// into the returned array. Also returns the length of the value.
+const fn scan_string<const N: usize>(
+    input: &str,
+    byte_string: bool,
+) -> Result<([u8; N], usize), ParseErrorKind> {
+    let bytes = input.as_bytes();
+    let (num_hashes, mut i) = tri!(literal_start(bytes, byte_string));
+
+    let mut out = [0; N];
+    let mut len = 0;
+    loop {
+        let (unit, next) = match num_hashes {
+            Some(num_hashes) => tri!(next_raw_unit(bytes, i, num_hashes, !byte_string)),
+            None => tri!(next_unit(bytes, i, !byte_string)),
+        };
+        i = next;
+
+        match unit {
+            Unit::Byte(b) => {
+                if len < N {
+                    out[len] = b;
+                }
+                len += 1;
+            }
+            Unit::Char(c) => {
+                let (encoded, encoded_len) = encode_utf8(c);
+                let mut j = 0;
+                while j < encoded_len {
+                    if len < N {
+                        out[len] = encoded[j];
+                    }
+                    len += 1;
+                    j += 1;
+                }
+            }
+            Unit::Nothing => {}
+            Unit::End => break,
+        }
+    }
+
+    tri!(check_suffix(bytes, i));
+    Ok((out, len))
+}
+
+/// Checks the start of a (raw) (byte) string literal. Returns the number of
+/// hashes for raw literals and the index of the first byte of the content.
+const fn literal_start(
+    bytes: &[u8],
+    byte_string: bool,
+) -> Result<(Option<usize>, usize), ParseErrorKind> {
+    if bytes.is_empty() {
+        return Err(Empty);
+    }
+
+    let (prefix_len, wrong_start) = if byte_string {
+        (1, InvalidByteStringLiteralStart)
+    } else {
+        (0, InvalidStringLiteralStart)
+    };
+    if byte_string && bytes[0] != b'b' {
+        return Err(wrong_start);
+    }
+
+    match if prefix_len < bytes.len() { bytes[prefix_len] } else { 0 } {
+        b'"' => return Ok((None, prefix_len + 1)),
+        b'r' => {}
+        _ => return Err(wrong_start),
+    }
+
+    let start_hashes = prefix_len + 1;
+    let mut i = start_hashes;
+    while i < bytes.len() && bytes[i] == b'#' {
+        i += 1;
+    }
+    if i == bytes.len() {
+        return Err(InvalidLiteral);
+    }
+    let num_hashes = i - start_hashes;
+    if num_hashes > 256 {
+        return Err(TooManyHashes);
+    }
+    if bytes[i] != b'"' {
+        return Err(InvalidLiteral);
+    }
+
+    Ok((Some(num_hashes), i + 1))
+}
+
+/// Same logic as `escape::scan_raw_string`.
+const fn next_raw_unit(
+    bytes: &[u8],
+    i: usize,
+    num_hashes: usize,
+    unicode: bool,
+) -> Result<(Unit, usize), ParseErrorKind> {
+    if i >= bytes.len() {
+        return Err(UnterminatedRawString);
+    }
+
+    let b = bytes[i];
+    if b == b'"' {
+        let mut end = i + 1;
+        while end < bytes.len() && end - (i + 1) < num_hashes && bytes[end] == b'#' {
+            end += 1;
+        }
+        if end - (i + 1) == num_hashes {
+            return Ok((Unit::End, end));
+        }
+    }
+    if b == b'\r' {
+        return Err(CarriageReturn);
+    }
+    if !unicode && !b.is_ascii() {
+        return Err(NonAsciiInByteLiteral);
+    }
+
+    Ok((Unit::Byte(b), i + 1))
+}
+
+/// Same logic as `escape::unescape_string`.
+const fn next_unit(bytes: &[u8], i: usize, unicode: bool) -> Result<(Unit, usize), ParseErrorKind> {
+    if i >= bytes.len() {
+        return Err(UnterminatedString);
+    }
+
+    match bytes[i] {
+        b'\\' if i + 1 < bytes.len() && bytes[i + 1] == b'\n' => {
+            let mut end = i + 2;
+            while end < bytes.len() && matches!(bytes[end], b' ' | b'\t' | b'\n') {
+                end += 1;
+            }
+            if end == bytes.len() {
+                return Err(UnterminatedString);
+            }
+            Ok((Unit::Nothing, end))
+        }
+        b'\\' => unescape(bytes, i, unicode),
+        b'\r' => Err(CarriageReturn),
+        b'"' => Ok((Unit::End, i + 1)),
+        b if !unicode && !b.is_ascii() => Err(NonAsciiInByteLiteral),
+        b => Ok((Unit::Byte(b), i + 1)),
+    }
+}
+
+/// Same logic as `escape::unescape` for strings (`unicode = true`) and byte
+/// strings (`unicode = false`). `bytes[i]` is the backslash.
+const fn unescape(bytes: &[u8], i: usize, unicode: bool) -> Result<(Unit, usize), ParseErrorKind> {
+    // The escape must not use the last char, which might be the closing quote.
+    let mut end = bytes.len() - 1;
+    while end > i && bytes[end] & 0xC0 == 0x80 {
+        end -= 1;
+    }
+
+    if i + 1 >= end {
+        return Err(UnterminatedEscape);
+    }
+    let unit = match bytes[i + 1] {
+        b'\'' | b'"' | b'\\' => Unit::Byte(bytes[i + 1]),
+        b'n' => Unit::Byte(b'\n'),
+        b'r' => Unit::Byte(b'\r'),
+        b't' => Unit::Byte(b'\t'),
+        b'0' => Unit::Byte(b'\0'),
+        b'x' => {
+            if i + 4 > end {
+                return Err(UnterminatedEscape);
+            }
+            let value = match (hex_digit_value(bytes[i + 2]), hex_digit_value(bytes[i + 3])) {
+                (Some(first), Some(second)) => 16 * first + second,
+                _ => return Err(InvalidXEscape),
+            };
+            if unicode && value > 0x7F {
+                return Err(NonAsciiXEscape);
+            }
+            return Ok((Unit::Byte(value), i + 4));
+        }
+        b'u' => return unescape_unicode(bytes, i, end, unicode),
+        _ => return Err(UnknownEscape),
+    };
+
+    Ok((unit, i + 2))
+}
+
+/// Parses a `\u{...}` escape starting at `i`, which must end before `end`.
+const fn unescape_unicode(
+    bytes: &[u8],
+    i: usize,
+    end: usize,
+    unicode: bool,
+) -> Result<(Unit, usize), ParseErrorKind> {
+    if !unicode {
+        return Err(UnicodeEscapeInByteLiteral);
+    }
+    if i + 2 >= end || bytes[i + 2] != b'{' {
+        return Err(UnicodeEscapeWithoutBrace);
+    }
+
+    let mut closing_pos = i + 3;
+    while closing_pos < end && bytes[closing_pos] != b'}' {
+        closing_pos += 1;
+    }
+    if closing_pos == end {
+        return Err(UnterminatedUnicodeEscape);
+    }
+    if bytes[i + 3] == b'_' {
+        return Err(InvalidStartOfUnicodeEscape);
+    }
+
+    let mut value: u32 = 0;
+    let mut digit_count = 0;
+    let mut j = i + 3;
+    while j < closing_pos {
+        let b = bytes[j];
+        j += 1;
+        if b == b'_' {
+            continue;
+        }
+
+        let digit = match hex_digit_value(b) {
+            Some(digit) => digit,
+            None => return Err(NonHexDigitInUnicodeEscape),
+        };
+        if digit_count == 6 {
+            return Err(TooManyDigitInUnicodeEscape);
+        }
+        digit_count += 1;
+        value = 16 * value + digit as u32;
+    }
+
+    if value > 0x10FFFF || (value >= 0xD800 && value <= 0xDFFF) {
+        return Err(InvalidUnicodeEscapeChar);
+    }
+
+    Ok((Unit::Char(value), closing_pos + 1))
+}
+
+/// Returns the UTF-8 encoding of the given valid code point.
+const fn encode_utf8(c: u32) -> ([u8; 4], usize) {
+    if c < 0x80 {
+        ([c as u8, 0, 0, 0], 1)
+    } else if c < 0x800 {
+        ([0xC0 | (c >> 6) as u8, 0x80 | (c & 0x3F) as u8, 0, 0], 2)
+    } else if c < 0x10000 {
+        let bytes = [
+            0xE0 | (c >> 12) as u8,
+            0x80 | ((c >> 6) & 0x3F) as u8,
+            0x80 | (c & 0x3F) as u8,
+            0,
+        ];
+        (bytes, 3)
+    } else {
+        let bytes = [
+            0xF0 | (c >> 18) as u8,
+            0x80 | ((c >> 12) & 0x3F) as u8,
+            0x80 | ((c >> 6) & 0x3F) as u8,
+            0x80 | (c & 0x3F) as u8,
+        ];
+        (bytes, 4)
+    }
+}
+
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,103 @@
+use crate::{ByteStringLit, IntegerLit, StringLit};
+use super::*;
+
+
+/// All strings up to length 4 consisting of the given parts.
+fn all_inputs(alphabet: &[&str]) -> Vec<String> {
+    let mut out = vec![String::new()];
+    let mut last = vec![String::new()];
+    for _ in 0..4 {
+        last = last.iter()
+            .flat_map(|prefix| alphabet.iter().map(move |part| format!("{prefix}{part}")))
+            .collect();
+        out.extend(last.iter().cloned());
+    }
+    out
+}
+
+#[test]
+fn integer_agrees_with_runtime() {
+    let alphabet = ["0", "1", "7", "9", "a", "F", "x", "o", "b", "_", "e", "u", "8", ".", "é"];
+    for input in all_inputs(&alphabet) {
+        let expected = IntegerLit::parse(&*input)
+            .map_err(|e| e.kind)
+            .and_then(|lit| lit.value::<u128>().ok_or(IntegerOutOfRange));
+        assert_eq!(integer(&input, u128::MAX), expected, "for input {input:?}");
+    }
+}
+
+#[test]
+fn integer_values() {
+    assert_eq!(u8("0"), 0);
+    assert_eq!(u8("255"), 255);
+    assert_eq!(u8("0b1111_1111"), 255);
+    assert_eq!(i8("127"), 127);
+    assert_eq!(u16("0o777u16"), 0o777);
+    assert_eq!(u32("0xDEAD_beef"), 0xDEAD_BEEF);
+    assert_eq!(u64("0xFF_FF"), 0xFFFF);
+    assert_eq!(i64("9_223_372_036_854_775_807"), i64::MAX);
+    assert_eq!(u128("340282366920938463463374607431768211455"), u128::MAX);
+    assert_eq!(i128("0x7FFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF"), i128::MAX);
+    assert_eq!(usize("27usize"), 27);
+    assert_eq!(isize("3"), 3);
+
+    assert_eq!(integer("256", u8::MAX as u128), Err(IntegerOutOfRange));
+    assert_eq!(integer("128", i8::MAX as u128), Err(IntegerOutOfRange));
+    let too_large = "340282366920938463463374607431768211456";
+    assert_eq!(integer(too_large, u128::MAX), Err(IntegerOutOfRange));
+    assert_eq!(integer("0b102", u128::MAX), Err(InvalidDigit));
+    assert_eq!(integer("0x", u128::MAX), Err(NoDigits));
+    assert_eq!(integer("1e3", u128::MAX), Err(IntegerSuffixStartingWithE));
+    assert_eq!(integer("-1", u128::MAX), Err(DoesNotStartWithDigit));
+}
+
+#[test]
+#[should_panic(expected = "integer literal is out of range for its type")]
+fn integer_panics() {
+    u8("256");
+}
+
+#[test]
+fn strings_agree_with_runtime() {
+    let alphabet = [
+        "\"", "r", "#", "b", "\\", "x", "u", "{", "}", "F", "7", "n", "_", "\n", "\r", "\0", "é",
+    ];
+    for input in all_inputs(&alphabet) {
+        let expected = StringLit::parse(&*input).map(|lit| lit.value().len()).map_err(|e| e.kind);
+        let actual = scan_string::<0>(&input, false).map(|(_, len)| len);
+        assert_eq!(actual, expected, "for input {input:?}");
+
+        let expected = ByteStringLit::parse(&*input)
+            .map(|lit| lit.value().to_vec())
+            .map_err(|e| e.kind);
+        let actual = scan_string::<8>(&input, true).map(|(out, len)| out[..len].to_vec());
+        assert_eq!(actual, expected, "for input {input:?}");
+    }
+}
+
+#[test]
+fn string_values() {
+    const LEN: usize = string_len(r#""a\u{1F980}\t""#);
+    assert_eq!(LEN, 6);
+    assert_eq!(string_len(r#""""#), 0);
+    assert_eq!(string_len(r#""Grüße""#), "Grüße".len());
+    assert_eq!(string_len(r##"r#"a"b"#"##), 3);
+    assert_eq!(string_len("\"a\\\n   b\""), 2);
+    assert_eq!(string_len(r#""\u{E9}\u{20AC}\u{10FFFF}"suffix"#), 2 + 3 + 4);
+
+    assert_eq!(byte_string_len(r#"b"\xFF\0""#), 2);
+    assert_eq!(byte_string_len(r#"br"\xFF""#), 4);
+    assert_eq!(byte_string::<4>(r#"b"\x7FELF""#), *b"\x7FELF");
+    assert_eq!(byte_string::<0>(r###"br##""##"###), [0u8; 0]);
+
+    assert_eq!(scan_string::<0>(r#""\u{D800}""#, false).err(), Some(InvalidUnicodeEscapeChar));
+    assert_eq!(scan_string::<0>(r#""\u{110000}""#, false).err(), Some(InvalidUnicodeEscapeChar));
+    assert_eq!(scan_string::<0>(r#"b"é""#, true).err(), Some(NonAsciiInByteLiteral));
+    assert_eq!(scan_string::<0>(r#""abc"#, false).err(), Some(UnterminatedString));
+}
+
+#[test]
+#[should_panic(expected = "length of byte string literal does not match array length")]
+fn byte_string_wrong_length() {
+    byte_string::<3>(r#"b"abcd""#);
+}
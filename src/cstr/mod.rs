@@ -6,8 +6,8 @@ use std::{
 
 use crate::{
     err::{perr, ParseErrorKind::*},
-    escape::{scan_raw_string, unescape_string},
-    Buffer, ParseError,
+    escape::{scan_raw_string, unescape_string, EscapeContainer, RawChunks},
+    Buffer, ByteChunks, ParseError,
 };
 
 
@@ -37,13 +37,7 @@ impl<B: Buffer> CStringLit<B> {
     /// Parses the input as a (raw) byte string literal. Returns an error if the
     /// input is invalid or represents a different kind of literal.
     pub fn parse(input: B) -> Result<Self, ParseError> {
-        if input.is_empty() {
-            return Err(perr(None, Empty));
-        }
-        if !input.starts_with(r#"c""#) && !input.starts_with("cr") {
-            return Err(perr(None, InvalidCStringLiteralStart));
-        }
-
+        check_start(&input)?;
         let (value, num_hashes, start_suffix) = parse_impl(&input)?;
         Ok(Self { raw: input, value, num_hashes, start_suffix })
     }
@@ -59,6 +53,16 @@ impl<B: Buffer> CStringLit<B> {
         self.value
     }
 
+    /// Returns an iterator over the value of this literal (without the
+    /// trailing nul byte), in chunks taken directly from the raw input. Unicode
+    /// escapes are yielded as [`ByteChunk::Char`][crate::ByteChunk::Char].
+    /// To not build the unescaped value in the first place, use
+    /// [`parse_chunks`][CStringLit::parse_chunks].
+    pub fn chunks(&self) -> ByteChunks<'_> {
+        let content = &self.raw[inner_range(self.num_hashes, self.start_suffix)];
+        ByteChunks::new(RawChunks::new(content, self.is_raw_c_string(), true, true))
+    }
+
     /// The optional suffix. Returns `""` if the suffix is empty/does not exist.
     pub fn suffix(&self) -> &str {
         &(*self.raw)[self.start_suffix..]
@@ -89,7 +93,18 @@ fn inner_range(num_hashes: Option<u8>, start_suffix: usize) -> Range<usize> {
     }
 }
 
-impl CStringLit<&str> {
+impl<'a> CStringLit<&'a str> {
+    /// Checks that `input` is a valid (raw) C string literal exactly like
+    /// [`parse`][Self::parse] and returns an iterator over its value like
+    /// [`chunks`][Self::chunks] does. Unlike `parse`, this never allocates,
+    /// even if the literal contains escapes.
+    pub fn parse_chunks(input: &'a str) -> Result<ByteChunks<'a>, ParseError> {
+        check_start(input)?;
+        let (_, num_hashes, start_suffix) = scan::<()>(input)?;
+        let content = &input[inner_range(num_hashes, start_suffix)];
+        Ok(ByteChunks::new(RawChunks::new(content, num_hashes.is_some(), true, true)))
+    }
+
     /// Makes a copy of the underlying buffer and returns the owned version of
     /// `Self`.
     pub fn into_owned(self) -> CStringLit<String> {
@@ -111,15 +126,28 @@ impl<B: Buffer> fmt::Display for CStringLit<B> {
 
 /// Precondition: input has to start with either `b"` or `br`.
 #[inline(never)]
-fn parse_impl(input: &str) -> Result<(CString, Option<u8>, usize), ParseError> {
-    let (vec, num_hashes, start_suffix) = if input.starts_with("cr") {
+fn check_start(input: &str) -> Result<(), ParseError> {
+    if input.is_empty() {
+        return Err(perr(None, Empty));
+    }
+    if !input.starts_with(r#"c""#) && !input.starts_with("cr") {
+        return Err(perr(None, InvalidCStringLiteralStart));
+    }
+    Ok(())
+}
+
+fn scan<C: EscapeContainer>(input: &str) -> Result<(Option<C>, Option<u8>, usize), ParseError> {
+    if input.starts_with("cr") {
         scan_raw_string(input, 2, true, false)
-            .map(|(num, start_suffix)| (None, Some(num), start_suffix))?
+            .map(|(num, start_suffix)| (None, Some(num), start_suffix))
     } else {
-        unescape_string::<Vec<u8>>(input, 2, true, true, false)
-            .map(|(v, start_suffix)| (v, None, start_suffix))?
-    };
+        unescape_string::<C>(input, 2, true, true, false)
+            .map(|(v, start_suffix)| (v, None, start_suffix))
+    }
+}
 
+fn parse_impl(input: &str) -> Result<(CString, Option<u8>, usize), ParseError> {
+    let (vec, num_hashes, start_suffix) = scan::<Vec<u8>>(input)?;
 
     let inner_range = inner_range(num_hashes, start_suffix);
     let vec = vec.unwrap_or_else(|| input[inner_range].as_bytes().to_vec());
@@ -129,5 +157,13 @@ fn parse_impl(input: &str) -> Result<(CString, Option<u8>, usize), ParseError> {
 }
 
 
+/// Checks whether `input` is a valid C string literal, exactly like
+/// [`CStringLit::parse`], but without allocating.
+pub(crate) fn validate(input: &str) -> Result<(), ParseError> {
+    check_start(input)?;
+    scan::<()>(input).map(|_| ())
+}
+
+
 #[cfg(test)]
 mod tests;
@@ -1,7 +1,7 @@
 use std::ffi::CString;
 
 use crate::{
-    test_util::{assert_parse_ok_eq, assert_roundtrip},
+    test_util::{assert_parse_ok_eq, assert_roundtrip, count_allocations},
     CStringLit, Literal,
 };
 
@@ -220,3 +220,45 @@ fn invalid_escapes() {
     assert_err!(CStringLit, r#"c" \xaj""#, InvalidXEscape, 3..7);
     assert_err!(CStringLit, r#"c"\xjbbaz""#, InvalidXEscape, 2..6);
 }
+
+#[test]
+fn chunks() {
+    use crate::ByteChunk;
+
+    #[track_caller]
+    fn check(input: &str, expected: &[ByteChunk<'_>]) {
+        let lit = CStringLit::parse(input).unwrap();
+        let chunks = lit.chunks().collect::<Vec<_>>();
+        assert_eq!(chunks, expected, "for `{input}`");
+
+        // `parse_chunks` yields the same chunks without allocating
+        let (same, allocations) = count_allocations(|| {
+            CStringLit::parse_chunks(input).unwrap().eq(expected.iter().copied())
+        });
+        assert!(same, "`parse_chunks` differs from `chunks` for `{input}`");
+        assert_eq!(allocations, 0, "`parse_chunks` allocated for `{input}`");
+
+        let mut value = Vec::new();
+        for chunk in chunks {
+            match chunk {
+                ByteChunk::Verbatim(s) => value.extend_from_slice(s),
+                ByteChunk::Byte(b) => value.push(b),
+                ByteChunk::Char(c) => {
+                    value.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+                }
+            }
+        }
+        assert_eq!(value, lit.value().to_bytes());
+    }
+
+    check(r#"c"""#, &[]);
+    check(r#"c"foo"_suffix"#, &[ByteChunk::Verbatim(b"foo")]);
+    check(r#"c"a\xFF\u{E9}ü""#, &[
+        ByteChunk::Verbatim(b"a"),
+        ByteChunk::Byte(0xFF),
+        ByteChunk::Char('é'),
+        ByteChunk::Verbatim("ü".as_bytes()),
+    ]);
+    check("c\"foo\\\n    bar\"", &[ByteChunk::Verbatim(b"foo"), ByteChunk::Verbatim(b"bar")]);
+    check(r##"cr#"a\n"b"#"##, &[ByteChunk::Verbatim(br#"a\n"b"#)]);
+}
@@ -0,0 +1,53 @@
+/// How [`StringLit::value_dedented_with`][crate::StringLit::value_dedented_with]
+/// and [`ByteStringLit::value_dedented_with`][crate::ByteStringLit::value_dedented_with]
+/// remove indentation.
+///
+/// In both modes, the common indentation (spaces and tabs, each counting as
+/// one character) of all lines that do not consist only of whitespace is
+/// removed. Whitespace-only lines are shortened accordingly. `\` at the end
+/// of a line (string continue) is processed before, so lines joined that way
+/// count as one line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DedentMode {
+    /// Like the `indoc!` macro: the first line (the one with the opening
+    /// quote) is not considered when computing the indentation and is kept
+    /// as is. If it is empty, it is removed.
+    Indoc,
+
+    /// All lines are treated the same, including the first one, which is
+    /// never removed. Like Python's `textwrap.dedent`.
+    Unindent,
+}
+
+/// Removes the indentation from `s` as described by `mode`.
+pub(crate) fn dedent(s: &[u8], mode: DedentMode) -> Vec<u8> {
+    let indoc = mode == DedentMode::Indoc;
+    let s = if indoc && s.starts_with(b"\r\n") { &s[1..] } else { s };
+    let ignore_first_line = indoc && s.starts_with(b"\n");
+    let lines = || s.split(|&b| b == b'\n');
+
+    let indent = lines()
+        .skip(if indoc { 1 } else { 0 })
+        .filter_map(indentation)
+        .min()
+        .unwrap_or(0);
+
+    let mut out = Vec::with_capacity(s.len());
+    for (i, line) in lines().enumerate() {
+        if i > 1 || (i == 1 && !ignore_first_line) {
+            out.push(b'\n');
+        }
+        if i == 0 && indoc {
+            out.extend_from_slice(line);
+        } else if line.len() > indent {
+            out.extend_from_slice(&line[indent..]);
+        }
+    }
+    out
+}
+
+/// Returns the number of leading spaces and tabs, or `None` if the line only
+/// consists of whitespace.
+fn indentation(line: &[u8]) -> Option<usize> {
+    line.iter().position(|&b| b != b' ' && b != b'\t')
+}
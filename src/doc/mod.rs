@@ -0,0 +1,168 @@
+//! Extracting documentation from `doc` attributes.
+//!
+//! Doc comments reach proc macros as attributes: `/// text` becomes
+//! `#[doc = r" text"]` and `//! text` becomes `#![doc = r" text"]`. The
+//! string literal may also be non-raw or come from a `macro_rules!` macro
+//! (e.g. `#[doc = $doc]`). [`collect`] finds all such attributes in a token
+//! stream and returns their values, each with the span of its literal.
+//! [`dedent`] and [`join`] then turn these into one string, like rustdoc does.
+//!
+//! ```
+//! # #[cfg(feature = "proc-macro2")]
+//! # fn main() {
+//! use litrs::doc;
+//!
+//! let input: proc_macro2::TokenStream = r#"
+//!     #[doc = r" Does a thing."]
+//!     #[doc = r""]
+//!     #[doc = "     let x = \"indented\";"]
+//!     #[derive(Debug)]
+//!     pub struct Foo;
+//! "#.parse().unwrap();
+//!
+//! let docs = doc::collect(input);
+//! assert_eq!(docs.len(), 3);
+//! assert_eq!(*docs[0], " Does a thing.");
+//!
+//! let text = doc::join(&doc::dedent(docs));
+//! assert_eq!(text, "Does a thing.\n\n    let x = \"indented\";");
+//! # }
+//! # #[cfg(not(feature = "proc-macro2"))]
+//! # fn main() {}
+//! ```
+
+use std::convert::TryFrom;
+
+use crate::{Spanned, StringLit};
+
+
+/// Returns the values of all `doc` attributes (`#[doc = "..."]` and
+/// `#![doc = "..."]`) in `attrs`, in order.
+///
+/// `attrs` can be a `proc_macro::TokenStream` or, with the `proc-macro2`
+/// feature, a `proc_macro2::TokenStream`. Only top-level attributes are
+/// considered and all other tokens are ignored, so `attrs` can be the whole
+/// input of a derive macro. `doc` attributes whose value is not a string
+/// literal (e.g. `#[doc = include_str!("README.md")]`) or that have a
+/// different form (e.g. `#[doc(hidden)]`) are skipped as well.
+///
+/// The values are not modified: doc comments usually start with a space. See
+/// [`dedent`] and [`join`].
+pub fn collect(attrs: impl DocAttrs) -> Vec<Spanned<String>> {
+    attrs.collect_docs()
+}
+
+/// Removes the common indentation from all lines of all docs, like rustdoc
+/// does. Lines only consisting of whitespace are ignored when determining the
+/// indentation. Spaces and tabs both count as one character of indentation.
+pub fn dedent(docs: Vec<Spanned<String>>) -> Vec<Spanned<String>> {
+    let indent = docs.iter()
+        .flat_map(|doc| doc.split('\n'))
+        .filter(|line| !line.trim().is_empty())
+        .map(indentation)
+        .min()
+        .unwrap_or(0);
+    if indent == 0 {
+        return docs;
+    }
+
+    docs.into_iter()
+        .map(|doc| doc.map(|s| {
+            let mut out = String::with_capacity(s.len());
+            for (i, line) in s.split('\n').enumerate() {
+                if i > 0 {
+                    out.push('\n');
+                }
+                out.push_str(&line[indentation(line).min(indent)..]);
+            }
+            out
+        }))
+        .collect()
+}
+
+/// Joins all docs with newlines, like rustdoc does. Use [`dedent`] first to
+/// remove the space that usually follows `///`.
+pub fn join(docs: &[Spanned<String>]) -> String {
+    let mut out = String::new();
+    for (i, doc) in docs.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        out.push_str(doc);
+    }
+    out
+}
+
+/// Returns the number of leading spaces and tabs.
+fn indentation(line: &str) -> usize {
+    line.bytes().take_while(|&b| b == b' ' || b == b'\t').count()
+}
+
+/// Token streams that `doc` attributes can be collected from via
+/// [`collect`]. This trait is sealed and cannot be implemented outside of
+/// this crate. The trait's methods are implementation detail of this library
+/// and are not subject to semver.
+pub trait DocAttrs: self::sealed::Sealed {
+    #[doc(hidden)]
+    fn collect_docs(self) -> Vec<Spanned<String>>;
+}
+
+pub(crate) mod sealed {
+    pub trait Sealed {}
+}
+
+macro_rules! impl_doc_attrs {
+    ([$($prefix:tt)*] => ) => {
+        impl sealed::Sealed for $($prefix)* TokenStream {}
+        impl DocAttrs for $($prefix)* TokenStream {
+            fn collect_docs(self) -> Vec<Spanned<String>> {
+                use $($prefix)* {Delimiter, TokenTree};
+
+                let mut docs = Vec::new();
+                let mut tokens = self.into_iter().peekable();
+                while let Some(tt) = tokens.next() {
+                    match tt {
+                        TokenTree::Punct(p) if p.as_char() == '#' => {}
+                        _ => continue,
+                    }
+                    if matches!(tokens.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '!') {
+                        tokens.next();
+                    }
+                    let group = match tokens.peek() {
+                        Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Bracket => {
+                            g.stream()
+                        }
+                        _ => continue,
+                    };
+                    tokens.next();
+
+                    // Only `doc = <string literal>`, nothing else.
+                    let mut inner = group.into_iter();
+                    match (inner.next(), inner.next(), inner.next(), inner.next()) {
+                        (
+                            Some(TokenTree::Ident(name)),
+                            Some(TokenTree::Punct(eq)),
+                            Some(value),
+                            None,
+                        ) if name.to_string().as_str() == "doc" && eq.as_char() == '=' => {
+                            if let Ok(lit) = Spanned::<StringLit<String>>::try_from(value) {
+                                docs.push(lit.map(StringLit::into_value));
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+
+                docs
+            }
+        }
+    };
+}
+
+impl_doc_attrs!([proc_macro::] => );
+#[cfg(feature = "proc-macro2")]
+impl_doc_attrs!([proc_macro2::] => );
+
+
+#[cfg(all(test, feature = "proc-macro2"))]
+mod tests;
@@ -0,0 +1,105 @@
+use super::{collect, dedent, join};
+
+
+// ===== Utility functions =======================================================================
+
+#[track_caller]
+fn values(input: &str) -> Vec<String> {
+    let stream: proc_macro2::TokenStream = input.parse().expect("invalid token stream");
+    collect(stream).into_iter().map(|doc| doc.into_inner()).collect()
+}
+
+#[track_caller]
+fn joined(input: &str) -> String {
+    let stream: proc_macro2::TokenStream = input.parse().expect("invalid token stream");
+    join(&dedent(collect(stream)))
+}
+
+
+// ===== Actual tests ============================================================================
+
+#[test]
+fn empty() {
+    assert!(values("").is_empty());
+    assert!(values("struct Foo;").is_empty());
+    assert_eq!(joined(""), "");
+}
+
+#[test]
+fn doc_comments() {
+    // The tokenizer turns doc comments into attributes.
+    let input = "
+        //! Inner
+        /// Outer
+        ///   indented
+        /** block */
+        fn foo() {}
+    ";
+    assert_eq!(values(input), [" Inner", " Outer", "   indented", " block "]);
+    assert_eq!(joined(input), "Inner\nOuter\n  indented\nblock ");
+}
+
+#[test]
+fn attributes() {
+    assert_eq!(values(r#"#[doc = "foo"]"#), ["foo"]);
+    assert_eq!(values(r#"#![doc = "foo"]"#), ["foo"]);
+    assert_eq!(values(r##"#[doc = r#"a "quote""#]"##), [r#"a "quote""#]);
+    assert_eq!(values(r#"#[doc = "tab\tnewline\n\u{1F980}"]"#), ["tab\tnewline\n🦀"]);
+    assert_eq!(
+        values(r#"#[derive(Debug)] #[doc = "a"] #[serde(rename = "x")] #[doc = "b"] struct X;"#),
+        ["a", "b"],
+    );
+}
+
+#[test]
+fn skipped() {
+    assert!(values("#[doc(hidden)]").is_empty());
+    assert!(values(r#"#[doc(alias = "x")]"#).is_empty());
+    assert!(values(r#"#[doc = include_str!("README.md")]"#).is_empty());
+    assert!(values("#[doc = 3]").is_empty());
+    assert!(values(r#"#[doc = b"x"]"#).is_empty());
+    assert!(values(r#"#[docs = "x"]"#).is_empty());
+    assert!(values(r#"#[cfg_attr(feature = "x", doc = "x")]"#).is_empty());
+    assert!(values(r#"# doc = "x""#).is_empty());
+    assert!(values(r#"#! doc = "x""#).is_empty());
+
+    // Nested attributes, e.g. on fields, are not collected.
+    assert_eq!(values(r#"#[doc = "a"] struct X { #[doc = "b"] b: u8 }"#), ["a"]);
+}
+
+#[test]
+fn invisible_group() {
+    use proc_macro2::{
+        Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree,
+    };
+
+    let lit = TokenTree::Literal(Literal::string("from macro"));
+    let value = Group::new(Delimiter::None, lit.into());
+    let attr: TokenStream = vec![
+        TokenTree::Ident(Ident::new("doc", Span::call_site())),
+        TokenTree::Punct(Punct::new('=', Spacing::Alone)),
+        TokenTree::Group(value),
+    ].into_iter().collect();
+    let stream: TokenStream = vec![
+        TokenTree::Punct(Punct::new('#', Spacing::Alone)),
+        TokenTree::Group(Group::new(Delimiter::Bracket, attr)),
+    ].into_iter().collect();
+
+    let docs = collect(stream);
+    assert_eq!(docs.len(), 1);
+    assert_eq!(*docs[0], "from macro");
+}
+
+#[test]
+fn dedent_and_join() {
+    assert_eq!(joined(r#"#[doc = "a"] #[doc = "b"]"#), "a\nb");
+    assert_eq!(joined(r#"#[doc = "  a"] #[doc = "    b"]"#), "a\n  b");
+    assert_eq!(joined(r#"#[doc = "\ta"] #[doc = "\t\tb"]"#), "a\n\tb");
+
+    // Whitespace only lines do not count, but are dedented as far as possible.
+    assert_eq!(joined(r#"#[doc = "   a"] #[doc = ""] #[doc = " "] #[doc = "     "]"#), "a\n\n\n  ");
+
+    // Multi-line values are dedented per line.
+    assert_eq!(joined(r#"#[doc = "\n    a\n      b\n"]"#), "\na\n  b\n");
+    assert_eq!(joined(r#"#[doc = " x"] #[doc = "\n    a"]"#), "x\n\n   a");
+}
@@ -0,0 +1,278 @@
+use std::io::{self, Write};
+
+use crate::{escape::raw_string_hashes, ByteStringLit};
+
+
+/// Options to configure how byte string literals are generated from binary
+/// data by [`ByteStringLit::from_bytes_with`] and
+/// [`ByteStringLit::write_bytes_with`].
+///
+/// ```
+/// use litrs::{ByteStringLit, EmitOptions};
+///
+/// let options = EmitOptions::new().max_line_len(10);
+/// let lit = ByteStringLit::from_bytes_with(b"\x00hello world", &options);
+/// assert_eq!(lit.raw_input(), "b\"\\0hello\\\n\\x20world\"");
+/// assert_eq!(lit.value(), b"\x00hello world");
+///
+/// let options = EmitOptions::new().prefer_raw(true);
+/// let lit = ByteStringLit::from_bytes_with(br#"say "hi""#, &options);
+/// assert_eq!(lit.raw_input(), r###"br#"say "hi""#"###);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EmitOptions {
+    max_line_len: Option<usize>,
+    prefer_raw: bool,
+    array_fallback: bool,
+}
+
+impl EmitOptions {
+    /// Creates the default options: a single line `b"..."` literal.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits the length of lines (in bytes, without the newline). Long
+    /// byte string literals are split with `\` + newline, which is not part
+    /// of the value. Lines can only be longer if a single escape does not
+    /// fit, i.e. if the limit is smaller than 5.
+    pub fn max_line_len(mut self, len: usize) -> Self {
+        self.max_line_len = Some(len);
+        self
+    }
+
+    /// Emits a raw byte string literal (e.g. `br#"..."#`) if possible. This
+    /// is the case if the data only consists of printable ASCII characters,
+    /// newlines and tabs, and, with [`max_line_len`][Self::max_line_len],
+    /// no line of the data is too long, as raw literals cannot be split.
+    pub fn prefer_raw(mut self, prefer_raw: bool) -> Self {
+        self.prefer_raw = prefer_raw;
+        self
+    }
+
+    /// Lets [`ByteStringLit::write_bytes_with`] emit an array expression
+    /// like `[0x00, 0xFF]` instead of a byte string literal if more than half
+    /// of the bytes would need a `\x` escape. Note that the array is a
+    /// `[u8; N]`, while a byte string literal is a `&[u8; N]`.
+    pub fn array_fallback(mut self, array_fallback: bool) -> Self {
+        self.array_fallback = array_fallback;
+        self
+    }
+}
+
+impl ByteStringLit<String> {
+    /// Creates a byte string literal with the given value, formatted
+    /// according to the given options. [`EmitOptions::array_fallback`] is
+    /// ignored, as an array is not a literal.
+    pub fn from_bytes_with(data: &[u8], options: &EmitOptions) -> Self {
+        let mut raw = Vec::with_capacity(data.len() + 3);
+        let options = EmitOptions { array_fallback: false, ..options.clone() };
+        Self::write_bytes_with(data, &options, &mut raw).expect("writing to `Vec` cannot fail");
+
+        let raw = String::from_utf8(raw).expect("bug: emitted non-ASCII byte string literal");
+        ByteStringLit::parse(raw).unwrap_or_else(|e| {
+            unreachable!("bug: `from_bytes_with` created invalid literal: {}", e)
+        })
+    }
+
+    /// Writes a byte string literal (or array expression, see
+    /// [`EmitOptions::array_fallback`]) with the given value to `w`, formatted
+    /// according to the given options. This is meant for build scripts
+    /// embedding large binary files: the output is buffered internally and
+    /// streamed to `w`, so the literal never exists in memory as a whole.
+    ///
+    /// ```
+    /// use litrs::{ByteStringLit, EmitOptions};
+    ///
+    /// let mut out = Vec::new();
+    /// let options = EmitOptions::new().array_fallback(true);
+    /// ByteStringLit::write_bytes_with(&[0xDE, 0xAD, b'!'], &options, &mut out).unwrap();
+    /// assert_eq!(out, b"[0xDE, 0xAD, 0x21]");
+    /// ```
+    pub fn write_bytes_with<W: Write>(
+        data: &[u8],
+        options: &EmitOptions,
+        w: W,
+    ) -> io::Result<()> {
+        let mut out = LineWriter {
+            inner: w,
+            buf: Vec::with_capacity(BUF_SIZE),
+            col: 0,
+        };
+
+        let hashes = if options.prefer_raw { raw_hashes(data, options) } else { None };
+        if let Some(hashes) = hashes {
+            out.raw(b"br")?;
+            out.repeat(b'#', hashes)?;
+            out.raw(b"\"")?;
+            out.raw(data)?;
+            out.raw(b"\"")?;
+            out.repeat(b'#', hashes)?;
+        } else if options.array_fallback && is_mostly_binary(data) {
+            write_array(data, options.max_line_len, &mut out)?;
+        } else {
+            write_escaped(data, options.max_line_len, &mut out)?;
+        }
+
+        out.flush()
+    }
+}
+
+/// Returns the number of `#` for a raw byte string literal with the given
+/// value, or `None` if it cannot (or should not) be a raw literal.
+fn raw_hashes(data: &[u8], options: &EmitOptions) -> Option<usize> {
+    if !data.iter().all(|&b| b == b'\n' || b == b'\t' || (b' '..=b'~').contains(&b)) {
+        return None;
+    }
+    let hashes = raw_string_hashes(std::str::from_utf8(data).ok()?)?;
+    if hashes > u8::MAX as usize {
+        return None;
+    }
+
+    if let Some(max) = options.max_line_len {
+        let num_lines = data.split(|&b| b == b'\n').count();
+        let too_long = data.split(|&b| b == b'\n').enumerate().any(|(i, line)| {
+            let start = if i == 0 { 3 + hashes } else { 0 };
+            let end = if i == num_lines - 1 { 1 + hashes } else { 0 };
+            start + line.len() + end > max
+        });
+        if too_long {
+            return None;
+        }
+    }
+
+    Some(hashes)
+}
+
+/// Returns whether more than half of the bytes need a `\x` escape.
+fn is_mostly_binary(data: &[u8]) -> bool {
+    data.iter().filter(|&&b| needs_hex(b)).count() > data.len() / 2
+}
+
+/// Returns whether the byte can only be written as `\x` escape.
+fn needs_hex(b: u8) -> bool {
+    !matches!(b, b' '..=b'~' | b'\n' | b'\r' | b'\t' | b'\0')
+}
+
+fn write_escaped<W: Write>(
+    data: &[u8],
+    max_line_len: Option<usize>,
+    out: &mut LineWriter<W>,
+) -> io::Result<()> {
+    out.raw(b"b\"")?;
+    let mut hex = *b"\\x00";
+    for b in data {
+        let escaped: &[u8] = match *b {
+            b'"' => b"\\\"",
+            b'\\' => b"\\\\",
+            b'\n' => b"\\n",
+            b'\r' => b"\\r",
+            b'\t' => b"\\t",
+            b'\0' => b"\\0",
+            c if !needs_hex(c) => std::slice::from_ref(b),
+            b => {
+                hex[2] = HEX_DIGITS[(b >> 4) as usize];
+                hex[3] = HEX_DIGITS[(b & 0xF) as usize];
+                &hex
+            }
+        };
+
+        // Reserve one byte for the `\` of the string continue.
+        match max_line_len {
+            Some(max) if out.col > 0 && out.col + escaped.len() + 1 > max => {
+                out.raw(b"\\\n")?;
+
+                // A space at the start of the line would be skipped as part
+                // of the string continue, so it has to be escaped.
+                if *b == b' ' {
+                    out.raw(b"\\x20")?;
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        out.raw(escaped)?;
+    }
+    out.raw(b"\"")
+}
+
+fn write_array<W: Write>(
+    data: &[u8],
+    max_line_len: Option<usize>,
+    out: &mut LineWriter<W>,
+) -> io::Result<()> {
+    let mut entry = *b"0x00,";
+    match max_line_len {
+        // Formatted like rustfmt would: one line if it fits, otherwise one
+        // indented block with as many entries per line as fit.
+        Some(max) if data.len() * 6 > max => {
+            out.raw(b"[\n")?;
+            for &b in data {
+                entry[2] = HEX_DIGITS[(b >> 4) as usize];
+                entry[3] = HEX_DIGITS[(b & 0xF) as usize];
+                if out.col == 0 {
+                    out.raw(b"    ")?;
+                } else if out.col + 1 + entry.len() > max {
+                    out.raw(b"\n    ")?;
+                } else {
+                    out.raw(b" ")?;
+                }
+                out.raw(&entry)?;
+            }
+            out.raw(b"\n]")
+        }
+        _ => {
+            out.raw(b"[")?;
+            for (i, &b) in data.iter().enumerate() {
+                entry[2] = HEX_DIGITS[(b >> 4) as usize];
+                entry[3] = HEX_DIGITS[(b & 0xF) as usize];
+                if i > 0 {
+                    out.raw(b" ")?;
+                }
+                let end = if i == data.len() - 1 { entry.len() - 1 } else { entry.len() };
+                out.raw(&entry[..end])?;
+            }
+            out.raw(b"]")
+        }
+    }
+}
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+const BUF_SIZE: usize = 8 * 1024;
+
+/// Buffers output and keeps track of the current column.
+struct LineWriter<W> {
+    inner: W,
+    buf: Vec<u8>,
+    col: usize,
+}
+
+impl<W: Write> LineWriter<W> {
+    fn raw(&mut self, s: &[u8]) -> io::Result<()> {
+        match s.iter().rposition(|&b| b == b'\n') {
+            Some(pos) => self.col = s.len() - pos - 1,
+            None => self.col += s.len(),
+        }
+
+        if self.buf.len() + s.len() > BUF_SIZE {
+            self.inner.write_all(&self.buf)?;
+            self.buf.clear();
+        }
+        if s.len() > BUF_SIZE {
+            self.inner.write_all(s)
+        } else {
+            self.buf.extend_from_slice(s);
+            Ok(())
+        }
+    }
+
+    fn repeat(&mut self, b: u8, n: usize) -> io::Result<()> {
+        (0..n).try_for_each(|_| self.raw(&[b]))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.write_all(&self.buf)?;
+        self.buf.clear();
+        self.inner.flush()
+    }
+}
@@ -3,37 +3,71 @@ use std::{fmt, ops::Range};
 
 /// An error signaling that a different kind of token was expected. Returned by
 /// the various `TryFrom` impls.
+///
+/// You can also create it yourself via [`InvalidToken::new`], e.g. to report
+/// errors in your proc macro with the same messages. If several kinds of
+/// tokens are accepted, pass a slice of [`TokenKind`]s:
+///
+/// ```
+/// # #[cfg(feature = "proc-macro2")]
+/// # fn main() {
+/// use litrs::{Expected, InvalidToken, TokenKind};
+///
+/// let span = proc_macro2::Span::call_site();
+/// let expected = &[TokenKind::StringLit, TokenKind::IntegerLit];
+/// let e = InvalidToken::new2(expected, TokenKind::Group, span);
+/// assert_eq!(e.to_string(), "expected a string or integer literal, but found a group");
+///
+/// let e = InvalidToken::new2(Expected::Custom("a type"), TokenKind::Punct, span);
+/// assert_eq!(e.to_string(), "expected a type, but found a punctuation character");
+/// # }
+/// # #[cfg(not(feature = "proc-macro2"))]
+/// # fn main() {}
+/// ```
 #[derive(Debug, Clone, Copy)]
 pub struct InvalidToken {
-    pub(crate) expected: TokenKind,
+    pub(crate) expected: Expected,
     pub(crate) actual: TokenKind,
     pub(crate) span: Span,
 }
 
 impl InvalidToken {
+    /// Creates a new error with the given `proc_macro` span. `expected` can
+    /// be a single [`TokenKind`], a slice of them, or a custom
+    /// [`Expected`] value.
+    pub fn new(
+        expected: impl Into<Expected>,
+        actual: TokenKind,
+        span: proc_macro::Span,
+    ) -> Self {
+        Self { expected: expected.into(), actual, span: span.into() }
+    }
+
+    /// Like [`new`][Self::new], but with a `proc_macro2` span.
+    #[cfg(feature = "proc-macro2")]
+    pub fn new2(
+        expected: impl Into<Expected>,
+        actual: TokenKind,
+        span: proc_macro2::Span,
+    ) -> Self {
+        Self { expected: expected.into(), actual, span: span.into() }
+    }
+
+    /// Returns what kind of token was expected.
+    pub fn expected(&self) -> Expected {
+        self.expected
+    }
+
+    /// Returns the kind of token that was found instead.
+    pub fn actual(&self) -> TokenKind {
+        self.actual
+    }
+
     /// Returns a token stream representing `compile_error!("msg");` where
     /// `"msg"` is the output of `self.to_string()`. **Panics if called outside
     /// of a proc-macro context!**
     pub fn to_compile_error(&self) -> proc_macro::TokenStream {
-        use proc_macro::{Delimiter, Group, Ident, Punct, Spacing, TokenTree};
-
-        let span = match self.span {
-            Span::One(s) => s,
-            #[cfg(feature = "proc-macro2")]
-            Span::Two(s) => s.unwrap(),
-        };
-        let msg = self.to_string();
-        let tokens = vec![
-            TokenTree::from(Ident::new("compile_error", span)),
-            TokenTree::from(Punct::new('!', Spacing::Alone)),
-            TokenTree::from(Group::new(
-                Delimiter::Parenthesis,
-                TokenTree::from(proc_macro::Literal::string(&msg)).into(),
-            )),
-        ];
-
-
-        tokens.into_iter().map(|mut t| { t.set_span(span); t }).collect()
+        compile_error(self.span, &self.to_string())
     }
 
     /// Like [`to_compile_error`][Self::to_compile_error], but returns a token
@@ -41,66 +75,212 @@ impl InvalidToken {
     /// context.
     #[cfg(feature = "proc-macro2")]
     pub fn to_compile_error2(&self) -> proc_macro2::TokenStream {
-        use proc_macro2::{Delimiter, Group, Ident, Punct, Spacing, TokenTree};
-
-        let span = match self.span {
-            Span::One(s) => proc_macro2::Span::from(s),
-            Span::Two(s) => s,
-        };
-        let msg = self.to_string();
-        let tokens = vec![
-            TokenTree::from(Ident::new("compile_error", span)),
-            TokenTree::from(Punct::new('!', Spacing::Alone)),
-            TokenTree::from(Group::new(
-                Delimiter::Parenthesis,
-                TokenTree::from(proc_macro2::Literal::string(&msg)).into(),
-            )),
-        ];
-
-
-        tokens.into_iter().map(|mut t| { t.set_span(span); t }).collect()
+        compile_error2(self.span, &self.to_string())
     }
 }
 
+/// Returns a token stream representing `compile_error!("msg");` with all
+/// tokens having the given span. Panics outside of a proc-macro context.
+pub(crate) fn compile_error(span: Span, msg: &str) -> proc_macro::TokenStream {
+    use proc_macro::{Delimiter, Group, Ident, Punct, Spacing, TokenTree};
+
+    let span = match span {
+        Span::One(s) => s,
+        #[cfg(feature = "proc-macro2")]
+        Span::Two(s) => s.unwrap(),
+    };
+    let tokens = vec![
+        TokenTree::from(Ident::new("compile_error", span)),
+        TokenTree::from(Punct::new('!', Spacing::Alone)),
+        TokenTree::from(Group::new(
+            Delimiter::Parenthesis,
+            TokenTree::from(proc_macro::Literal::string(msg)).into(),
+        )),
+    ];
+
+
+    tokens.into_iter().map(|mut t| { t.set_span(span); t }).collect()
+}
+
+/// Like `compile_error`, but for `proc_macro2`.
+#[cfg(feature = "proc-macro2")]
+pub(crate) fn compile_error2(span: Span, msg: &str) -> proc_macro2::TokenStream {
+    use proc_macro2::{Delimiter, Group, Ident, Punct, Spacing, TokenTree};
+
+    let span = match span {
+        Span::One(s) => proc_macro2::Span::from(s),
+        Span::Two(s) => s,
+    };
+    let tokens = vec![
+        TokenTree::from(Ident::new("compile_error", span)),
+        TokenTree::from(Punct::new('!', Spacing::Alone)),
+        TokenTree::from(Group::new(
+            Delimiter::Parenthesis,
+            TokenTree::from(proc_macro2::Literal::string(msg)).into(),
+        )),
+    ];
+
+
+    tokens.into_iter().map(|mut t| { t.set_span(span); t }).collect()
+}
+
 impl std::error::Error for InvalidToken {}
 
 impl fmt::Display for InvalidToken {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fn kind_desc(kind: TokenKind) -> &'static str {
-            match kind {
-                TokenKind::Punct => "a punctuation character",
-                TokenKind::Ident => "an identifier",
-                TokenKind::Group => "a group",
-                TokenKind::Literal => "a literal",
-                TokenKind::BoolLit => "a bool literal (`true` or `false`)",
-                TokenKind::ByteLit => "a byte literal (e.g. `b'r')",
-                TokenKind::ByteStringLit => r#"a byte string literal (e.g. `b"fox"`)"#,
-                TokenKind::CharLit => "a character literal (e.g. `'P'`)",
-                TokenKind::FloatLit => "a float literal (e.g. `3.14`)",
-                TokenKind::IntegerLit => "an integer literal (e.g. `27`)",
-                TokenKind::StringLit => r#"a string literal (e.g. "Ferris")"#,
-                TokenKind::CStringLit => r#"a C string literal (e.g. c"Ferris")"#,
-            }
-        }
-
-        write!(f, "expected {}, but found {}", kind_desc(self.expected), kind_desc(self.actual))
+        write!(f, "expected {}, but found {}", self.expected, self.actual.description())
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub(crate) enum TokenKind {
+/// A kind of token, used in [`InvalidToken`] to describe what token was
+/// expected and what token was found.
+///
+/// The kind of a token tree can be obtained via `From<&TokenTree>` (for
+/// `proc_macro` and `proc_macro2`). Note that the identifiers `true` and
+/// `false` are considered `BoolLit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TokenKind {
+    /// A punctuation character, e.g. `+`.
     Punct,
+    /// An identifier, e.g. `foo`.
     Ident,
+    /// A group, e.g. `(...)`.
     Group,
+    /// Any literal.
     Literal,
+    /// A bool literal, i.e. `true` or `false`.
     BoolLit,
+    /// A byte literal, e.g. `b'r'`.
     ByteLit,
+    /// A byte string literal, e.g. `b"fox"`.
     ByteStringLit,
+    /// A character literal, e.g. `'P'`.
     CharLit,
+    /// A float literal, e.g. `3.14`.
     FloatLit,
+    /// An integer literal, e.g. `27`.
     IntegerLit,
+    /// A string literal, e.g. `"Ferris"`.
     StringLit,
+    /// A C string literal, e.g. `c"Ferris"`.
     CStringLit,
+
+    /// The end of the input, e.g. an empty token stream.
+    End,
+}
+
+impl TokenKind {
+    /// Returns a description of this kind for error messages, e.g. `"an
+    /// integer literal (e.g. `27`)"`.
+    pub fn description(self) -> &'static str {
+        match self {
+            TokenKind::Punct => "a punctuation character",
+            TokenKind::Ident => "an identifier",
+            TokenKind::Group => "a group",
+            TokenKind::Literal => "a literal",
+            TokenKind::BoolLit => "a bool literal (`true` or `false`)",
+            TokenKind::ByteLit => "a byte literal (e.g. `b'r')",
+            TokenKind::ByteStringLit => r#"a byte string literal (e.g. `b"fox"`)"#,
+            TokenKind::CharLit => "a character literal (e.g. `'P'`)",
+            TokenKind::FloatLit => "a float literal (e.g. `3.14`)",
+            TokenKind::IntegerLit => "an integer literal (e.g. `27`)",
+            TokenKind::StringLit => r#"a string literal (e.g. "Ferris")"#,
+            TokenKind::CStringLit => r#"a C string literal (e.g. c"Ferris")"#,
+            TokenKind::End => "the end of input",
+        }
+    }
+
+    /// For specific literal kinds, returns the name without article and
+    /// "literal", e.g. `"integer"`.
+    fn literal_name(self) -> Option<&'static str> {
+        match self {
+            TokenKind::BoolLit => Some("bool"),
+            TokenKind::ByteLit => Some("byte"),
+            TokenKind::ByteStringLit => Some("byte string"),
+            TokenKind::CharLit => Some("character"),
+            TokenKind::FloatLit => Some("float"),
+            TokenKind::IntegerLit => Some("integer"),
+            TokenKind::StringLit => Some("string"),
+            TokenKind::CStringLit => Some("C string"),
+            _ => None,
+        }
+    }
+}
+
+/// What kind of token was expected, as stored in [`InvalidToken`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Expected {
+    /// A single kind of token.
+    Kind(TokenKind),
+
+    /// One of several kinds of tokens. Shown as "expected a string or
+    /// integer literal" or "expected an identifier, a literal or a group".
+    OneOf(&'static [TokenKind]),
+
+    /// A custom description of the expected token, e.g. `"a type"`. It is
+    /// shown as "expected a type, but found ...".
+    Custom(&'static str),
+}
+
+impl From<TokenKind> for Expected {
+    fn from(src: TokenKind) -> Self {
+        Self::Kind(src)
+    }
+}
+
+impl From<&'static [TokenKind]> for Expected {
+    fn from(src: &'static [TokenKind]) -> Self {
+        Self::OneOf(src)
+    }
+}
+
+impl<const N: usize> From<&'static [TokenKind; N]> for Expected {
+    fn from(src: &'static [TokenKind; N]) -> Self {
+        Self::OneOf(src)
+    }
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kinds = match *self {
+            Expected::Kind(kind) | Expected::OneOf(&[kind]) => {
+                return f.write_str(kind.description());
+            }
+            Expected::Custom(s) => return f.write_str(s),
+            Expected::OneOf(&[]) => return f.write_str("nothing"),
+            Expected::OneOf(kinds) => kinds,
+        };
+
+        // If only specific literals are expected, we merge them into one
+        // phrase, e.g. "a string, integer or float literal".
+        let only_literals = kinds.iter().all(|k| k.literal_name().is_some());
+        for (i, kind) in kinds.iter().enumerate() {
+            if i == kinds.len() - 1 {
+                f.write_str(" or ")?;
+            } else if i > 0 {
+                f.write_str(", ")?;
+            }
+
+            match kind.literal_name() {
+                Some(name) if only_literals && i > 0 => f.write_str(name)?,
+                Some(name) => {
+                    let article = if name.starts_with('i') { "an" } else { "a" };
+                    write!(f, "{} {}", article, name)?;
+                    if !only_literals {
+                        f.write_str(" literal")?;
+                    }
+                }
+                None => f.write_str(kind.description())?,
+            }
+        }
+        if only_literals {
+            f.write_str(" literal")?;
+        }
+
+        Ok(())
+    }
 }
 
 /// Unfortunately, we have to deal with both cases.
@@ -151,7 +331,9 @@ impl From<proc_macro2::Span> for Span {
 ///
 /// While this library does its best to emit sensible and precise errors, and to
 /// keep the returned errors as stable as possible, full stability cannot be
-/// guaranteed.
+/// guaranteed. What is guaranteed is that [`code`][Self::code] always returns
+/// the same code for the same kind of error. See [`explain`][crate::explain]
+/// for long-form explanations of those codes.
 #[derive(Debug, Clone)]
 pub struct ParseError {
     pub(crate) span: Option<Range<usize>>,
@@ -334,15 +516,27 @@ pub(crate) enum ParseErrorKind {
     /// Integer suffixes cannot start with `e` or `E` as this conflicts with the
     /// grammar for float literals.
     IntegerSuffixStartingWithE,
+
+    /// A suffix that is not allowed by the given `ParseOptions`.
+    DisallowedSuffix,
+
+    /// In strict mode: integer value does not fit into the type given by the
+    /// suffix (or `u128` if there is no suffix).
+    IntegerOutOfRange,
+
+    /// In strict mode: float value is too large for the type given by the
+    /// suffix (or `f64` if there is no suffix).
+    FloatOutOfRange,
 }
 
 impl std::error::Error for ParseError {}
 
-impl fmt::Display for ParseError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+impl ParseErrorKind {
+    /// A short description of the error, without span information.
+    pub(crate) const fn description(self) -> &'static str {
         use ParseErrorKind::*;
 
-        let description = match self.kind {
+        match self {
             Empty => "input is empty",
             UnexpectedChar => "unexpected character",
             InvalidLiteral => "invalid literal",
@@ -384,9 +578,16 @@ impl fmt::Display for ParseError {
             InvalidSuffix => "literal suffix is not a valid identifier",
             UnexpectedIntegerLit => "expected float literal, but found integer",
             IntegerSuffixStartingWithE => "integer literal suffix must not start with 'e' or 'E'",
-        };
+            DisallowedSuffix => "literal suffix is not allowed here",
+            IntegerOutOfRange => "integer literal is out of range for its type",
+            FloatOutOfRange => "float literal is out of range for its type",
+        }
+    }
+}
 
-        description.fmt(f)?;
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.kind.description().fmt(f)?;
         if let Some(span) = &self.span {
             write!(f, " (at {}..{})", span.start, span.end)?;
         }
@@ -0,0 +1,644 @@
+//! Escaping of values so that they can be put into literals.
+//!
+//! The functions in this module return the text to put between the quotes of
+//! a literal of the corresponding kind. Only what the kind requires is
+//! escaped: the quote, backslashes, `\r` and, depending on the kind, other
+//! characters or bytes. This is the inverse of the [`unescape`][crate::unescape]
+//! module.
+//!
+//! ```
+//! use litrs::{escape, StringLit};
+//!
+//! let body = escape::for_string("say \"hi\"\r\n");
+//! assert_eq!(body, "say \\\"hi\\\"\\r\n");
+//!
+//! let lit = StringLit::parse(format!("\"{}\"", body)).unwrap();
+//! assert_eq!(lit.value(), "say \"hi\"\r\n");
+//!
+//! assert_eq!(escape::for_byte_string(b"a\xFF\\"), r"a\xFF\\");
+//! ```
+
+use std::{ffi::CStr, fmt::Write, ops::Range};
+
+use crate::{
+    err::{perr, ParseErrorKind::*},
+    parse::{check_suffix, hex_digit_value},
+    ParseError,
+};
+
+
+/// Escapes `s` for a string literal (`"..."`). Escapes `"`, `\` and `\r`.
+pub fn for_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str(r#"\""#),
+            '\\' => out.push_str(r"\\"),
+            '\r' => out.push_str(r"\r"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Escapes `c` for a character literal (`'...'`). Escapes `'`, `\`, `\r`,
+/// `\n` and `\t`.
+///
+/// ```
+/// use litrs::escape;
+///
+/// assert_eq!(escape::for_char('\''), r"\'");
+/// assert_eq!(escape::for_char('\n'), r"\n");
+/// assert_eq!(escape::for_char('"'), "\"");
+/// ```
+pub fn for_char(c: char) -> String {
+    match c {
+        '\'' => r"\'".into(),
+        '\\' => r"\\".into(),
+        '\r' => r"\r".into(),
+        '\n' => r"\n".into(),
+        '\t' => r"\t".into(),
+        c => c.into(),
+    }
+}
+
+/// Escapes `bytes` for a byte string literal (`b"..."`). Escapes `"`, `\` and
+/// `\r`. Non-ASCII bytes are written as `\xNN`.
+pub fn for_byte_string(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len());
+    for &b in bytes {
+        match b {
+            b'"' => out.push_str(r#"\""#),
+            b'\\' => out.push_str(r"\\"),
+            b'\r' => out.push_str(r"\r"),
+            b if b.is_ascii() => out.push(b.into()),
+            b => write!(out, r"\x{:02X}", b).unwrap(),
+        }
+    }
+    out
+}
+
+/// Escapes `s` for a C string literal (`c"..."`). Escapes `"`, `\` and `\r`.
+/// Bytes that are not part of valid UTF-8 are written as `\xNN`, valid UTF-8
+/// is kept as is. The nul terminator is not part of the result.
+///
+/// ```
+/// use litrs::escape;
+/// use std::ffi::CStr;
+///
+/// let s = CStr::from_bytes_with_nul(b"Gr\xC3\xBC\xDFe\0").unwrap();
+/// assert_eq!(escape::for_c_string(s), r"Grü\xDFe");
+/// ```
+pub fn for_c_string(s: &CStr) -> String {
+    let mut out = String::with_capacity(s.to_bytes().len());
+    let mut rest = s.to_bytes();
+    while !rest.is_empty() {
+        let (valid, invalid) = match std::str::from_utf8(rest) {
+            Ok(valid) => (valid, &[][..]),
+            Err(e) => {
+                let (valid, after) = rest.split_at(e.valid_up_to());
+                let invalid_len = e.error_len().unwrap_or(after.len());
+                let valid = std::str::from_utf8(valid).expect("checked above");
+                (valid, &after[..invalid_len])
+            }
+        };
+
+        out.push_str(&for_string(valid));
+        for b in invalid {
+            write!(out, r"\x{:02X}", b).unwrap();
+        }
+        rest = &rest[valid.len() + invalid.len()..];
+    }
+    out
+}
+
+
+/// Must start with `\`. Returns the unscaped value as `E` and the number of
+/// input bytes the escape is long.
+///
+/// `unicode` and `byte_escapes` specify which types of escapes are
+/// supported. [Quote escapes] are always unescaped, [Unicode escapes] only if
+/// `unicode` is true. If `byte_escapes` is false, [ASCII escapes] are
+/// used, if it's true, [Byte escapes] are (the only difference being that the
+/// latter supports \xHH escapes > 0x7f).
+///
+/// [Quote escapes]: https://doc.rust-lang.org/reference/tokens.html#quote-escapes
+/// [Unicode escapes]: https://doc.rust-lang.org/reference/tokens.html#unicode-escapes
+/// [Ascii escapes]: https://doc.rust-lang.org/reference/tokens.html#ascii-escapes
+/// [Byte escapes]: https://doc.rust-lang.org/reference/tokens.html#byte-escapes
+pub(crate) fn unescape(
+    input: &str,
+    unicode: bool,
+    byte_escapes: bool,
+    allow_nul: bool,
+) -> Result<(Unescape, usize), ParseError> {
+    let first = input.as_bytes().get(1).ok_or(perr(0, UnterminatedEscape))?;
+    let out = match first {
+        // Quote escapes
+        b'\'' => (Unescape::Byte(b'\''), 2),
+        b'"' => (Unescape::Byte(b'"'), 2),
+
+        // Ascii escapes
+        b'n' => (Unescape::Byte(b'\n'), 2),
+        b'r' => (Unescape::Byte(b'\r'), 2),
+        b't' => (Unescape::Byte(b'\t'), 2),
+        b'\\' => (Unescape::Byte(b'\\'), 2),
+        b'0' => if allow_nul {
+            (Unescape::Byte(b'\0'), 2)
+        } else {
+            return Err(perr(0..2, DisallowedNulEscape))
+        },
+        b'x' => {
+            let hex_string = input.get(2..4)
+                .ok_or(perr(0..input.len(), UnterminatedEscape))?
+                .as_bytes();
+            let first = hex_digit_value(hex_string[0]).ok_or(perr(0..4, InvalidXEscape))?;
+            let second = hex_digit_value(hex_string[1]).ok_or(perr(0..4, InvalidXEscape))?;
+            let value = second + 16 * first;
+
+            if !byte_escapes && value > 0x7F {
+                return Err(perr(0..4, NonAsciiXEscape));
+            }
+
+            if !allow_nul && value == 0 {
+                return Err(perr(0..4, DisallowedNulEscape));
+            }
+
+            (Unescape::Byte(value), 4)
+        }
+
+        // Unicode escape
+        b'u' => {
+            if !unicode {
+                return Err(perr(0..2, UnicodeEscapeInByteLiteral));
+            }
+
+            if input.as_bytes().get(2) != Some(&b'{') {
+                return Err(perr(0..2, UnicodeEscapeWithoutBrace));
+            }
+
+            let closing_pos = input.bytes().position(|b| b == b'}')
+                .ok_or(perr(0..input.len(), UnterminatedUnicodeEscape))?;
+
+            let inner = &input[3..closing_pos];
+            if inner.as_bytes().first() == Some(&b'_') {
+                return Err(perr(3, InvalidStartOfUnicodeEscape));
+            }
+
+            let mut v: u32 = 0;
+            let mut digit_count = 0;
+            for (i, b) in inner.bytes().enumerate() {
+                if b == b'_' {
+                    continue;
+                }
+
+                let digit = hex_digit_value(b).ok_or(perr(3 + i, NonHexDigitInUnicodeEscape))?;
+
+                if digit_count == 6 {
+                    return Err(perr(3 + i, TooManyDigitInUnicodeEscape));
+                }
+                digit_count += 1;
+                v = 16 * v + digit as u32;
+            }
+
+            if !allow_nul && v == 0 {
+                return Err(perr(0..closing_pos + 1, DisallowedNulEscape));
+            }
+
+            let c = std::char::from_u32(v)
+                .ok_or(perr(0..closing_pos + 1, InvalidUnicodeEscapeChar))?;
+
+            (Unescape::Unicode(c), closing_pos + 1)
+        }
+
+        _ => return Err(perr(0..2, UnknownEscape)),
+    };
+
+    Ok(out)
+}
+
+/// Result of unescaping an escape-sequence in a string.
+pub(crate) enum Unescape {
+    Byte(u8),
+    Unicode(char),
+}
+
+impl Unescape {
+    /// Returns this value as `char`, panicking if it's a byte with a value > 0x7f.
+    pub(crate) fn unwrap_char(self) -> char {
+        match self {
+            Self::Byte(b) => {
+                assert!(b <= 0x7F, "non ASCII byte");
+                b.into()
+            }
+            Self::Unicode(c) => c,
+        }
+    }
+
+    /// Returns this value as `u8`, panicking if it was `Unicode`.
+    pub(crate) fn unwrap_byte(self) -> u8 {
+        match self {
+            Self::Byte(b) => b,
+            Self::Unicode(_) => panic!("unexpected unicode escape value"),
+        }
+    }
+}
+
+pub(crate) trait EscapeContainer {
+    fn new() -> Self;
+    fn push(&mut self, v: Unescape);
+    fn push_str(&mut self, s: &str);
+}
+
+impl EscapeContainer for Vec<u8> {
+    fn new() -> Self {
+        Self::new()
+    }
+    fn push_str(&mut self, s: &str) {
+        self.extend_from_slice(s.as_bytes());
+    }
+    fn push(&mut self, v: Unescape) {
+        match v {
+            Unescape::Byte(b) => self.push(b),
+            Unescape::Unicode(c) => {
+                let start = self.len();
+                self.resize(self.len() + c.len_utf8(), 0);
+                c.encode_utf8(&mut self[start..]);
+            }
+        }
+    }
+}
+
+impl EscapeContainer for String {
+    fn new() -> Self {
+        Self::new()
+    }
+    fn push_str(&mut self, s: &str) {
+        self.push_str(s);
+    }
+    fn push(&mut self, v: Unescape) {
+        self.push(v.unwrap_char());
+    }
+}
+
+/// Discards all values. Used to only check a literal without allocating.
+impl EscapeContainer for () {
+    fn new() -> Self {}
+    fn push_str(&mut self, _: &str) {}
+    fn push(&mut self, _: Unescape) {}
+}
+
+
+/// A piece of the content of a (byte/C) string literal: either verbatim text
+/// or a single unescaped value.
+pub(crate) enum RawChunk<'a> {
+    Verbatim(&'a str),
+    Escape(Unescape),
+}
+
+/// Iterates over the content (without quotes) of an already validated string,
+/// byte string or C string literal, splitting it into verbatim parts and
+/// escapes. String continues (`\` followed by a newline) are skipped.
+#[derive(Debug, Clone)]
+pub(crate) struct RawChunks<'a> {
+    rest: &'a str,
+    len: usize,
+    raw: bool,
+    unicode: bool,
+    byte_escapes: bool,
+}
+
+impl<'a> RawChunks<'a> {
+    /// `raw` specifies whether this is the content of a raw string literal,
+    /// the other two flags are the same as for `unescape`.
+    pub(crate) fn new(content: &'a str, raw: bool, unicode: bool, byte_escapes: bool) -> Self {
+        Self { rest: content, len: content.len(), raw, unicode, byte_escapes }
+    }
+
+    /// Like `next`, but also returns the range of the chunk within the
+    /// content.
+    pub(crate) fn next_with_range(&mut self) -> Option<(RawChunk<'a>, Range<usize>)> {
+        loop {
+            if self.rest.is_empty() {
+                return None;
+            }
+
+            let start = self.len - self.rest.len();
+            let end_verbatim = if self.raw {
+                self.rest.len()
+            } else {
+                self.rest.find('\\').unwrap_or(self.rest.len())
+            };
+            if end_verbatim > 0 {
+                let (verbatim, rest) = self.rest.split_at(end_verbatim);
+                self.rest = rest;
+                return Some((RawChunk::Verbatim(verbatim), start..start + end_verbatim));
+            }
+
+            // String continue: skip the whitespace and look again.
+            if self.rest.as_bytes().get(1) == Some(&b'\n') {
+                let end = self.rest[2..].bytes()
+                    .position(|b| !is_string_continue_skipable_whitespace(b))
+                    .map(|i| i + 2)
+                    .unwrap_or(self.rest.len());
+                self.rest = &self.rest[end..];
+                continue;
+            }
+
+            // Nul escapes are allowed here: C string literals were already
+            // checked to not contain them.
+            let (value, len) = unescape(self.rest, self.unicode, self.byte_escapes, true)
+                .unwrap_or_else(|e| unreachable!("bug: invalid escape in valid literal: {}", e));
+            self.rest = &self.rest[len..];
+            return Some((RawChunk::Escape(value), start..start + len));
+        }
+    }
+}
+
+impl<'a> Iterator for RawChunks<'a> {
+    type Item = RawChunk<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with_range().map(|(chunk, _)| chunk)
+    }
+}
+
+
+/// Checks whether the character is skipped after a string continue start
+/// (unescaped backlash followed by `\n`).
+fn is_string_continue_skipable_whitespace(b: u8) -> bool {
+    b == b' ' || b == b'\t' || b == b'\n'
+}
+
+/// Returns the index of the first byte in `bytes` that is `"`, `\r` or `\0`,
+/// or `\\` (if `backslash` is true), or non-ASCII (if `non_ascii` is true).
+/// Returns `bytes.len()` if there is no such byte.
+///
+/// Checks eight bytes at a time (SWAR), as most of a typical string literal
+/// consists of bytes that don't need special handling.
+#[inline]
+fn find_special(bytes: &[u8], backslash: bool, non_ascii: bool) -> usize {
+    const LO: u64 = u64::from_ne_bytes([0x01; 8]);
+    const HI: u64 = u64::from_ne_bytes([0x80; 8]);
+
+    // Sets the high bit of each byte in `word` that equals `b`. Bits of bytes
+    // following a matching byte might also be set, but that's fine as we are
+    // only interested in the first match.
+    fn has_byte(word: u64, b: u8) -> u64 {
+        let x = word ^ (LO * b as u64);
+        x.wrapping_sub(LO) & !x & HI
+    }
+
+    let is_special = |b: u8| {
+        matches!(b, b'"' | b'\r' | b'\0')
+            || (backslash && b == b'\\')
+            || (non_ascii && !b.is_ascii())
+    };
+
+    let mut i = 0;
+    while let Some(chunk) = bytes.get(i..i + 8) {
+        let word = u64::from_le_bytes(chunk.try_into().unwrap());
+        let mut found = has_byte(word, b'"') | has_byte(word, b'\r') | has_byte(word, b'\0');
+        if backslash {
+            found |= has_byte(word, b'\\');
+        }
+        if non_ascii {
+            found |= word & HI;
+        }
+
+        if found != 0 {
+            return i + found.trailing_zeros() as usize / 8;
+        }
+        i += 8;
+    }
+
+    bytes[i..].iter().position(|&b| is_special(b)).map_or(bytes.len(), |pos| i + pos)
+}
+
+/// Unescapes a whole string or byte string.
+#[inline(never)]
+pub(crate) fn unescape_string<C: EscapeContainer>(
+    input: &str,
+    offset: usize,
+    unicode: bool,
+    byte_escapes: bool,
+    allow_nul: bool,
+) -> Result<(Option<C>, usize), ParseError> {
+    let (value, closing_quote_pos) =
+        unescape_content(input, offset, true, unicode, byte_escapes, allow_nul)?;
+
+    let start_suffix = closing_quote_pos + 1;
+    let suffix = &input[start_suffix..];
+    check_suffix(suffix).map_err(|kind| perr(start_suffix, kind))?;
+
+    Ok((value, start_suffix))
+}
+
+/// Unescapes the content of a string or byte string, starting at `offset`.
+/// If `quoted` is true, the content ends at the first unescaped `"`, which
+/// has to exist. Otherwise, it ends at the end of the input and `"` is treated
+/// like any other character. Returns the end index of the content.
+pub(crate) fn unescape_content<C: EscapeContainer>(
+    input: &str,
+    offset: usize,
+    quoted: bool,
+    unicode: bool,
+    byte_escapes: bool,
+    allow_nul: bool,
+) -> Result<(Option<C>, usize), ParseError> {
+    let mut closing_quote_pos = None;
+    let mut i = offset;
+    let mut end_last_escape = offset;
+    let mut value = C::new();
+    while i < input.len() {
+        // Skip everything that doesn't need special handling.
+        i += find_special(&input.as_bytes()[i..], true, !unicode);
+        if i == input.len() {
+            break;
+        }
+
+        match input.as_bytes()[i] {
+            // Handle "string continue".
+            b'\\' if input.as_bytes().get(i + 1) == Some(&b'\n') => {
+                value.push_str(&input[end_last_escape..i]);
+
+                // Find the first non-whitespace character.
+                let end_escape = input[i + 2..].bytes()
+                    .position(|b| !is_string_continue_skipable_whitespace(b));
+                let end_escape = match end_escape {
+                    Some(end) => end,
+                    None if quoted => return Err(perr(None, UnterminatedString)),
+                    None => input.len() - i - 2,
+                };
+
+                i += 2 + end_escape;
+                end_last_escape = i;
+            }
+            b'\\' => {
+                // If quoted, everything except the last char, which might be
+                // the closing quote.
+                let last_len = if quoted {
+                    input.chars().next_back().map_or(0, char::len_utf8)
+                } else {
+                    0
+                };
+                let rest = &input[i..input.len() - last_len];
+                let (c, len) = unescape(rest, unicode, byte_escapes, allow_nul)
+                    .map_err(|e| e.offset_span(i))?;
+                value.push_str(&input[end_last_escape..i]);
+                value.push(c);
+                i += len;
+                end_last_escape = i;
+            }
+            b'\r' => return Err(perr(i, CarriageReturn)),
+            b'"' if quoted => {
+                closing_quote_pos = Some(i);
+                break;
+            }
+            b'\0' if !allow_nul => return Err(perr(i, NulByte)),
+            b if !unicode && !b.is_ascii() => return Err(perr(i, NonAsciiInByteLiteral)),
+            _ => i += 1,
+        }
+    }
+
+    let closing_quote_pos = match closing_quote_pos {
+        Some(pos) => pos,
+        None if quoted => return Err(perr(None, UnterminatedString)),
+        None => input.len(),
+    };
+
+    // If there was no escape (or string continue) in the input string, the
+    // string value equals the input, so we store `None`. Note that `value`
+    // might be empty even if there were escapes, e.g. for `"\<newline>"`.
+    let value = if end_last_escape == offset {
+        None
+    } else {
+        // There was an escape in the string, so we need to push the
+        // remaining unescaped part of the string still.
+        value.push_str(&input[end_last_escape..closing_quote_pos]);
+        Some(value)
+    };
+
+    Ok((value, closing_quote_pos))
+}
+
+/// Reads and checks a raw (byte) string literal. Returns the number of hashes
+/// and the index when the suffix starts.
+#[inline(never)]
+pub(crate) fn scan_raw_string(
+    input: &str,
+    offset: usize,
+    unicode: bool,
+    allow_nul: bool,
+) -> Result<(u8, usize), ParseError> {
+    // Raw string literal
+    let num_hashes = input[offset..].bytes().position(|b| b != b'#')
+        .ok_or(perr(None, InvalidLiteral))?;
+
+    if num_hashes > 256 {
+        return Err(perr(offset..offset + num_hashes, TooManyHashes));
+    }
+
+    if input.as_bytes().get(offset + num_hashes) != Some(&b'"') {
+        return Err(perr(None, InvalidLiteral));
+    }
+    let start_inner = offset + num_hashes + 1;
+    let hashes = &input[offset..num_hashes + offset];
+
+    let closing_quote_pos = scan_raw_content(input, start_inner, Some(hashes), unicode, allow_nul)?
+        .ok_or(perr(None, UnterminatedRawString))?;
+
+    let start_suffix = closing_quote_pos + num_hashes + 1;
+    let suffix = &input[start_suffix..];
+    check_suffix(suffix).map_err(|kind| perr(start_suffix, kind))?;
+
+    Ok((num_hashes as u8, start_suffix))
+}
+
+/// Checks the content of a raw (byte) string, starting at `offset`. If
+/// `hashes` is given, the content ends at the first `"` followed by these
+/// hashes, and the index of that `"` is returned. Otherwise, the content ends
+/// at the end of the input and `None` is returned.
+pub(crate) fn scan_raw_content(
+    input: &str,
+    offset: usize,
+    hashes: Option<&str>,
+    unicode: bool,
+    allow_nul: bool,
+) -> Result<Option<usize>, ParseError> {
+    let mut i = offset;
+    while i < input.len() {
+        i += find_special(&input.as_bytes()[i..], false, !unicode);
+        if i == input.len() {
+            break;
+        }
+
+        let b = input.as_bytes()[i];
+        if let (b'"', Some(hashes)) = (b, hashes) {
+            if input[i + 1..].starts_with(hashes) {
+                return Ok(Some(i));
+            }
+        }
+
+        // CR are just always disallowed in all (raw) strings. Rust performs
+        // a normalization of CR LF to just LF in a pass prior to lexing. But
+        // in lexing, it's disallowed.
+        if b == b'\r' {
+            return Err(perr(i, CarriageReturn));
+        }
+
+        if b == b'\0' && !allow_nul {
+            return Err(perr(i, NulByte));
+        }
+
+        if !unicode {
+            if !b.is_ascii() {
+                return Err(perr(i, NonAsciiInByteLiteral));
+            }
+        }
+
+        i += 1;
+    }
+
+    Ok(None)
+}
+
+/// Appends `s` to `out`, escaped such that it can be used inside a (non-raw)
+/// string literal. Only escapes what is necessary or invisible: quotes,
+/// backslashes and control characters.
+pub(crate) fn escape_str_into(s: &str, out: &mut String) {
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\0' => out.push_str("\\0"),
+            c if c.is_control() => out.extend(c.escape_unicode()),
+            c => out.push(c),
+        }
+    }
+}
+
+/// Returns the minimal number of `#` for a raw string literal with the given
+/// content, or `None` if the content cannot be put in a raw string literal
+/// at all (because it contains a `\r`).
+pub(crate) fn raw_string_hashes(s: &str) -> Option<usize> {
+    if s.contains('\r') {
+        return None;
+    }
+
+    // The literal would end at the first `"` followed by enough `#`.
+    let bytes = s.as_bytes();
+    let needed = bytes.iter().enumerate()
+        .filter(|(_, b)| **b == b'"')
+        .map(|(i, _)| bytes[i + 1..].iter().take_while(|b| **b == b'#').count() + 1)
+        .max()
+        .unwrap_or(0);
+    Some(needed)
+}
+
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,72 @@
+use std::ffi::CString;
+
+use crate::{ByteStringLit, CStringLit, CharLit, StringLit};
+use super::*;
+
+
+fn test_chars() -> impl Iterator<Item = char> {
+    (0..0x3000).chain(0xFFF0..0x10100).chain(0x10FFF0..=0x10FFFF).filter_map(char::from_u32)
+}
+
+#[test]
+fn string() {
+    assert_eq!(for_string(""), "");
+    assert_eq!(for_string("abc\n\t\0'🦊"), "abc\n\t\0'🦊");
+    assert_eq!(for_string(r#"a"b\c"#), r#"a\"b\\c"#);
+    assert_eq!(for_string("a\r\nb"), "a\\r\nb");
+    assert_eq!(for_string("\\\n  x"), "\\\\\n  x");
+
+    let all = test_chars().collect::<String>();
+    let lit = StringLit::parse(format!("\"{}\"", for_string(&all))).unwrap();
+    assert_eq!(lit.value(), all);
+}
+
+#[test]
+fn char() {
+    assert_eq!(for_char('a'), "a");
+    assert_eq!(for_char('"'), "\"");
+    assert_eq!(for_char('\''), r"\'");
+    assert_eq!(for_char('\t'), r"\t");
+
+    for c in test_chars() {
+        let lit = CharLit::parse(format!("'{}'", for_char(c)))
+            .unwrap_or_else(|e| panic!("failed to parse escaped {c:?}: {e}"));
+        assert_eq!(lit.value(), c);
+    }
+}
+
+#[test]
+fn byte_string() {
+    assert_eq!(for_byte_string(b"abc\n\0'"), "abc\n\0'");
+    assert_eq!(for_byte_string(b"\"\\\r"), r#"\"\\\r"#);
+    assert_eq!(for_byte_string(b"\x7F\x80\xFF"), "\x7F\\x80\\xFF");
+
+    let all = (0..=255).collect::<Vec<u8>>();
+    let lit = ByteStringLit::parse(format!("b\"{}\"", for_byte_string(&all))).unwrap();
+    assert_eq!(lit.value(), all);
+}
+
+#[test]
+fn c_string() {
+    #[track_caller]
+    fn check(bytes: &[u8], expected: &str) {
+        let s = CString::new(bytes).unwrap();
+        let escaped = for_c_string(&s);
+        assert_eq!(escaped, expected);
+
+        let lit = CStringLit::parse(format!("c\"{}\"", escaped)).unwrap();
+        assert_eq!(lit.value(), &*s);
+    }
+
+    check(b"", "");
+    check(b"abc", "abc");
+    check("Grüße \"🦀\"\r\n".as_bytes(), "Grüße \\\"🦀\\\"\\r\n");
+    check(b"a\xFFb", r"a\xFFb");
+    check(b"\xC3\xBC\xC3", r"ü\xC3");
+    check(b"\xF0\x9F\xA6x\xE9", r"\xF0\x9F\xA6x\xE9");
+
+    let all = (1..=255).collect::<Vec<u8>>();
+    let s = CString::new(all).unwrap();
+    let lit = CStringLit::parse(format!("c\"{}\"", for_c_string(&s))).unwrap();
+    assert_eq!(lit.value(), &*s);
+}
@@ -0,0 +1,489 @@
+use crate::{err::ParseErrorKind, ParseError};
+
+
+/// Returns a long-form explanation for the given error code (e.g. `LIT0011`),
+/// similar to `rustc --explain`. Returns `None` for unknown codes. The
+/// explanation is Markdown and contains an erroneous and a corrected example.
+///
+/// Error codes are obtained via [`ParseError::code`] and are stable: a code
+/// always refers to the same kind of error and codes are never reused.
+///
+/// ```
+/// use litrs::StringLit;
+///
+/// let e = StringLit::parse(r#""\x80""#).unwrap_err();
+/// assert_eq!(e.code(), "LIT0011");
+///
+/// let explanation = litrs::explain(e.code()).unwrap();
+/// assert!(explanation.contains("Corrected example"));
+/// ```
+pub fn explain(code: &str) -> Option<&'static str> {
+    let kind = ALL_KINDS.iter().find(|kind| kind.code() == code)?;
+    Some(kind.explanation())
+}
+
+impl ParseError {
+    /// Returns the stable error code of this error, e.g. `"LIT0011"`. Unlike
+    /// the message and span, the code of an error kind never changes, so it
+    /// can be used to group or filter errors. Pass it to [`explain`] for a
+    /// long-form explanation.
+    pub fn code(&self) -> &'static str {
+        self.kind.code()
+    }
+}
+
+/// All error kinds, in order of their codes.
+pub(crate) const ALL_KINDS: &[ParseErrorKind] = {
+    use ParseErrorKind::*;
+    &[
+        Empty,
+        UnexpectedChar,
+        InvalidLiteral,
+        DoesNotStartWithDigit,
+        InvalidDigit,
+        NoDigits,
+        NoExponentDigits,
+        UnknownEscape,
+        UnterminatedEscape,
+        InvalidXEscape,
+        NonAsciiXEscape,
+        UnicodeEscapeInByteLiteral,
+        InvalidStartOfUnicodeEscape,
+        UnicodeEscapeWithoutBrace,
+        NonHexDigitInUnicodeEscape,
+        TooManyDigitInUnicodeEscape,
+        InvalidUnicodeEscapeChar,
+        UnterminatedUnicodeEscape,
+        UnterminatedCharLiteral,
+        OverlongCharLiteral,
+        EmptyCharLiteral,
+        UnterminatedByteLiteral,
+        OverlongByteLiteral,
+        EmptyByteLiteral,
+        NonAsciiInByteLiteral,
+        UnescapedSingleQuote,
+        UnescapedSpecialWhitespace,
+        DoesNotStartWithQuote,
+        UnterminatedRawString,
+        UnterminatedString,
+        InvalidStringLiteralStart,
+        InvalidByteLiteralStart,
+        InvalidByteStringLiteralStart,
+        InvalidCStringLiteralStart,
+        DisallowedNulEscape,
+        NulByte,
+        CarriageReturn,
+        TooManyHashes,
+        InvalidSuffix,
+        UnexpectedIntegerLit,
+        IntegerSuffixStartingWithE,
+        DisallowedSuffix,
+        IntegerOutOfRange,
+        FloatOutOfRange,
+    ]
+};
+
+/// Builds an explanation from a summary, an erroneous example, the reason
+/// and a corrected example.
+macro_rules! explanation {
+    ($summary:literal, bad: $bad:literal, $why:literal, good: $good:literal $(,)?) => {
+        concat!(
+            $summary,
+            "\n\nErroneous example:\n\n```\n",
+            $bad,
+            "\n```\n\n",
+            $why,
+            "\n\nCorrected example:\n\n```\n",
+            $good,
+            "\n```\n",
+        )
+    };
+}
+
+impl ParseErrorKind {
+    /// The stable error code. Once assigned, codes must never change! New
+    /// error kinds get the next free number (and are added to `ALL_KINDS`).
+    pub(crate) fn code(self) -> &'static str {
+        use ParseErrorKind::*;
+
+        match self {
+            Empty => "LIT0001",
+            UnexpectedChar => "LIT0002",
+            InvalidLiteral => "LIT0003",
+            DoesNotStartWithDigit => "LIT0004",
+            InvalidDigit => "LIT0005",
+            NoDigits => "LIT0006",
+            NoExponentDigits => "LIT0007",
+            UnknownEscape => "LIT0008",
+            UnterminatedEscape => "LIT0009",
+            InvalidXEscape => "LIT0010",
+            NonAsciiXEscape => "LIT0011",
+            UnicodeEscapeInByteLiteral => "LIT0012",
+            InvalidStartOfUnicodeEscape => "LIT0013",
+            UnicodeEscapeWithoutBrace => "LIT0014",
+            NonHexDigitInUnicodeEscape => "LIT0015",
+            TooManyDigitInUnicodeEscape => "LIT0016",
+            InvalidUnicodeEscapeChar => "LIT0017",
+            UnterminatedUnicodeEscape => "LIT0018",
+            UnterminatedCharLiteral => "LIT0019",
+            OverlongCharLiteral => "LIT0020",
+            EmptyCharLiteral => "LIT0021",
+            UnterminatedByteLiteral => "LIT0022",
+            OverlongByteLiteral => "LIT0023",
+            EmptyByteLiteral => "LIT0024",
+            NonAsciiInByteLiteral => "LIT0025",
+            UnescapedSingleQuote => "LIT0026",
+            UnescapedSpecialWhitespace => "LIT0027",
+            DoesNotStartWithQuote => "LIT0028",
+            UnterminatedRawString => "LIT0029",
+            UnterminatedString => "LIT0030",
+            InvalidStringLiteralStart => "LIT0031",
+            InvalidByteLiteralStart => "LIT0032",
+            InvalidByteStringLiteralStart => "LIT0033",
+            InvalidCStringLiteralStart => "LIT0034",
+            DisallowedNulEscape => "LIT0035",
+            NulByte => "LIT0036",
+            CarriageReturn => "LIT0037",
+            TooManyHashes => "LIT0038",
+            InvalidSuffix => "LIT0039",
+            UnexpectedIntegerLit => "LIT0040",
+            IntegerSuffixStartingWithE => "LIT0041",
+            DisallowedSuffix => "LIT0042",
+            IntegerOutOfRange => "LIT0043",
+            FloatOutOfRange => "LIT0044",
+        }
+    }
+
+    fn explanation(self) -> &'static str {
+        use ParseErrorKind::*;
+
+        match self {
+            Empty => explanation!(
+                "The input passed to a `parse` function was empty.",
+                bad: r#"Literal::parse("")"#,
+                "An empty string is not a literal. Note that an empty string *literal* \
+                    is written with two quotes.",
+                good: r#"Literal::parse("\"\"")"#,
+            ),
+            UnexpectedChar => explanation!(
+                "An unexpected character was encountered inside a number literal.",
+                bad: "1._5\n1.x",
+                "The fractional part of a float literal must start with a digit and a \
+                    literal ending in `.` cannot be followed by anything else.",
+                good: "1.5\n1.0",
+            ),
+            InvalidLiteral => explanation!(
+                "The input is not a literal at all.",
+                bad: "foo\n-3\n.5",
+                "Literals start with a quote, a digit, or are `true`/`false`. A minus \
+                    sign is not part of the literal, and float literals must start \
+                    with a digit.",
+                good: "\"foo\"\n3\n0.5",
+            ),
+            DoesNotStartWithDigit => explanation!(
+                "An integer or float literal was expected, but the input does not start \
+                    with a decimal digit.",
+                bad: r#"IntegerLit::parse("x7")"#,
+                "Number literals always start with a decimal digit, even hexadecimal \
+                    ones (`0x...`).",
+                good: r#"IntegerLit::parse("7")"#,
+            ),
+            InvalidDigit => explanation!(
+                "An integer literal contains a digit that is not valid for its base.",
+                bad: "0b102\n0o78",
+                "Binary literals (`0b`) only contain `0` and `1`, octal literals (`0o`) \
+                    only `0` to `7`. Hexadecimal literals (`0x`) use `0` to `9` and \
+                    `a` to `f`.",
+                good: "0b101\n0o77",
+            ),
+            NoDigits => explanation!(
+                "An integer literal does not contain any digits after its base prefix.",
+                bad: "0x\n0b__",
+                "After `0x`, `0o` or `0b`, at least one digit is required. Underscores \
+                    alone are not enough.",
+                good: "0x0\n0b0",
+            ),
+            NoExponentDigits => explanation!(
+                "The exponent of a float literal does not contain any digits.",
+                bad: "1e\n2.5e+_",
+                "After `e` or `E` (and an optional sign), at least one decimal digit is \
+                    required.",
+                good: "1e3\n2.5e+1",
+            ),
+            UnknownEscape => explanation!(
+                "An unknown escape sequence was used.",
+                bad: r#""C:\dir\file""#,
+                "Valid escapes are `\\n`, `\\r`, `\\t`, `\\\\`, `\\0`, `\\'`, `\\\"`, `\\x7F` and \
+                    `\\u{7FFF}` (the latter is not allowed in byte literals). To write a \
+                    backslash, escape it or use a raw string.",
+                good: r#""C:\\dir\\file"
+r"C:\dir\file""#,
+            ),
+            UnterminatedEscape => explanation!(
+                "The input ended in the middle of an escape sequence.",
+                bad: r#"'\"#,
+                "An escape sequence was started, but the literal ended before it was \
+                    complete. This usually means the closing quote is missing or was \
+                    escaped by accident.",
+                good: r#"'\\'"#,
+            ),
+            InvalidXEscape => explanation!(
+                r"A `\x` escape is not followed by two hex digits.",
+                bad: r#""\x7G"
+"\xZZ""#,
+                r"`\x` escapes always consist of exactly two hexadecimal digits.",
+                good: r#""\x07""#,
+            ),
+            NonAsciiXEscape => explanation!(
+                r"A `\x` escape in a character or string literal exceeds the ASCII range.",
+                bad: r#""\x80""#,
+                "Character and string literals contain Unicode characters, not bytes: `str` \
+                    is always valid UTF-8. A `\\xNN` escape denotes the character with the \
+                    code point `NN`, but only up to `\\x7F`, to avoid confusion with the byte \
+                    `0x80`, which is not valid UTF-8 on its own. Use a `\\u{...}` escape for \
+                    other characters. In byte and byte string literals, which contain \
+                    arbitrary bytes, `\\x80` to `\\xFF` are allowed.",
+                good: r#""\u{80}"
+b"\x80""#,
+            ),
+            UnicodeEscapeInByteLiteral => explanation!(
+                r"A `\u{...}` escape was used in a byte or byte string literal.",
+                bad: r#"b"\u{1F980}""#,
+                "Byte literals contain bytes, not Unicode characters, so `\\u{...}` \
+                    escapes are not allowed. Use `\\x` escapes for single bytes, or a C \
+                    string literal if you want UTF-8 encoded characters.",
+                good: r#"b"\xF0\x9F\xA6\x80"
+c"\u{1F980}""#,
+            ),
+            InvalidStartOfUnicodeEscape => explanation!(
+                r"A `\u{...}` escape does not start with a hex digit.",
+                bad: r#""\u{_41}""#,
+                r"The first character after `\u{` must be a hexadecimal digit.",
+                good: r#""\u{41}""#,
+            ),
+            UnicodeEscapeWithoutBrace => explanation!(
+                r"A `\u` escape is not followed by an opening brace.",
+                bad: r#""\u0041""#,
+                "Unlike in other languages, Unicode escapes in Rust are written with \
+                    braces.",
+                good: r#""\u{0041}""#,
+            ),
+            NonHexDigitInUnicodeEscape => explanation!(
+                r"A `\u{...}` escape contains a character that is not a hex digit.",
+                bad: r#""\u{4G}""#,
+                r"Only hexadecimal digits and underscores are allowed between the braces.",
+                good: r#""\u{4F}""#,
+            ),
+            TooManyDigitInUnicodeEscape => explanation!(
+                r"A `\u{...}` escape contains more than six hex digits.",
+                bad: r#""\u{0001F980}""#,
+                "Unicode code points are at most `10FFFF`, so at most six digits are \
+                    allowed. Remove leading zeros.",
+                good: r#""\u{1F980}""#,
+            ),
+            InvalidUnicodeEscapeChar => explanation!(
+                r"The value of a `\u{...}` escape is not a valid character.",
+                bad: r#""\u{D800}"
+"\u{110000}""#,
+                "A `char` is a Unicode scalar value: at most `10FFFF` and not a \
+                    surrogate (`D800` to `DFFF`).",
+                good: r#""\u{FFFD}""#,
+            ),
+            UnterminatedUnicodeEscape => explanation!(
+                r"A `\u{...}` escape lacks the closing brace.",
+                bad: r#""\u{41""#,
+                r"Unicode escapes must be terminated by `}`.",
+                good: r#""\u{41}""#,
+            ),
+            UnterminatedCharLiteral => explanation!(
+                "A character literal is not terminated.",
+                bad: "'a",
+                "Character literals must end with `'`.",
+                good: "'a'",
+            ),
+            OverlongCharLiteral => explanation!(
+                "A character literal contains more than one character.",
+                bad: "'ab'",
+                "A character literal contains exactly one `char`. Note that some symbols \
+                    that look like one character consist of several code points. If you \
+                    meant to write a string, use double quotes.",
+                good: "'a'\n\"ab\"",
+            ),
+            EmptyCharLiteral => explanation!(
+                "A character literal is empty.",
+                bad: "''",
+                "A character literal contains exactly one `char`. To write the quote \
+                    character itself, escape it.",
+                good: r"'\''",
+            ),
+            UnterminatedByteLiteral => explanation!(
+                "A byte literal is not terminated.",
+                bad: "b'a",
+                "Byte literals must end with `'`.",
+                good: "b'a'",
+            ),
+            OverlongByteLiteral => explanation!(
+                "A byte literal contains more than one byte.",
+                bad: "b'ab'",
+                "A byte literal contains exactly one byte. If you meant to write multiple \
+                    bytes, use a byte string literal.",
+                good: "b'a'\nb\"ab\"",
+            ),
+            EmptyByteLiteral => explanation!(
+                "A byte literal is empty.",
+                bad: "b''",
+                "A byte literal contains exactly one byte. To write the quote character \
+                    itself, escape it.",
+                good: r"b'\''",
+            ),
+            NonAsciiInByteLiteral => explanation!(
+                "A byte or byte string literal contains a non-ASCII character.",
+                bad: r#"b"café""#,
+                "Byte literals may only contain ASCII characters, as it would be unclear \
+                    which bytes a non-ASCII character represents. Use `\\x` escapes for \
+                    other bytes, or a C string literal for UTF-8 encoded text.",
+                good: r#"b"caf\xC3\xA9"
+c"café""#,
+            ),
+            UnescapedSingleQuote => explanation!(
+                "A character or byte literal contains an unescaped `'`.",
+                bad: "'''",
+                "Inside character and byte literals, the quote character has to be \
+                    escaped.",
+                good: r"'\''",
+            ),
+            UnescapedSpecialWhitespace => explanation!(
+                "A character or byte literal contains a literal newline, tab or carriage \
+                    return.",
+                bad: "'\t'",
+                "These characters are not allowed unescaped in character and byte \
+                    literals. Use the `\\n`, `\\t` and `\\r` escapes instead.",
+                good: r"'\t'",
+            ),
+            DoesNotStartWithQuote => explanation!(
+                "A character, byte, string or byte string literal was expected, but the \
+                    input does not start with the corresponding quote (and prefix).",
+                bad: r#"CharLit::parse("a")"#,
+                "When parsing a specific kind of literal, the input must be a complete \
+                    literal including quotes.",
+                good: r#"CharLit::parse("'a'")"#,
+            ),
+            UnterminatedRawString => explanation!(
+                "A raw string literal is not terminated.",
+                bad: r###"r#"foo"
+r##"foo"#"###,
+                "Raw string literals end with `\"` followed by the same number of `#` \
+                    they started with.",
+                good: r###"r#"foo"#
+r##"foo"##"###,
+            ),
+            UnterminatedString => explanation!(
+                "A string literal is not terminated.",
+                bad: r#""foo"#,
+                "String literals must end with `\"`.",
+                good: r#""foo""#,
+            ),
+            InvalidStringLiteralStart => explanation!(
+                "A string literal was expected, but the input does not start like one.",
+                bad: r#"StringLit::parse("'a'")"#,
+                r#"String literals start with `"`, `r"` or `r#`."#,
+                good: r#"StringLit::parse("\"a\"")"#,
+            ),
+            InvalidByteLiteralStart => explanation!(
+                "A byte literal was expected, but the input does not start like one.",
+                bad: r#"ByteLit::parse("'a'")"#,
+                "Byte literals start with `b'`.",
+                good: r#"ByteLit::parse("b'a'")"#,
+            ),
+            InvalidByteStringLiteralStart => explanation!(
+                "A byte string literal was expected, but the input does not start like one.",
+                bad: r#"ByteStringLit::parse("\"a\"")"#,
+                r#"Byte string literals start with `b"`, `br"` or `br#`."#,
+                good: r#"ByteStringLit::parse("b\"a\"")"#,
+            ),
+            InvalidCStringLiteralStart => explanation!(
+                "A C string literal was expected, but the input does not start like one.",
+                bad: r#"CStringLit::parse("\"a\"")"#,
+                r#"C string literals start with `c"`, `cr"` or `cr#`."#,
+                good: r#"CStringLit::parse("c\"a\"")"#,
+            ),
+            DisallowedNulEscape => explanation!(
+                r"A C string literal contains a `\0` escape (or equivalent).",
+                bad: r#"c"foo\0bar"
+c"foo\x00""#,
+                "C strings are terminated by a nul byte, so they cannot contain one. The \
+                    terminating nul byte is added automatically.",
+                good: r#"c"foo"
+b"foo\0bar""#,
+            ),
+            NulByte => explanation!(
+                "A C string literal contains a literal nul byte.",
+                bad: "c\"foo<U+0000>\"",
+                "C strings are terminated by a nul byte, so they cannot contain one. The \
+                    terminating nul byte is added automatically.",
+                good: r#"c"foo""#,
+            ),
+            CarriageReturn => explanation!(
+                "A string literal contains a carriage return that is not part of a CRLF \
+                    line break.",
+                bad: "\"foo<CR>bar\"",
+                "A bare carriage return is not allowed in (raw) string literals, as it \
+                    is invisible in most editors. Use the `\\r` escape instead.",
+                good: r#""foo\rbar""#,
+            ),
+            TooManyHashes => explanation!(
+                "A raw string literal uses too many `#` symbols.",
+                bad: "r####...####\"foo\"####...####",
+                "Raw string literals can be delimited by at most 256 `#` symbols, which \
+                    is more than enough for any content.",
+                good: r###"r##"foo"##"###,
+            ),
+            InvalidSuffix => explanation!(
+                "The suffix of a literal is not a valid identifier.",
+                bad: "\"foo\"_\n'a'_",
+                "Literal suffixes (like `u8` in `27u8`) must be valid identifiers. A \
+                    single `_` is not a valid identifier.",
+                good: "\"foo\"\n27_u8",
+            ),
+            UnexpectedIntegerLit => explanation!(
+                "A float literal was expected, but an integer literal was found.",
+                bad: r#"FloatLit::parse("3")"#,
+                "Float literals have a fractional part or an exponent. Note that `3f32` \
+                    is syntactically an integer literal with a float suffix.",
+                good: r#"FloatLit::parse("3.0")"#,
+            ),
+            IntegerSuffixStartingWithE => explanation!(
+                "The suffix of an integer literal starts with `e` or `E`.",
+                bad: "0b101em\n0o17E",
+                "In decimal literals, `e` starts the exponent of a float literal. To \
+                    avoid confusion, suffixes of binary and octal literals cannot start \
+                    with `e` or `E` either. In hexadecimal literals, `e` is a digit.",
+                good: "0b101\n0o17u8",
+            ),
+            DisallowedSuffix => explanation!(
+                "A literal has a suffix that is not allowed by the `ParseOptions` used.",
+                bad: "27lol\n\"foo\"x",
+                "Depending on the options, only suffixes that are valid in Rust \
+                    expressions (like `u8` or `f32`), a custom list of suffixes, or no \
+                    suffixes at all are allowed.",
+                good: "27u8\n\"foo\"",
+            ),
+            IntegerOutOfRange => explanation!(
+                "An integer literal does not fit into the type given by its suffix (or \
+                    `u128` if there is no suffix).",
+                bad: "256u8\n0x1_0000_0000u32",
+                "This error is only emitted in strict mode. The value must be \
+                    representable by the type.",
+                good: "255u8\n256u16",
+            ),
+            FloatOutOfRange => explanation!(
+                "A float literal is too large for the type given by its suffix (or `f64` \
+                    if there is no suffix).",
+                bad: "1e39f32\n1e309",
+                "This error is only emitted in strict mode. A literal that would round \
+                    to infinity is rejected.",
+                good: "1e38f32\n1e308",
+            ),
+        }
+    }
+}
@@ -0,0 +1,385 @@
+use std::{convert::TryFrom, ffi::CString, fmt, path::PathBuf};
+
+use crate::{
+    err::{self, Expected, InvalidToken, Span, TokenKind},
+    float,
+    impls::kind_of,
+    BoolLit, ByteLit, ByteStringLit, CStringLit, CharLit, FloatLit, FloatRounding, FloatType,
+    FromFloatLiteral, IntegerBase, IntegerLit, Literal, StringLit,
+};
+
+
+/// Extracts a Rust value of type `T` from a literal token.
+///
+/// `token` can be a `proc_macro::TokenTree` or `proc_macro::Literal`, a
+/// reference to one of those, or the `proc_macro2` equivalents if the
+/// `proc-macro2` feature is enabled. See [`FromLiteral`] for the list of
+/// supported types and the rules for the conversion. The returned error
+/// carries the span of `token` and can be turned into a `compile_error!`
+/// invocation.
+///
+/// ```ignore
+/// use proc_macro::TokenStream;
+///
+/// #[proc_macro]
+/// pub fn foo(input: TokenStream) -> TokenStream {
+///     let first_token = input.into_iter().next().unwrap(); // Do proper error handling!
+///     let port: u16 = match litrs::extract(first_token) {
+///         Ok(port) => port,
+///         Err(e) => return e.to_compile_error(),
+///     };
+///
+///     // `port` is the value of the integer literal, for example `8080`.
+///     todo!()
+/// }
+/// ```
+///
+/// With Rust 1.63 or newer, you can also write `litrs::extract::<u16>(token)`.
+pub fn extract<T: FromLiteral>(token: impl LiteralToken) -> Result<T, ExtractError> {
+    token.extract_value()
+}
+
+/// Token types that a value can be extracted from via [`extract`]. This trait
+/// is sealed and cannot be implemented outside of this crate. The trait's
+/// methods are implementation detail of this library and are not subject to
+/// semver.
+pub trait LiteralToken: self::sealed::Sealed {
+    #[doc(hidden)]
+    fn extract_value<T: FromLiteral>(self) -> Result<T, ExtractError>;
+}
+
+pub(crate) mod sealed {
+    pub trait Sealed {}
+}
+
+/// Types that can be created from a literal. Used by [`extract`].
+///
+/// Implemented for:
+/// - [`Literal<String>`][Literal] and all specific literal types with `String`
+///   buffer (e.g. [`StringLit<String>`][StringLit]): only checks the kind of
+///   the literal, the suffix is not checked.
+/// - `String`, `PathBuf`: from string literals.
+/// - `char`: from character literals.
+/// - `bool`: from `true` and `false`.
+/// - `u8` - `u128`, `i8` - `i128`, `usize`, `isize`: from integer literals.
+///   Returns an error if the value does not fit into the type.
+/// - `f32`, `f64`: from float literals and decimal integer literals with float
+///   suffix (e.g. `3f32`). Returns an error if the value is too large for the
+///   type, i.e. if it would be rounded to infinity.
+/// - `Vec<u8>`: from byte string literals.
+/// - `CString`: from C string literals.
+///
+/// For all value types, the literal must not have a suffix, except for the
+/// type suffix matching the requested type (e.g. `27u16` for `u16`).
+///
+/// Signed integers and floats can also be extracted from negated literals like
+/// `-3`. These arrive in proc macros as invisible group when a `macro_rules!`
+/// macro forwards a `$x:literal` or `$x:expr` fragment, or as two separate
+/// tokens in a token stream.
+pub trait FromLiteral: Sized {
+    /// Converts the given literal to `Self`.
+    fn from_literal(lit: Literal<String>) -> Result<Self, ExtractError>;
+
+    /// Converts the given literal, which is preceded by a minus sign, to
+    /// `Self`. The default implementation returns an error.
+    fn from_negated_literal(_lit: Literal<String>) -> Result<Self, ExtractError> {
+        Err(ExtractError::new(ExtractErrorKind::UnexpectedNegation))
+    }
+}
+
+/// An error returned by [`extract`] and [`FromLiteral`]: the literal has the
+/// wrong kind or suffix, its value is out of range, or the token is not a
+/// literal at all.
+#[derive(Debug, Clone)]
+pub struct ExtractError {
+    pub(crate) kind: ExtractErrorKind,
+
+    /// Only `None` if the error was created by `FromLiteral::from_literal`
+    /// directly, i.e. not via `extract`.
+    pub(crate) span: Option<Span>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum ExtractErrorKind {
+    WrongKind {
+        expected: Expected,
+        actual: TokenKind,
+    },
+    OutOfRange(&'static str),
+    UnexpectedNegation,
+    InvalidSuffix {
+        suffix: String,
+        ty: &'static str,
+    },
+    Custom(String),
+}
+
+impl ExtractError {
+    /// Creates an error with a custom message. Useful for implementing
+    /// [`FromLiteral`] for your own types.
+    pub fn custom(msg: impl Into<String>) -> Self {
+        Self::new(ExtractErrorKind::Custom(msg.into()))
+    }
+
+    fn new(kind: ExtractErrorKind) -> Self {
+        Self { kind, span: None }
+    }
+
+    fn wrong_kind(expected: TokenKind, actual: &Literal<String>) -> Self {
+        Self::new(ExtractErrorKind::WrongKind {
+            expected: expected.into(),
+            actual: kind_of(actual),
+        })
+    }
+
+    /// Sets the span, unless it is already set.
+    pub(crate) fn with_span(self, span: Span) -> Self {
+        Self {
+            span: Some(self.span.unwrap_or(span)),
+            ..self
+        }
+    }
+
+    /// Returns a token stream representing `compile_error!("msg");` where
+    /// `"msg"` is the output of `self.to_string()`. **Panics if called outside
+    /// of a proc-macro context!**
+    pub fn to_compile_error(&self) -> proc_macro::TokenStream {
+        let span = self.span.unwrap_or_else(|| proc_macro::Span::call_site().into());
+        err::compile_error(span, &self.to_string())
+    }
+
+    /// Like [`to_compile_error`][Self::to_compile_error], but returns a token
+    /// stream from `proc_macro2` and does not panic outside of a proc-macro
+    /// context.
+    #[cfg(feature = "proc-macro2")]
+    pub fn to_compile_error2(&self) -> proc_macro2::TokenStream {
+        let span = self.span.unwrap_or_else(|| proc_macro2::Span::call_site().into());
+        err::compile_error2(span, &self.to_string())
+    }
+}
+
+impl From<InvalidToken> for ExtractError {
+    fn from(src: InvalidToken) -> Self {
+        Self {
+            kind: ExtractErrorKind::WrongKind { expected: src.expected, actual: src.actual },
+            span: Some(src.span),
+        }
+    }
+}
+
+impl std::error::Error for ExtractError {}
+
+impl fmt::Display for ExtractError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ExtractErrorKind::WrongKind { expected, actual } => write!(
+                f,
+                "expected {}, but found {}",
+                expected,
+                actual.description(),
+            ),
+            ExtractErrorKind::OutOfRange(ty) => {
+                write!(f, "literal is out of range for type `{}`", ty)
+            }
+            ExtractErrorKind::UnexpectedNegation => {
+                write!(f, "literal cannot be negated")
+            }
+            ExtractErrorKind::InvalidSuffix { suffix, ty } => {
+                write!(f, "invalid suffix `{}` for literal of type `{}`", suffix, ty)
+            }
+            ExtractErrorKind::Custom(msg) => msg.fmt(f),
+        }
+    }
+}
+
+/// Makes sure `suffix` is empty or equal to `ty`.
+fn check_suffix(suffix: &str, ty: &'static str) -> Result<(), ExtractError> {
+    if suffix.is_empty() || suffix == ty {
+        Ok(())
+    } else {
+        Err(ExtractError::new(ExtractErrorKind::InvalidSuffix { suffix: suffix.into(), ty }))
+    }
+}
+
+/// Makes sure `suffix` is empty. `ty` is only used for the error message.
+fn check_no_suffix(suffix: &str, ty: &'static str) -> Result<(), ExtractError> {
+    if suffix.is_empty() {
+        Ok(())
+    } else {
+        Err(ExtractError::new(ExtractErrorKind::InvalidSuffix { suffix: suffix.into(), ty }))
+    }
+}
+
+
+// ==============================================================================================
+// ===== `FromLiteral` impls for literal types
+// ==============================================================================================
+
+impl FromLiteral for Literal<String> {
+    fn from_literal(lit: Literal<String>) -> Result<Self, ExtractError> {
+        Ok(lit)
+    }
+}
+
+macro_rules! impl_for_specific_lit {
+    ($ty:ty, $variant:ident, $kind:ident) => {
+        impl FromLiteral for $ty {
+            fn from_literal(lit: Literal<String>) -> Result<Self, ExtractError> {
+                match lit {
+                    Literal::$variant(l) => Ok(l),
+                    other => Err(ExtractError::wrong_kind(TokenKind::$kind, &other)),
+                }
+            }
+        }
+    };
+}
+
+impl_for_specific_lit!(BoolLit, Bool, BoolLit);
+impl_for_specific_lit!(IntegerLit<String>, Integer, IntegerLit);
+impl_for_specific_lit!(FloatLit<String>, Float, FloatLit);
+impl_for_specific_lit!(CharLit<String>, Char, CharLit);
+impl_for_specific_lit!(StringLit<String>, String, StringLit);
+impl_for_specific_lit!(ByteLit<String>, Byte, ByteLit);
+impl_for_specific_lit!(ByteStringLit<String>, ByteString, ByteStringLit);
+impl_for_specific_lit!(CStringLit<String>, CString, CStringLit);
+
+
+// ==============================================================================================
+// ===== `FromLiteral` impls for value types
+// ==============================================================================================
+
+macro_rules! impl_for_value {
+    ($ty:ty, $name:literal, $variant:ident, $kind:ident, |$l:ident| $convert:expr) => {
+        impl FromLiteral for $ty {
+            fn from_literal(lit: Literal<String>) -> Result<Self, ExtractError> {
+                match lit {
+                    Literal::$variant($l) => {
+                        check_no_suffix($l.suffix(), $name)?;
+                        Ok($convert)
+                    }
+                    other => Err(ExtractError::wrong_kind(TokenKind::$kind, &other)),
+                }
+            }
+        }
+    };
+}
+
+impl_for_value!(String, "String", String, StringLit, |l| l.into_value());
+impl_for_value!(PathBuf, "PathBuf", String, StringLit, |l| l.into_value().into());
+impl_for_value!(char, "char", Char, CharLit, |l| l.value());
+impl_for_value!(Vec<u8>, "Vec<u8>", ByteString, ByteStringLit, |l| l.into_value());
+impl_for_value!(CString, "CString", CString, CStringLit, |l| l.into_value());
+
+impl FromLiteral for bool {
+    fn from_literal(lit: Literal<String>) -> Result<Self, ExtractError> {
+        match lit {
+            Literal::Bool(l) => Ok(l.value()),
+            other => Err(ExtractError::wrong_kind(TokenKind::BoolLit, &other)),
+        }
+    }
+}
+
+macro_rules! impl_for_int {
+    ($($ty:ident),*) => {
+        $(
+            impl FromLiteral for $ty {
+                fn from_literal(lit: Literal<String>) -> Result<Self, ExtractError> {
+                    let value = int_value(lit, stringify!($ty))?;
+                    <$ty>::try_from(value).map_err(|_| out_of_range(stringify!($ty)))
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_for_signed_int {
+    ($($ty:ident),*) => {
+        $(
+            impl FromLiteral for $ty {
+                fn from_literal(lit: Literal<String>) -> Result<Self, ExtractError> {
+                    let value = int_value(lit, stringify!($ty))?;
+                    <$ty>::try_from(value).map_err(|_| out_of_range(stringify!($ty)))
+                }
+
+                fn from_negated_literal(lit: Literal<String>) -> Result<Self, ExtractError> {
+                    // The absolute value of `MIN` is `MAX + 1`. Casting that to
+                    // `i128` and negating it works for all types, including
+                    // `i128` itself thanks to two's complement.
+                    let value = int_value(lit, stringify!($ty))?;
+                    if value > <$ty>::MAX as u128 + 1 {
+                        return Err(out_of_range(stringify!($ty)));
+                    }
+                    Ok((value as i128).wrapping_neg() as $ty)
+                }
+            }
+        )*
+    };
+}
+
+impl_for_int!(u8, u16, u32, u64, u128, usize);
+impl_for_signed_int!(i8, i16, i32, i64, i128, isize);
+
+/// Returns the value of the integer literal `lit`, after checking its suffix.
+fn int_value(lit: Literal<String>, ty: &'static str) -> Result<u128, ExtractError> {
+    match lit {
+        Literal::Integer(l) => {
+            check_suffix(l.suffix(), ty)?;
+            l.value::<u128>().ok_or_else(|| out_of_range(ty))
+        }
+        other => Err(ExtractError::wrong_kind(TokenKind::IntegerLit, &other)),
+    }
+}
+
+fn out_of_range(ty: &'static str) -> ExtractError {
+    ExtractError::new(ExtractErrorKind::OutOfRange(ty))
+}
+
+macro_rules! impl_for_float {
+    ($ty:ident, $variant:ident) => {
+        impl FromLiteral for $ty {
+            fn from_literal(lit: Literal<String>) -> Result<Self, ExtractError> {
+                float_value(lit, FloatType::$variant)
+            }
+
+            fn from_negated_literal(lit: Literal<String>) -> Result<Self, ExtractError> {
+                float_value::<$ty>(lit, FloatType::$variant).map(|v| -v)
+            }
+        }
+    };
+}
+
+/// Returns the value of the float literal `lit`, after checking its suffix
+/// and range.
+fn float_value<F: FromFloatLiteral>(
+    lit: Literal<String>,
+    ty: FloatType,
+) -> Result<F, ExtractError> {
+    let out_of_range = || out_of_range(ty.suffix());
+    match lit {
+        Literal::Float(l) => {
+            check_suffix(l.suffix(), ty.suffix())?;
+            if l.rounding(ty) == FloatRounding::Infinity {
+                return Err(out_of_range());
+            }
+            Ok(l.value())
+        }
+
+        // Integer literals are only floats if they have a float suffix, like
+        // `3f32`.
+        Literal::Integer(l) if l.suffix() == ty.suffix() && l.base() == IntegerBase::Decimal => {
+            let digits = l.raw_main_part();
+            if float::integer_rounding(digits, ty) == FloatRounding::Infinity {
+                return Err(out_of_range());
+            }
+            Ok(float::integer_value(digits))
+        }
+        other => Err(ExtractError::wrong_kind(TokenKind::FloatLit, &other)),
+    }
+}
+
+impl_for_float!(f32, F32);
+impl_for_float!(f64, F64);
+
+
+#[cfg(test)]
+mod tests;
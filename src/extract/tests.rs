@@ -0,0 +1,228 @@
+use std::{ffi::CString, path::PathBuf};
+
+use super::{ExtractError, FromLiteral};
+use crate::{IntegerLit, Literal, StringLit};
+
+
+// ===== Utility functions =======================================================================
+
+#[track_caller]
+fn from<T: FromLiteral>(input: &str) -> Result<T, ExtractError> {
+    T::from_literal(Literal::parse(input.to_owned()).expect("invalid literal"))
+}
+
+#[track_caller]
+fn err<T: FromLiteral + std::fmt::Debug>(input: &str) -> String {
+    from::<T>(input).unwrap_err().to_string()
+}
+
+
+// ===== Actual tests ============================================================================
+
+#[test]
+fn literal_types() {
+    assert!(matches!(from::<Literal<String>>("3px").unwrap(), Literal::Integer(_)));
+    assert_eq!(from::<StringLit<String>>(r#""foo"bar"#).unwrap().suffix(), "bar");
+    assert_eq!(from::<IntegerLit<String>>("0xffu8").unwrap().value::<u8>(), Some(255));
+    assert_eq!(
+        err::<IntegerLit<String>>("'x'"),
+        "expected an integer literal (e.g. `27`), but found a character literal (e.g. `'P'`)",
+    );
+}
+
+#[test]
+fn strings_and_bytes() {
+    assert_eq!(from::<String>(r#""a\tb""#).unwrap(), "a\tb");
+    assert_eq!(from::<String>(r##"r#"raw"#"##).unwrap(), "raw");
+    assert_eq!(from::<PathBuf>(r#""/tmp/foo""#).unwrap(), PathBuf::from("/tmp/foo"));
+    assert_eq!(from::<char>(r"'\u{1F980}'").unwrap(), '🦀');
+    assert!(from::<bool>("true").unwrap());
+    assert!(!from::<bool>("false").unwrap());
+    assert_eq!(from::<Vec<u8>>(r#"b"\xffa""#).unwrap(), b"\xffa");
+    assert_eq!(from::<CString>(r#"c"hi""#).unwrap(), CString::new("hi").unwrap());
+
+    assert_eq!(
+        err::<String>("'x'"),
+        r#"expected a string literal (e.g. "Ferris"), but found a character literal (e.g. `'P'`)"#,
+    );
+    assert_eq!(
+        err::<Vec<u8>>(r#""foo""#),
+        r#"expected a byte string literal (e.g. `b"fox"`), but found a string literal (e.g. "Ferris")"#,
+    );
+    assert_eq!(
+        err::<bool>("1"),
+        "expected a bool literal (`true` or `false`), but found an integer literal (e.g. `27`)",
+    );
+    assert_eq!(err::<String>(r#""foo"bar"#), "invalid suffix `bar` for literal of type `String`");
+    assert_eq!(err::<char>("'x'y"), "invalid suffix `y` for literal of type `char`");
+
+    // The type name is not a valid suffix for non-numeric types.
+    assert_eq!(
+        err::<String>(r#""foo"String"#),
+        "invalid suffix `String` for literal of type `String`",
+    );
+    assert_eq!(err::<char>("'a'char"), "invalid suffix `char` for literal of type `char`");
+    assert_eq!(
+        err::<PathBuf>(r#""p"PathBuf"#),
+        "invalid suffix `PathBuf` for literal of type `PathBuf`",
+    );
+}
+
+#[test]
+fn integers() {
+    assert_eq!(from::<u8>("255").unwrap(), 255);
+    assert_eq!(from::<u8>("0xff_u8").unwrap(), 255);
+    assert_eq!(from::<u16>("0o17").unwrap(), 15);
+    assert_eq!(from::<i8>("127i8").unwrap(), 127);
+    assert_eq!(from::<u128>("0xffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff").unwrap(), u128::MAX);
+    assert_eq!(from::<usize>("0b101").unwrap(), 5);
+
+    assert_eq!(err::<u8>("256"), "literal is out of range for type `u8`");
+    assert_eq!(err::<i8>("128"), "literal is out of range for type `i8`");
+    assert_eq!(err::<u16>("3u8"), "invalid suffix `u8` for literal of type `u16`");
+    assert_eq!(err::<u16>("3px"), "invalid suffix `px` for literal of type `u16`");
+    assert_eq!(
+        err::<u32>("3.0"),
+        "expected an integer literal (e.g. `27`), but found a float literal (e.g. `3.14`)",
+    );
+}
+
+#[test]
+fn floats() {
+    assert_eq!(from::<f64>("2.75").unwrap(), 2.75);
+    assert_eq!(from::<f32>("0.1f32").unwrap(), 0.1);
+    assert_eq!(from::<f32>("1e-50").unwrap(), 0.0);
+    assert_eq!(from::<f32>("3f32").unwrap(), 3.0);
+    assert_eq!(from::<f64>("1_000f64").unwrap(), 1000.0);
+
+    assert_eq!(err::<f32>("1e39"), "literal is out of range for type `f32`");
+    assert_eq!(err::<f32>("1000000000000000000000000000000000000000f32"),
+        "literal is out of range for type `f32`");
+    assert_eq!(err::<f32>("3.0f64"), "invalid suffix `f64` for literal of type `f32`");
+    assert_eq!(
+        err::<f64>("3"),
+        "expected a float literal (e.g. `3.14`), but found an integer literal (e.g. `27`)",
+    );
+    assert!(from::<f32>("0x3f32").is_err());
+}
+
+#[test]
+fn custom() {
+    struct Even(u32);
+
+    impl FromLiteral for Even {
+        fn from_literal(lit: Literal<String>) -> Result<Self, ExtractError> {
+            let n = u32::from_literal(lit)?;
+            if n % 2 == 0 {
+                Ok(Even(n))
+            } else {
+                Err(ExtractError::custom(format!("{} is not even", n)))
+            }
+        }
+    }
+
+    assert_eq!(from::<Even>("4").unwrap().0, 4);
+    assert_eq!(from::<Even>("5").err().unwrap().to_string(), "5 is not even");
+}
+
+#[cfg(feature = "proc-macro2")]
+#[test]
+fn extract_from_tokens() {
+    use crate::extract;
+    use proc_macro2::{Ident, Span, TokenTree};
+
+    let lit = proc_macro2::Literal::u16_suffixed(8080);
+    assert_eq!(extract::<u16>(&lit).unwrap(), 8080);
+    assert_eq!(extract::<u16>(TokenTree::from(lit.clone())).unwrap(), 8080);
+    assert_eq!(
+        extract::<u8>(lit).unwrap_err().to_string(),
+        "invalid suffix `u16` for literal of type `u8`",
+    );
+
+    let tt = TokenTree::from(Ident::new("true", Span::call_site()));
+    assert!(extract::<bool>(&tt).unwrap());
+    assert!(extract::<String>(&tt).is_err());
+
+    let tt = TokenTree::from(Ident::new("foo", Span::call_site()));
+    let e = extract::<u16>(tt).unwrap_err();
+    assert_eq!(e.to_string(), "expected a literal, but found an identifier");
+    assert!(e.to_compile_error2().to_string().starts_with("compile_error !"));
+}
+
+#[test]
+fn negated() {
+    #[track_caller]
+    fn neg<T: FromLiteral>(input: &str) -> Result<T, ExtractError> {
+        T::from_negated_literal(Literal::parse(input.to_owned()).expect("invalid literal"))
+    }
+
+    assert_eq!(neg::<i8>("3").unwrap(), -3);
+    assert_eq!(neg::<i8>("128").unwrap(), i8::MIN);
+    assert_eq!(neg::<i8>("0x80i8").unwrap(), i8::MIN);
+    assert_eq!(neg::<i32>("0").unwrap(), 0);
+    assert_eq!(neg::<i128>("170141183460469231731687303715884105728").unwrap(), i128::MIN);
+    assert_eq!(neg::<isize>("1").unwrap(), -1);
+    assert_eq!(neg::<f32>("2.5").unwrap(), -2.5);
+    assert_eq!(neg::<f64>("1e3f64").unwrap(), -1000.0);
+
+    assert_eq!(neg::<i8>("129").err().unwrap().to_string(), "literal is out of range for type `i8`");
+    assert_eq!(
+        neg::<i128>("170141183460469231731687303715884105729").err().unwrap().to_string(),
+        "literal is out of range for type `i128`",
+    );
+    assert_eq!(neg::<u8>("3").err().unwrap().to_string(), "literal cannot be negated");
+    assert_eq!(neg::<String>(r#""a""#).err().unwrap().to_string(), "literal cannot be negated");
+    assert_eq!(neg::<bool>("true").err().unwrap().to_string(), "literal cannot be negated");
+    assert_eq!(
+        neg::<i8>("3.0").err().unwrap().to_string(),
+        "expected an integer literal (e.g. `27`), but found a float literal (e.g. `3.14`)",
+    );
+}
+
+#[cfg(feature = "proc-macro2")]
+#[test]
+fn extract_negated_tokens() {
+    use crate::extract;
+    use proc_macro2::{Delimiter, Group, Punct, Spacing, TokenStream, TokenTree};
+
+    let invisible = |s: &str| {
+        TokenTree::from(Group::new(Delimiter::None, s.parse::<TokenStream>().unwrap()))
+    };
+
+    assert_eq!(extract::<i32>(invisible("-3")).unwrap(), -3);
+    assert_eq!(extract::<i32>(&invisible("- 3i32")).unwrap(), -3);
+    assert_eq!(extract::<f64>(invisible("-2.5")).unwrap(), -2.5);
+    assert_eq!(extract::<i32>(invisible("3")).unwrap(), 3);
+    assert_eq!(
+        extract::<u32>(invisible("-3")).unwrap_err().to_string(),
+        "literal cannot be negated",
+    );
+    assert!(extract::<i32>(invisible("+3")).is_err());
+    assert!(extract::<i32>(invisible("-3 4")).is_err());
+
+    // Nested invisible groups.
+    let minus = TokenTree::from(Punct::new('-', Spacing::Alone));
+    let nested = [minus, invisible("7")].into_iter().collect::<TokenStream>();
+    let nested = TokenTree::from(Group::new(Delimiter::None, nested));
+    assert_eq!(extract::<i64>(nested.clone()).unwrap(), -7);
+    let nested = TokenTree::from(Group::new(Delimiter::None, nested.into()));
+    assert_eq!(extract::<i64>(nested).unwrap(), -7);
+
+    // Token streams.
+    let stream = |s: &str| s.parse::<TokenStream>().unwrap();
+    assert_eq!(extract::<i16>(stream("-300")).unwrap(), -300);
+    assert_eq!(extract::<u16>(stream("300")).unwrap(), 300);
+    assert_eq!(extract::<String>(stream(r#""hi""#)).unwrap(), "hi");
+    assert_eq!(
+        extract::<u16>(stream("")).unwrap_err().to_string(),
+        "expected a literal, but found the end of input",
+    );
+    assert_eq!(
+        extract::<u16>(stream("1, 2")).unwrap_err().to_string(),
+        "expected the end of input, but found a punctuation character",
+    );
+
+    // Negative literal tokens.
+    assert_eq!(extract::<i8>(proc_macro2::Literal::i8_unsuffixed(-128)).unwrap(), -128);
+    assert_eq!(extract::<f32>(proc_macro2::Literal::f32_suffixed(-1.5)).unwrap(), -1.5);
+}
@@ -0,0 +1,397 @@
+//! Exact conversion from decimal float literals to binary floating point
+//! values. `std` can only do this for `f32` and `f64` and doesn't tell us
+//! whether the result is exact, so we do it ourselves with a tiny bignum.
+
+use std::cmp::Ordering;
+
+
+/// Parameters of a binary IEEE 754 floating point format.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Format {
+    /// Number of significand bits, including the implicit leading bit.
+    pub(crate) precision: u32,
+    /// Exponent of the largest finite value. Also the exponent bias.
+    pub(crate) emax: i64,
+}
+
+impl Format {
+    pub(crate) const F16: Self = Self { precision: 11, emax: 15 };
+    pub(crate) const F32: Self = Self { precision: 24, emax: 127 };
+    pub(crate) const F64: Self = Self { precision: 53, emax: 1023 };
+    pub(crate) const F128: Self = Self { precision: 113, emax: 16383 };
+
+    fn emin(self) -> i64 {
+        1 - self.emax
+    }
+}
+
+/// A value rounded to a binary floating point format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Rounded {
+    /// The value `mantissa * 2^exp`. `mantissa` is 0 for the value zero.
+    Finite { mantissa: u128, exp: i64, exact: bool },
+    /// The value is too large for the format.
+    Infinity,
+}
+
+impl Rounded {
+    /// Returns the IEEE 754 bit pattern of this value, in the lowest bits of
+    /// the returned integer.
+    pub(crate) fn to_bits(self, format: Format) -> u128 {
+        let frac_bits = format.precision - 1;
+        match self {
+            Rounded::Infinity => ((2 * format.emax + 1) as u128) << frac_bits,
+            Rounded::Finite { mantissa, exp, .. } => {
+                if mantissa >> frac_bits == 0 {
+                    // Zero or subnormal: biased exponent is 0.
+                    mantissa
+                } else {
+                    let biased = (exp + frac_bits as i64 + format.emax) as u128;
+                    (biased << frac_bits) | (mantissa & ((1 << frac_bits) - 1))
+                }
+            }
+        }
+    }
+}
+
+/// A decimal number `digits * 10^exp`. `digits` only contains ASCII digits.
+#[derive(Debug, Clone)]
+pub(crate) struct Decimal {
+    pub(crate) digits: String,
+    pub(crate) exp: i64,
+}
+
+/// Values with more than this many decimal digits before or after the point
+/// are infinite or zero in all supported formats.
+const MAX_DECIMAL_EXP: i64 = 5100;
+
+impl Decimal {
+    /// Creates a decimal from the parts of a float literal. Underscores are
+    /// ignored, `exponent` may be empty or include the leading `e` and sign.
+    pub(crate) fn from_parts(integer: &str, fraction: &str, exponent: &str) -> Self {
+        let exponent = exponent.trim_start_matches(|c| c == 'e' || c == 'E');
+        let (negative, exponent) = match exponent.as_bytes().first() {
+            Some(b'-') => (true, &exponent[1..]),
+            Some(b'+') => (false, &exponent[1..]),
+            _ => (false, exponent),
+        };
+
+        // Saturate to keep the arithmetic below from overflowing. Everything
+        // this large is infinite or zero anyway.
+        let mut exp: i64 = 0;
+        for b in exponent.bytes().filter(|&b| b != b'_') {
+            exp = (exp * 10 + (b - b'0') as i64).min(i64::MAX / 100);
+        }
+        if negative {
+            exp = -exp;
+        }
+
+        let mut digits = String::with_capacity(integer.len() + fraction.len());
+        digits.extend(integer.chars().filter(|&c| c != '_'));
+        let fraction_len = fraction.bytes().filter(|&b| b != b'_').count();
+        digits.extend(fraction.chars().filter(|&c| c != '_'));
+
+        Self { digits, exp: exp - fraction_len as i64 }
+    }
+
+    /// Rounds this value to the nearest value representable in `format`,
+    /// with ties to even.
+    pub(crate) fn round(&self, format: Format) -> Rounded {
+        let digits = self.digits.trim_start_matches('0');
+        if digits.is_empty() {
+            return Rounded::Finite { mantissa: 0, exp: 0, exact: true };
+        }
+
+        // Cheap checks for absurdly large or small values.
+        let magnitude = self.exp + digits.len() as i64;
+        if magnitude > MAX_DECIMAL_EXP {
+            return Rounded::Infinity;
+        }
+        if magnitude < -MAX_DECIMAL_EXP {
+            return Rounded::Finite { mantissa: 0, exp: 0, exact: false };
+        }
+
+        // value = num / den
+        let mut num = Big::from_decimal(digits);
+        let mut den = Big::from_u64(1);
+        if self.exp >= 0 {
+            num.mul_pow10(self.exp as u32);
+        } else {
+            den.mul_pow10((-self.exp) as u32);
+        }
+
+        // Find `e` such that 2^e <= value < 2^(e+1).
+        let mut e = num.bit_len() as i64 - den.bit_len() as i64;
+        if cmp_scaled(&num, &den, e) == Ordering::Less {
+            e -= 1;
+        }
+
+        // The exponent of the last significand bit. We divide by `2^ulp_exp`
+        // to get the significand as integer.
+        let precision = format.precision as i64;
+        let mut ulp_exp = e.max(format.emin()) - (precision - 1);
+        if ulp_exp <= 0 {
+            num.shl((-ulp_exp) as usize);
+        } else {
+            den.shl(ulp_exp as usize);
+        }
+        let (mut mantissa, rem) = num.div_rem(&den, format.precision + 1);
+
+        let exact = rem.is_zero();
+        let mut twice_rem = rem;
+        twice_rem.shl(1);
+        match twice_rem.cmp(&den) {
+            Ordering::Greater => mantissa += 1,
+            Ordering::Equal if mantissa & 1 == 1 => mantissa += 1,
+            _ => {}
+        }
+        if mantissa >> format.precision != 0 {
+            mantissa >>= 1;
+            ulp_exp += 1;
+        }
+
+        if mantissa != 0 && ulp_exp + precision - 1 > format.emax {
+            return Rounded::Infinity;
+        }
+
+        Rounded::Finite { mantissa, exp: ulp_exp, exact }
+    }
+}
+
+/// Returns the shortest decimal that rounds to the same value as `value` in
+/// `format`, as `(digits, exp)` representing `0.digits * 10^exp`. `value`
+/// must be finite and not zero.
+pub(crate) fn shortest(value: Rounded, format: Format) -> (String, i64) {
+    let (mantissa, value_exp) = match value {
+        Rounded::Finite { mantissa, exp, .. } if mantissa != 0 => (mantissa, exp),
+        _ => panic!("bug: `shortest` called with zero or infinite value"),
+    };
+
+    // The exact decimal value: `mantissa * 2^exp`, which is equal to
+    // `mantissa * 5^-exp * 10^exp` for negative `exp`.
+    let mut exact = Big::from_u128(mantissa);
+    let mut dec_exp = 0;
+    if value_exp >= 0 {
+        exact.shl(value_exp as usize);
+    } else {
+        exact.mul_pow5((-value_exp) as u32);
+        dec_exp = value_exp;
+    }
+    let exact = exact.to_decimal();
+    let point = exact.len() as i64 + dec_exp;
+
+    // Checks whether `digits * 10^exp` rounds to `value`.
+    let roundtrips = |digits: &str, exp: i64| {
+        let d = Decimal { digits: digits.to_owned(), exp };
+        match d.round(format) {
+            Rounded::Finite { mantissa: m, exp: e, .. } => (m, e) == (mantissa, value_exp),
+            Rounded::Infinity => false,
+        }
+    };
+
+    for len in 1..exact.len() {
+        let down = exact[..len].to_owned();
+        let up = increment(&down);
+
+        // Prefer the candidate closer to the exact value. On ties, we round
+        // up, like `std` does when formatting floats.
+        let up_is_closer = exact.as_bytes()[len] >= b'5';
+        let candidates = if up_is_closer { [&up, &down] } else { [&down, &up] };
+        for candidate in candidates {
+            if roundtrips(candidate, point - len as i64) {
+                let point = point + (candidate.len() - len) as i64;
+                return (candidate.trim_end_matches('0').to_owned(), point);
+            }
+        }
+    }
+
+    (exact.trim_end_matches('0').to_owned(), point)
+}
+
+/// Adds one to the decimal number `digits`. The result might be one digit
+/// longer.
+fn increment(digits: &str) -> String {
+    let mut out = digits.as_bytes().to_vec();
+    for d in out.iter_mut().rev() {
+        if *d == b'9' {
+            *d = b'0';
+        } else {
+            *d += 1;
+            return String::from_utf8(out).unwrap();
+        }
+    }
+    out.insert(0, b'1');
+    String::from_utf8(out).unwrap()
+}
+
+/// Compares `a` with `b * 2^shift`.
+fn cmp_scaled(a: &Big, b: &Big, shift: i64) -> Ordering {
+    if shift >= 0 {
+        let mut b = b.clone();
+        b.shl(shift as usize);
+        a.cmp(&b)
+    } else {
+        let mut a = a.clone();
+        a.shl((-shift) as usize);
+        a.cmp(b)
+    }
+}
+
+
+/// Minimal arbitrary precision unsigned integer. Little endian `u32` limbs
+/// without trailing zero limbs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Big(Vec<u32>);
+
+impl Big {
+    fn from_u64(v: u64) -> Self {
+        Self::from_u128(v.into())
+    }
+
+    fn from_u128(mut v: u128) -> Self {
+        let mut limbs = Vec::new();
+        while v != 0 {
+            limbs.push(v as u32);
+            v >>= 32;
+        }
+        Self(limbs)
+    }
+
+    fn from_decimal(digits: &str) -> Self {
+        let mut out = Self(Vec::new());
+        for chunk in digits.as_bytes().chunks(9) {
+            let v = chunk.iter().fold(0, |acc, &d| acc * 10 + (d - b'0') as u32);
+            out.mul_add_small(10u32.pow(chunk.len() as u32), v);
+        }
+        out
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn bit_len(&self) -> usize {
+        match self.0.last() {
+            None => 0,
+            Some(last) => self.0.len() * 32 - last.leading_zeros() as usize,
+        }
+    }
+
+    /// `self = self * m + a`
+    fn mul_add_small(&mut self, m: u32, a: u32) {
+        let mut carry = a as u64;
+        for limb in &mut self.0 {
+            let v = *limb as u64 * m as u64 + carry;
+            *limb = v as u32;
+            carry = v >> 32;
+        }
+        if carry != 0 {
+            self.0.push(carry as u32);
+        }
+    }
+
+    fn mul_pow5(&mut self, mut n: u32) {
+        // 5^13 is the largest power of 5 fitting into `u32`.
+        while n > 0 {
+            let step = n.min(13);
+            self.mul_add_small(5u32.pow(step), 0);
+            n -= step;
+        }
+    }
+
+    fn mul_pow10(&mut self, n: u32) {
+        self.mul_pow5(n);
+        self.shl(n as usize);
+    }
+
+    fn shl(&mut self, n: usize) {
+        if self.is_zero() {
+            return;
+        }
+        let (limbs, bits) = (n / 32, n % 32);
+        if bits != 0 {
+            let mut carry = 0;
+            for limb in &mut self.0 {
+                let v = *limb;
+                *limb = (v << bits) | carry;
+                carry = v >> (32 - bits);
+            }
+            if carry != 0 {
+                self.0.push(carry);
+            }
+        }
+        self.0.splice(0..0, std::iter::repeat(0).take(limbs));
+    }
+
+    /// `self -= other`. Requires `self >= other`.
+    fn sub(&mut self, other: &Self) {
+        let mut borrow = false;
+        for (i, limb) in self.0.iter_mut().enumerate() {
+            let rhs = other.0.get(i).copied().unwrap_or(0);
+            let (v, b1) = limb.overflowing_sub(rhs);
+            let (v, b2) = v.overflowing_sub(borrow as u32);
+            *limb = v;
+            borrow = b1 || b2;
+        }
+        while self.0.last() == Some(&0) {
+            self.0.pop();
+        }
+    }
+
+    /// Divides `self` by `den`, returning quotient and remainder. The quotient
+    /// must fit into `quotient_bits` bits (at most 128).
+    fn div_rem(mut self, den: &Self, quotient_bits: u32) -> (u128, Self) {
+        let mut quotient = 0u128;
+        for i in (0..quotient_bits).rev() {
+            let mut shifted = den.clone();
+            shifted.shl(i as usize);
+            if self >= shifted {
+                self.sub(&shifted);
+                quotient |= 1 << i;
+            }
+        }
+        debug_assert!(self < *den, "bug: quotient does not fit");
+        (quotient, self)
+    }
+
+    fn to_decimal(&self) -> String {
+        let mut chunks = Vec::new();
+        let mut v = self.clone();
+        while !v.is_zero() {
+            // Divide by 10^9 and collect remainders.
+            let mut rem = 0u64;
+            for limb in v.0.iter_mut().rev() {
+                let cur = (rem << 32) | *limb as u64;
+                *limb = (cur / 1_000_000_000) as u32;
+                rem = cur % 1_000_000_000;
+            }
+            while v.0.last() == Some(&0) {
+                v.0.pop();
+            }
+            chunks.push(rem as u32);
+        }
+
+        let mut out = match chunks.pop() {
+            None => return "0".into(),
+            Some(first) => first.to_string(),
+        };
+        for chunk in chunks.iter().rev() {
+            out.push_str(&format!("{:09}", chunk));
+        }
+        out
+    }
+}
+
+impl PartialOrd for Big {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Big {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.len()
+            .cmp(&other.0.len())
+            .then_with(|| self.0.iter().rev().cmp(other.0.iter().rev()))
+    }
+}
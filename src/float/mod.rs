@@ -6,6 +6,10 @@ use crate::{
     Buffer, ParseError,
 };
 
+use self::convert::{Decimal, Format, Rounded};
+
+mod convert;
+
 
 
 /// A floating point literal, e.g. `3.14`, `8.`, `135e12`, or `1.956e2f64`.
@@ -121,6 +125,210 @@ impl<B: Buffer> FloatLit<B> {
     pub fn into_raw_input(self) -> B {
         self.raw
     }
+
+    /// Returns a new literal with the given suffix, keeping the number part
+    /// as is. Pass `""` to remove the suffix (or use
+    /// [`strip_suffix`][Self::strip_suffix]). Returns an error if the suffix
+    /// is invalid or the literal cannot have a suffix, e.g. `1.`.
+    ///
+    /// ```
+    /// use litrs::FloatLit;
+    ///
+    /// let lit = FloatLit::parse("1_000.5e3").unwrap();
+    /// assert_eq!(lit.with_suffix("f32").unwrap().raw_input(), "1_000.5e3f32");
+    /// assert!(FloatLit::parse("1.").unwrap().with_suffix("f32").is_err());
+    /// ```
+    pub fn with_suffix(&self, suffix: &str) -> Result<FloatLit<String>, ParseError> {
+        let raw = format!("{}{}", self.number_part(), suffix);
+        let lit = FloatLit::parse(raw)?;
+        if lit.end_number_part != self.end_number_part {
+            return Err(perr(self.end_number_part..lit.raw.len(), InvalidSuffix));
+        }
+        Ok(lit)
+    }
+
+    /// Returns a copy of this literal without suffix.
+    pub fn strip_suffix(&self) -> FloatLit<String> {
+        FloatLit {
+            raw: self.number_part().to_owned(),
+            end_integer_part: self.end_integer_part,
+            end_fractional_part: self.end_fractional_part,
+            end_number_part: self.end_number_part,
+        }
+    }
+
+    /// Returns a copy of this literal with all `_` removed from the number
+    /// part. The suffix is kept.
+    ///
+    /// ```
+    /// use litrs::FloatLit;
+    ///
+    /// let lit = FloatLit::parse("1_000.000_1e1_0_f64").unwrap();
+    /// assert_eq!(lit.without_underscores().raw_input(), "1000.0001e10f64");
+    /// ```
+    pub fn without_underscores(&self) -> FloatLit<String> {
+        let mut raw = String::with_capacity(self.raw.len());
+        let mut push = |s: &str| {
+            raw.extend(s.chars().filter(|&c| c != '_'));
+            raw.len()
+        };
+
+        let end_integer_part = push(self.integer_part());
+        let fractional_part = &(*self.raw)[self.end_integer_part..self.end_fractional_part];
+        let end_fractional_part = push(fractional_part);
+        let end_number_part = push(self.exponent_part());
+        raw.push_str(self.suffix());
+
+        FloatLit { raw, end_integer_part, end_fractional_part, end_number_part }
+    }
+
+    /// Converts this literal to the float value it represents, rounding to the
+    /// nearest representable value (ties to even), exactly like the Rust
+    /// compiler does. Values that are too large are converted to infinity.
+    ///
+    /// The optional type suffix of the literal **is ignored by this method**.
+    /// This means `F` does not need to match the type suffix!
+    ///
+    /// Besides `f32` and `f64`, this can return `half::f16` if the crate
+    /// feature `half` is enabled. For `f128`, see [`to_bits`][Self::to_bits].
+    ///
+    /// ```
+    /// use litrs::FloatLit;
+    ///
+    /// let lit = FloatLit::parse("3.14f32").unwrap();
+    /// assert_eq!(lit.value::<f32>(), 3.14f32);
+    /// assert_eq!(lit.value::<f64>(), 3.14f64);
+    /// assert_eq!(FloatLit::parse("1e40").unwrap().value::<f32>(), f32::INFINITY);
+    /// ```
+    pub fn value<F: FromFloatLiteral>(&self) -> F {
+        F::from_bits(self.to_bits(F::ty()))
+    }
+
+    /// Returns the IEEE 754 binary representation of the value of this literal
+    /// as `ty`, in the lowest bits of the returned integer. Like
+    /// [`value`][Self::value], the suffix is ignored.
+    ///
+    /// This is mainly useful for `f16` and `f128`, which are not available in
+    /// stable Rust yet. With nightly Rust, you can use e.g.
+    /// `f128::from_bits(lit.to_bits(FloatType::F128))`.
+    ///
+    /// ```
+    /// use litrs::{FloatLit, FloatType};
+    ///
+    /// let lit = FloatLit::parse("1.5").unwrap();
+    /// assert_eq!(lit.to_bits(FloatType::F16), 0x3e00);
+    /// assert_eq!(lit.to_bits(FloatType::F32), 1.5f32.to_bits() as u128);
+    /// assert_eq!(lit.to_bits(FloatType::F128), 0x3fff_8000_0000_0000_0000_0000_0000_0000);
+    /// ```
+    pub fn to_bits(&self, ty: FloatType) -> u128 {
+        let format = ty.format();
+        self.round(format).to_bits(format)
+    }
+
+    /// Returns how the value of this literal is rounded when converted to
+    /// `ty`. The suffix of the literal **is ignored by this method**. If you
+    /// want the type the literal would have in Rust, use
+    /// `FloatType::from_suffix(lit.suffix()).unwrap_or(FloatType::F64)`
+    /// (unsuffixed literals might also be inferred as `f32` though).
+    ///
+    /// ```
+    /// use litrs::{FloatLit, FloatRounding, FloatType};
+    ///
+    /// let lit = FloatLit::parse("0.1").unwrap();
+    /// assert_eq!(lit.rounding(FloatType::F64), FloatRounding::Inexact);
+    ///
+    /// let lit = FloatLit::parse("1e40").unwrap();
+    /// assert_eq!(lit.rounding(FloatType::F64), FloatRounding::Inexact);
+    /// assert_eq!(lit.rounding(FloatType::F32), FloatRounding::Infinity);
+    /// ```
+    pub fn rounding(&self, ty: FloatType) -> FloatRounding {
+        classify(self.round(ty.format()))
+    }
+
+    /// Returns whether the value of this literal can be represented exactly
+    /// by `ty`, e.g. `0.5` or `1e10`, but not `0.1`. The suffix of the literal
+    /// is ignored. Shorthand for `self.rounding(ty) == FloatRounding::Exact`.
+    pub fn is_exactly_representable(&self, ty: FloatType) -> bool {
+        self.rounding(ty) == FloatRounding::Exact
+    }
+
+    /// Returns the shortest float literal that has the same value as this
+    /// literal when converted to `ty`, or `None` if the value is infinite. The
+    /// suffix of this literal is kept, but is otherwise ignored.
+    ///
+    /// This is useful to find literals with excessive precision, i.e. digits
+    /// that do not influence the value (like Clippy's `excessive_precision`
+    /// lint does).
+    ///
+    /// ```
+    /// use litrs::{FloatLit, FloatType};
+    ///
+    /// let lit = FloatLit::parse("3.14159265358979323846f32").unwrap();
+    /// assert_eq!(lit.shortest_repr(FloatType::F32).unwrap(), "3.1415927f32");
+    /// assert_eq!(lit.shortest_repr(FloatType::F64).unwrap(), "3.141592653589793f32");
+    ///
+    /// let lit = FloatLit::parse("1_000.000_000").unwrap();
+    /// assert_eq!(lit.shortest_repr(FloatType::F64).unwrap(), "1000.0");
+    /// ```
+    pub fn shortest_repr(&self, ty: FloatType) -> Option<String> {
+        let format = ty.format();
+        let value = self.round(format);
+        let (digits, point) = match value {
+            Rounded::Infinity => return None,
+            Rounded::Finite { mantissa: 0, .. } => ("0".to_owned(), 1),
+            _ => convert::shortest(value, format),
+        };
+
+        // Like `Debug` for floats in `std`, we use the exponent notation
+        // for very large and small numbers.
+        let mut out = if !(-3..=16).contains(&point) {
+            let (first, rest) = digits.split_at(1);
+            let dot = if rest.is_empty() { "" } else { "." };
+            format!("{}{}{}e{}", first, dot, rest, point - 1)
+        } else if point <= 0 {
+            format!("0.{}{}", "0".repeat((-point) as usize), digits)
+        } else if point as usize >= digits.len() {
+            format!("{}{}.0", digits, "0".repeat(point as usize - digits.len()))
+        } else {
+            let (int, frac) = digits.split_at(point as usize);
+            format!("{}.{}", int, frac)
+        };
+
+        out.push_str(self.suffix());
+        Some(out)
+    }
+
+    fn round(&self, format: Format) -> Rounded {
+        let decimal = Decimal::from_parts(
+            self.integer_part(),
+            self.fractional_part().unwrap_or(""),
+            self.exponent_part(),
+        );
+        decimal.round(format)
+    }
+}
+
+/// Maps the result of rounding to the public classification.
+fn classify(rounded: Rounded) -> FloatRounding {
+    match rounded {
+        Rounded::Infinity => FloatRounding::Infinity,
+        Rounded::Finite { mantissa: 0, exact: false, .. } => FloatRounding::Zero,
+        Rounded::Finite { exact: true, .. } => FloatRounding::Exact,
+        Rounded::Finite { exact: false, .. } => FloatRounding::Inexact,
+    }
+}
+
+/// Like [`FloatLit::rounding`], but for a decimal integer (which may contain
+/// `_`). Used for integer literals with float suffix, like `3f32`.
+pub(crate) fn integer_rounding(digits: &str, ty: FloatType) -> FloatRounding {
+    classify(Decimal::from_parts(digits, "", "").round(ty.format()))
+}
+
+/// Like [`FloatLit::value`], but for a decimal integer (which may contain
+/// `_`).
+pub(crate) fn integer_value<F: FromFloatLiteral>(digits: &str) -> F {
+    let format = F::ty().format();
+    F::from_bits(Decimal::from_parts(digits, "", "").round(format).to_bits(format))
 }
 
 impl FloatLit<&str> {
@@ -210,12 +418,85 @@ pub(crate) fn parse_impl(input: &str) -> Result<FloatLit<&str>, ParseError> {
 }
 
 
+/// Float types a literal can be converted to. *Implementation detail*.
+///
+/// Implemented for `f32`, `f64` and, with the crate feature `half`, for
+/// `half::f16`. This trait is sealed and cannot be implemented outside of
+/// this crate. The trait's methods are implementation detail of this library
+/// and are not subject to semver.
+pub trait FromFloatLiteral: self::sealed::Sealed + Copy {
+    /// Creates itself from the IEEE 754 representation in the lowest bits.
+    #[doc(hidden)]
+    fn from_bits(bits: u128) -> Self;
+
+    #[doc(hidden)]
+    fn ty() -> FloatType;
+}
+
+impl self::sealed::Sealed for f32 {}
+impl FromFloatLiteral for f32 {
+    fn from_bits(bits: u128) -> Self {
+        f32::from_bits(bits as u32)
+    }
+    fn ty() -> FloatType {
+        FloatType::F32
+    }
+}
+
+impl self::sealed::Sealed for f64 {}
+impl FromFloatLiteral for f64 {
+    fn from_bits(bits: u128) -> Self {
+        f64::from_bits(bits as u64)
+    }
+    fn ty() -> FloatType {
+        FloatType::F64
+    }
+}
+
+#[cfg(feature = "half")]
+impl self::sealed::Sealed for half::f16 {}
+#[cfg(feature = "half")]
+impl FromFloatLiteral for half::f16 {
+    fn from_bits(bits: u128) -> Self {
+        half::f16::from_bits(bits as u16)
+    }
+    fn ty() -> FloatType {
+        FloatType::F16
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// How the value of a float literal is rounded when converted to a specific
+/// float type. Returned by [`FloatLit::rounding`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FloatRounding {
+    /// The value is represented exactly, e.g. `0.5` or `1e10`.
+    Exact,
+
+    /// The value is rounded to the nearest representable value, which is not
+    /// zero nor infinity, e.g. `0.1`.
+    Inexact,
+
+    /// The value is too large and rounds to infinity, e.g. `1e400` for `f64`.
+    Infinity,
+
+    /// The value is not zero, but too small and rounds to zero, e.g.
+    /// `1e-400` for `f64`.
+    Zero,
+}
+
 /// All possible float type suffixes.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum FloatType {
+    F16,
     F32,
     F64,
+    F128,
 }
 
 impl FloatType {
@@ -224,8 +505,10 @@ impl FloatType {
     /// is returned.
     pub fn from_suffix(suffix: &str) -> Option<Self> {
         match suffix {
+            "f16" => Some(FloatType::F16),
             "f32" => Some(FloatType::F32),
             "f64" => Some(FloatType::F64),
+            "f128" => Some(FloatType::F128),
             _ => None,
         }
     }
@@ -233,8 +516,19 @@ impl FloatType {
     /// Returns the suffix for this type, e.g. `"f32"` for `Self::F32`.
     pub fn suffix(self) -> &'static str {
         match self {
+            Self::F16 => "f16",
             Self::F32 => "f32",
             Self::F64 => "f64",
+            Self::F128 => "f128",
+        }
+    }
+
+    fn format(self) -> Format {
+        match self {
+            Self::F16 => Format::F16,
+            Self::F32 => Format::F32,
+            Self::F64 => Format::F64,
+            Self::F128 => Format::F128,
         }
     }
 }
@@ -1,4 +1,4 @@
-use super::{FloatLit, FloatType};
+use super::{convert::{Decimal, Format}, FloatLit, FloatRounding, FloatType};
 use crate::{
     test_util::{assert_parse_ok_eq, assert_roundtrip},
     Literal, ParseError,
@@ -251,3 +251,289 @@ fn parse_err() {
     assert_err_single!(FloatLit::parse("8f649"), UnexpectedIntegerLit, None);
     assert_err_single!(FloatLit::parse("8f64f32"), UnexpectedIntegerLit, None);
 }
+
+#[test]
+fn rounding() {
+    let check = |input: &str, ty: FloatType, expected: FloatRounding| {
+        let actual = FloatLit::parse(input).unwrap().rounding(ty);
+        if actual != expected {
+            panic!("expected {expected:?} for `{input}` as {ty}, but got {actual:?}");
+        }
+    };
+
+    for ty in [FloatType::F32, FloatType::F64] {
+        check("0.0", ty, FloatRounding::Exact);
+        check("0e999999", ty, FloatRounding::Exact);
+        check("000.000e-99999999999999999999", ty, FloatRounding::Exact);
+        check("1.0", ty, FloatRounding::Exact);
+        check("0.5", ty, FloatRounding::Exact);
+        check("0.375", ty, FloatRounding::Exact);
+        check("1_024.25e3", ty, FloatRounding::Exact);
+        check("1e10", ty, FloatRounding::Exact);
+        check("16777216.0", ty, FloatRounding::Exact);
+        check("0.1", ty, FloatRounding::Inexact);
+        check("3.14", ty, FloatRounding::Inexact);
+        check("1e400", ty, FloatRounding::Infinity);
+        check("1e99999999999999999999999", ty, FloatRounding::Infinity);
+        check("1e-400", ty, FloatRounding::Zero);
+        check("1e-99999999999999999999999", ty, FloatRounding::Zero);
+    }
+
+    check("16777217.0", FloatType::F32, FloatRounding::Inexact);
+    check("16777217.0", FloatType::F64, FloatRounding::Exact);
+    check("1e39", FloatType::F32, FloatRounding::Infinity);
+    check("1e39", FloatType::F64, FloatRounding::Inexact);
+    check("1e-46", FloatType::F32, FloatRounding::Zero);
+    check("1e-46", FloatType::F64, FloatRounding::Inexact);
+
+    // Largest finite values and the halfway point to the next power of two.
+    check("340282346638528859811704183484516925440.0", FloatType::F32, FloatRounding::Exact);
+    check("340282356779733661637539395458142568447.0", FloatType::F32, FloatRounding::Inexact);
+    check("340282356779733661637539395458142568448.0", FloatType::F32, FloatRounding::Infinity);
+    check("1.7976931348623157e308", FloatType::F64, FloatRounding::Inexact);
+    check("1.7976931348623158e308", FloatType::F64, FloatRounding::Inexact);
+    check("1.7976931348623159e308", FloatType::F64, FloatRounding::Infinity);
+
+    // Smallest subnormal and half of it (which rounds to zero: ties to even).
+    check("1.401298464324817e-45", FloatType::F32, FloatRounding::Inexact);
+    check("0.7e-45", FloatType::F32, FloatRounding::Zero);
+    check("0.8e-45", FloatType::F32, FloatRounding::Inexact);
+    check("4.9406564584124654e-324", FloatType::F64, FloatRounding::Inexact);
+    check("2.4703282292062327e-324", FloatType::F64, FloatRounding::Zero);
+    check("2.4703282292062328e-324", FloatType::F64, FloatRounding::Inexact);
+
+    assert!(FloatLit::parse("0.25").unwrap().is_exactly_representable(FloatType::F32));
+    assert!(!FloatLit::parse("0.2").unwrap().is_exactly_representable(FloatType::F64));
+    assert!(!FloatLit::parse("1e-400").unwrap().is_exactly_representable(FloatType::F64));
+}
+
+/// Simple deterministic pseudo random number generator for the tests below.
+fn lcg(state: &mut u64) -> u64 {
+    *state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+    *state >> 33
+}
+
+/// Generates float literals with a wide range of digit counts and exponents.
+fn random_literals(count: usize) -> Vec<String> {
+    let mut state = 27;
+    (0..count).map(|_| {
+        let int_digits = 1 + lcg(&mut state) % 20;
+        let frac_digits = lcg(&mut state) % 25;
+        let mut s = String::new();
+        for _ in 0..int_digits {
+            s.push((b'0' + (lcg(&mut state) % 10) as u8) as char);
+        }
+        s.push('.');
+        for _ in 0..frac_digits {
+            s.push((b'0' + (lcg(&mut state) % 10) as u8) as char);
+        }
+        if frac_digits == 0 {
+            s.push('0');
+        }
+        match lcg(&mut state) % 3 {
+            0 => {}
+            1 => s.push_str(&format!("e{}", lcg(&mut state) % 340)),
+            _ => s.push_str(&format!("e-{}", lcg(&mut state) % 360)),
+        }
+        s
+    }).collect()
+}
+
+#[test]
+fn rounding_agrees_with_std() {
+    let mut inputs = random_literals(3000);
+    inputs.extend([
+        "0.1", "0.2", "0.3", "2.5", "9007199254740993.0", "9007199254740992.0",
+        "2.2250738585072011e-308", "2.2250738585072014e-308", "1.1754942e-38",
+        "1.17549435e-38", "7.038531e-26", "8.589973e9", "1e23", "5e-324",
+    ].iter().map(|s| s.to_string()));
+
+    for input in inputs {
+        let lit = FloatLit::parse(&*input).unwrap();
+        let decimal = Decimal::from_parts(
+            lit.integer_part(),
+            lit.fractional_part().unwrap_or(""),
+            lit.exponent_part(),
+        );
+
+        let expected = input.parse::<f64>().unwrap().to_bits() as u128;
+        let actual = decimal.round(Format::F64).to_bits(Format::F64);
+        assert_eq!(actual, expected, "f64 bits differ for `{input}`");
+
+        let expected = input.parse::<f32>().unwrap().to_bits() as u128;
+        let actual = decimal.round(Format::F32).to_bits(Format::F32);
+        assert_eq!(actual, expected, "f32 bits differ for `{input}`");
+    }
+}
+
+#[test]
+fn shortest_repr() {
+    let check = |input: &str, ty: FloatType, expected: Option<&str>| {
+        let actual = FloatLit::parse(input).unwrap().shortest_repr(ty);
+        assert_eq!(actual.as_deref(), expected, "input: `{input}` as {ty}");
+    };
+
+    check("0.0", FloatType::F64, Some("0.0"));
+    check("0_0.0_0e1_0", FloatType::F64, Some("0.0"));
+    check("1e-400", FloatType::F64, Some("0.0"));
+    check("1.0", FloatType::F64, Some("1.0"));
+    check("1.000", FloatType::F64, Some("1.0"));
+    check("1e3", FloatType::F64, Some("1000.0"));
+    check("1_000.0", FloatType::F64, Some("1000.0"));
+    check("0.1", FloatType::F64, Some("0.1"));
+    check("0.10000000000000000555", FloatType::F64, Some("0.1"));
+    check("0.1000000000000001", FloatType::F64, Some("0.1000000000000001"));
+    check("1e400", FloatType::F64, None);
+    check("1e39", FloatType::F32, None);
+    check("1e16", FloatType::F64, Some("1e16"));
+    check("1.5e16", FloatType::F64, Some("1.5e16"));
+    check("1e15", FloatType::F64, Some("1000000000000000.0"));
+    check("1e-4", FloatType::F64, Some("0.0001"));
+    check("1e-5", FloatType::F64, Some("1e-5"));
+    check("0.000012345", FloatType::F64, Some("1.2345e-5"));
+    check("5e-324", FloatType::F64, Some("5e-324"));
+    check("1e-330", FloatType::F64, Some("0.0"));
+    check("3e-324", FloatType::F64, Some("5e-324"));
+
+    check("3.14159265358979323846f32", FloatType::F32, Some("3.1415927f32"));
+    check("3.14159265358979323846f32", FloatType::F64, Some("3.141592653589793f32"));
+    check("0.1f32", FloatType::F32, Some("0.1f32"));
+    check("16777217.0f32", FloatType::F32, Some("16777216.0f32"));
+    check("1.00000001_f32", FloatType::F32, Some("1.0f32"));
+    check("1.4e-45f32", FloatType::F32, Some("1e-45f32"));
+    check("3.4028235e38", FloatType::F32, Some("3.4028235e38"));
+}
+
+#[test]
+fn shortest_repr_agrees_with_std() {
+    for input in random_literals(2000) {
+        let lit = FloatLit::parse(&*input).unwrap();
+
+        let v = input.parse::<f64>().unwrap();
+        if v.is_finite() {
+            let expected = format!("{v:?}");
+            assert_eq!(lit.shortest_repr(FloatType::F64).unwrap(), expected, "input: `{input}`");
+        }
+
+        let v = input.parse::<f32>().unwrap();
+        if v.is_finite() {
+            let expected = format!("{v:?}");
+            assert_eq!(lit.shortest_repr(FloatType::F32).unwrap(), expected, "input: `{input}`");
+        }
+    }
+}
+
+#[test]
+fn half_and_quad_types() {
+    assert_eq!(FloatType::from_suffix("f16"), Some(FloatType::F16));
+    assert_eq!(FloatType::from_suffix("f128"), Some(FloatType::F128));
+    assert_eq!(FloatType::F16.suffix(), "f16");
+    assert_eq!(FloatType::F128.to_string(), "f128");
+    assert_eq!("f128".parse::<FloatType>(), Ok(FloatType::F128));
+    assert_eq!(FloatType::from_suffix("f8"), None);
+
+    assert_eq!(FloatLit::parse("1.0f16").unwrap().suffix(), "f16");
+    assert_eq!(FloatLit::parse("2.5f128").unwrap().suffix(), "f128");
+}
+
+#[test]
+fn to_bits() {
+    let bits = |input: &str, ty: FloatType| FloatLit::parse(input).unwrap().to_bits(ty);
+
+    assert_eq!(bits("0.0", FloatType::F16), 0x0000);
+    assert_eq!(bits("1.0", FloatType::F16), 0x3c00);
+    assert_eq!(bits("0.1", FloatType::F16), 0x2e66);
+    assert_eq!(bits("3.140625", FloatType::F16), 0x4248);
+    assert_eq!(bits("65504.0", FloatType::F16), 0x7bff);
+    assert_eq!(bits("65519.99", FloatType::F16), 0x7bff);
+    assert_eq!(bits("65520.0", FloatType::F16), 0x7c00);
+    assert_eq!(bits("6.103515625e-5", FloatType::F16), 0x0400);
+    assert_eq!(bits("5.960464477539063e-8", FloatType::F16), 0x0001);
+    assert_eq!(bits("2.9e-8", FloatType::F16), 0x0000);
+    assert_eq!(bits("3.0e-8", FloatType::F16), 0x0001);
+
+    assert_eq!(bits("0.0", FloatType::F128), 0);
+    assert_eq!(bits("1.0", FloatType::F128), 0x3fff_0000_0000_0000_0000_0000_0000_0000);
+    assert_eq!(bits("2.5", FloatType::F128), 0x4000_4000_0000_0000_0000_0000_0000_0000);
+    assert_eq!(bits("0.1", FloatType::F128), 0x3ffb_9999_9999_9999_9999_9999_9999_999a);
+    assert_eq!(
+        bits("1.18973149535723176508575932662800702e4932", FloatType::F128),
+        0x7ffe_ffff_ffff_ffff_ffff_ffff_ffff_ffff,
+    );
+    assert_eq!(bits("1.2e4932", FloatType::F128), 0x7fff_0000_0000_0000_0000_0000_0000_0000);
+    assert_eq!(
+        bits("6.475175119438025110924438958227646552e-4966", FloatType::F128),
+        0x0000_0000_0000_0000_0000_0000_0000_0001,
+    );
+
+    assert_eq!(FloatLit::parse("0.1").unwrap().value::<f32>(), 0.1f32);
+    assert_eq!(FloatLit::parse("0.1").unwrap().value::<f64>(), 0.1f64);
+    assert_eq!(FloatLit::parse("1e-400").unwrap().value::<f64>(), 0.0);
+    assert_eq!(FloatLit::parse("1e400").unwrap().value::<f64>(), f64::INFINITY);
+}
+
+#[cfg(feature = "half")]
+#[test]
+fn half_value() {
+    assert_eq!(FloatLit::parse("1.0").unwrap().value::<half::f16>(), half::f16::ONE);
+    assert_eq!(FloatLit::parse("65504.0").unwrap().value::<half::f16>(), half::f16::MAX);
+    assert_eq!(FloatLit::parse("1e5").unwrap().value::<half::f16>(), half::f16::INFINITY);
+    assert_eq!(
+        FloatLit::parse("0.333").unwrap().value::<half::f16>(),
+        half::f16::from_bits(0x3554),
+    );
+}
+
+#[test]
+fn half_and_quad_analysis() {
+    let lit = FloatLit::parse("70000.0").unwrap();
+    assert_eq!(lit.rounding(FloatType::F16), FloatRounding::Infinity);
+    assert_eq!(lit.rounding(FloatType::F128), FloatRounding::Exact);
+    assert_eq!(lit.shortest_repr(FloatType::F16), None);
+
+    let lit = FloatLit::parse("1e-8").unwrap();
+    assert_eq!(lit.rounding(FloatType::F16), FloatRounding::Zero);
+
+    let lit = FloatLit::parse("1e4000").unwrap();
+    assert_eq!(lit.rounding(FloatType::F64), FloatRounding::Infinity);
+    assert_eq!(lit.rounding(FloatType::F128), FloatRounding::Inexact);
+    assert_eq!(lit.shortest_repr(FloatType::F128).unwrap(), "1e4000");
+
+    let lit = FloatLit::parse("0.1f16").unwrap();
+    assert_eq!(lit.shortest_repr(FloatType::F16).unwrap(), "0.1f16");
+    let lit = FloatLit::parse("3.14159f16").unwrap();
+    assert_eq!(lit.shortest_repr(FloatType::F16).unwrap(), "3.14f16");
+    let lit = FloatLit::parse("3.14159265358979323846264338327950288f128").unwrap();
+    assert_eq!(
+        lit.shortest_repr(FloatType::F128).unwrap(),
+        "3.1415926535897932384626433832795028f128",
+    );
+}
+
+#[test]
+fn editing() {
+    #[track_caller]
+    fn check_eq(actual: FloatLit<String>, expected: &str) {
+        assert_eq!(actual, FloatLit::parse(expected.to_owned()).unwrap());
+    }
+
+    let lit = FloatLit::parse("1_000.000_1e1_0_f64").unwrap();
+    check_eq(lit.with_suffix("f32").unwrap(), "1_000.000_1e1_0_f32");
+    check_eq(lit.with_suffix("").unwrap(), "1_000.000_1e1_0_");
+    check_eq(lit.strip_suffix(), "1_000.000_1e1_0_");
+    check_eq(lit.without_underscores(), "1000.0001e10f64");
+    assert!(lit.with_suffix("3").is_err());
+
+    let lit = FloatLit::parse("2.5").unwrap();
+    check_eq(lit.with_suffix("f16").unwrap(), "2.5f16");
+    assert!(lit.with_suffix("_x").is_err());
+    assert!(lit.with_suffix("e3").is_err());
+
+    let lit = FloatLit::parse("1_.").unwrap();
+    check_eq(lit.without_underscores(), "1.");
+    assert!(lit.with_suffix("f32").is_err());
+    check_eq(lit.with_suffix("").unwrap(), "1_.");
+
+    check_eq(FloatLit::parse("1_e_3").unwrap().without_underscores(), "1e3");
+    check_eq(FloatLit::parse("3e8f32").unwrap().strip_suffix(), "3e8");
+}
@@ -0,0 +1,288 @@
+//! Parsing `format!`-style templates in string literals.
+//!
+//! Macros like `format!` interpret their string literal as template:
+//! `{...}` is a placeholder for an argument and `{{` and `}}` are literal
+//! braces. [`parse`] splits the value of a [`StringLit`] into [`Piece`]s
+//! following the same rules. Each piece knows its byte range within the raw
+//! literal (e.g. including escapes and `r#`), so errors can point to the
+//! exact part of the literal.
+//!
+//! ```
+//! use litrs::{fmt::{self, Arg, Piece}, StringLit};
+//!
+//! let lit = StringLit::parse(r#""id\t{{{0}}}: {name:>8}""#).unwrap();
+//! let pieces = fmt::parse(&lit).unwrap();
+//! assert_eq!(pieces, [
+//!     Piece::Literal { text: "id\t{".into(), span: 1..7 },
+//!     Piece::Argument { arg: Arg::Index(0), format_spec: "".into(), span: 7..10 },
+//!     Piece::Literal { text: "}: ".into(), span: 10..14 },
+//!     Piece::Argument { arg: Arg::Name("name".into()), format_spec: ">8".into(), span: 14..23 },
+//! ]);
+//!
+//! let lit = StringLit::parse(r#""a {b""#).unwrap();
+//! let e = fmt::parse(&lit).unwrap_err();
+//! assert_eq!(e.to_string(), "invalid format string: expected `}` but string was terminated");
+//! assert_eq!(e.span(), 3..5);
+//! ```
+//!
+//! The format spec (after `:`) is returned as is and not validated.
+
+use std::{fmt, ops::Range};
+
+use crate::{
+    escape::{RawChunk, RawChunks},
+    Buffer, StringLit,
+};
+
+
+/// Parses the value of the string literal as `format!` template.
+///
+/// Returns the pieces in order. Consecutive text (including escaped braces)
+/// is returned as a single [`Piece::Literal`]. An empty literal results in no
+/// pieces at all.
+pub fn parse<B: Buffer>(lit: &StringLit<B>) -> Result<Vec<Piece>, FmtError> {
+    let units = units(lit);
+    let raw_span = |range: Range<usize>| units[range.start].start..units[range.end - 1].end;
+
+    let mut pieces = Vec::new();
+    let mut text = String::new();
+    let mut start_text = 0;
+    let mut i = 0;
+    while let Some(&Unit { c, .. }) = units.get(i) {
+        let next = units.get(i + 1).map(|u| u.c);
+        match (c, next) {
+            ('{', Some('{')) | ('}', Some('}')) => {
+                text.push(c);
+                i += 2;
+            }
+            ('}', _) => {
+                return Err(FmtError {
+                    kind: FmtErrorKind::UnmatchedClosingBrace,
+                    span: raw_span(i..i + 1),
+                });
+            }
+            ('{', _) => {
+                if !text.is_empty() {
+                    let text = std::mem::take(&mut text);
+                    pieces.push(Piece::Literal { text, span: raw_span(start_text..i) });
+                }
+
+                let (arg, format_spec, end) = parse_argument(&units, i)
+                    .map_err(|(kind, range)| FmtError { kind, span: raw_span(range) })?;
+                pieces.push(Piece::Argument { arg, format_spec, span: raw_span(i..end) });
+                i = end;
+                start_text = i;
+            }
+            _ => {
+                text.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    if !text.is_empty() {
+        pieces.push(Piece::Literal { text, span: raw_span(start_text..units.len()) });
+    }
+
+    Ok(pieces)
+}
+
+/// Parses the placeholder starting at `units[start]` (which is `{`). Returns
+/// the argument, the format spec and the index after the closing `}`. Errors
+/// are returned with the range of units to point to.
+fn parse_argument(
+    units: &[Unit],
+    start: usize,
+) -> Result<(Arg, String, usize), (FmtErrorKind, Range<usize>)> {
+    let char_at = |i: usize| units.get(i).map(|u| u.c);
+    let unclosed = |end: usize| (FmtErrorKind::UnclosedPlaceholder, start..end);
+
+    // Argument: index, name or nothing.
+    let start_arg = start + 1;
+    let mut i = start_arg;
+    let arg = match char_at(i) {
+        Some(c) if c.is_ascii_digit() => {
+            while char_at(i).map_or(false, |c| c.is_ascii_digit()) {
+                i += 1;
+            }
+            let digits = units[start_arg..i].iter().map(|u| u.c).collect::<String>();
+            let index = digits.parse()
+                .map_err(|_| (FmtErrorKind::InvalidArgument, start_arg..i))?;
+            Arg::Index(index)
+        }
+        Some(c) if c == '_' || c.is_alphabetic() => {
+            while char_at(i).map_or(false, |c| c == '_' || c.is_alphanumeric()) {
+                i += 1;
+            }
+            let name = units[start_arg..i].iter().map(|u| u.c).collect::<String>();
+            if name == "_" {
+                return Err((FmtErrorKind::InvalidArgument, start_arg..i));
+            }
+            Arg::Name(name)
+        }
+        _ => Arg::Next,
+    };
+
+    // Format spec: everything up to the closing brace. The fill character
+    // (followed by an alignment) can be anything, even `}`.
+    let mut format_spec = String::new();
+    match char_at(i) {
+        Some(':') => {
+            i += 1;
+            if matches!(char_at(i + 1), Some('<' | '^' | '>')) {
+                format_spec.push(units[i].c);
+                i += 1;
+            }
+            loop {
+                match char_at(i) {
+                    Some('}') => break,
+                    Some(c) => format_spec.push(c),
+                    None => return Err(unclosed(i)),
+                }
+                i += 1;
+            }
+        }
+        Some('}') => {}
+        Some(c) => return Err((FmtErrorKind::UnexpectedChar(c), i..i + 1)),
+        None => return Err(unclosed(i)),
+    }
+
+    Ok((arg, format_spec, i + 1))
+}
+
+/// A character of the value together with its range in the raw literal.
+struct Unit {
+    c: char,
+    start: usize,
+    end: usize,
+}
+
+/// Returns all characters of the value with their range in the raw literal.
+fn units<B: Buffer>(lit: &StringLit<B>) -> Vec<Unit> {
+    let inner = lit.inner_range();
+    let content = &lit.raw_input()[inner.clone()];
+    let mut chunks = RawChunks::new(content, lit.is_raw_string(), true, false);
+
+    let mut units = Vec::with_capacity(content.len());
+    while let Some((chunk, range)) = chunks.next_with_range() {
+        let start = inner.start + range.start;
+        match chunk {
+            RawChunk::Verbatim(s) => units.extend(s.char_indices().map(|(i, c)| Unit {
+                c,
+                start: start + i,
+                end: start + i + c.len_utf8(),
+            })),
+            RawChunk::Escape(v) => units.push(Unit {
+                c: v.unwrap_char(),
+                start,
+                end: inner.start + range.end,
+            }),
+        }
+    }
+    units
+}
+
+
+/// A piece of a `format!` template, returned by [`parse`]. All spans are byte
+/// ranges in the raw input of the string literal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Piece {
+    /// Text that is output as is, with `{{` and `}}` unescaped. The span
+    /// includes the escaped braces.
+    Literal {
+        text: String,
+        span: Range<usize>,
+    },
+
+    /// A placeholder like `{}`, `{0}` or `{name:>8}`. The span includes the
+    /// braces.
+    Argument {
+        arg: Arg,
+        /// The part after `:`, e.g. `>8` or `?`. Empty if there is none.
+        format_spec: String,
+        span: Range<usize>,
+    },
+}
+
+impl Piece {
+    /// Returns the byte range of this piece in the raw literal.
+    pub fn span(&self) -> Range<usize> {
+        match self {
+            Piece::Literal { span, .. } | Piece::Argument { span, .. } => span.clone(),
+        }
+    }
+}
+
+/// The argument a placeholder refers to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Arg {
+    /// No explicit argument (e.g. `{}` or `{:?}`): the next positional one.
+    Next,
+
+    /// An explicit positional argument, e.g. `{1}`.
+    Index(usize),
+
+    /// A named argument or captured variable, e.g. `{name}`.
+    Name(String),
+}
+
+
+/// An invalid `format!` template, returned by [`parse`]. Knows the byte range
+/// in the raw literal that caused the error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FmtError {
+    kind: FmtErrorKind,
+    span: Range<usize>,
+}
+
+/// The different kinds of [`FmtError`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FmtErrorKind {
+    /// A `{` without closing `}`, e.g. `"a {b"`. The span starts at the `{`
+    /// and covers the rest of the string.
+    UnclosedPlaceholder,
+
+    /// A `}` that does not close a placeholder and is not escaped as `}}`.
+    UnmatchedClosingBrace,
+
+    /// An unexpected character after the argument of a placeholder, e.g. the
+    /// `-` in `{a-b}`.
+    UnexpectedChar(char),
+
+    /// The argument is not valid: `_` or an index that does not fit `usize`.
+    InvalidArgument,
+}
+
+impl FmtError {
+    /// The kind of this error.
+    pub fn kind(&self) -> FmtErrorKind {
+        self.kind
+    }
+
+    /// The byte range in the raw literal that caused the error.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
+
+impl std::error::Error for FmtError {}
+
+impl fmt::Display for FmtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid format string: ")?;
+        match self.kind {
+            FmtErrorKind::UnclosedPlaceholder => {
+                f.write_str("expected `}` but string was terminated")
+            }
+            FmtErrorKind::UnmatchedClosingBrace => f.write_str("unmatched `}` found"),
+            FmtErrorKind::UnexpectedChar(c) => write!(f, "expected `}}`, found `{}`", c),
+            FmtErrorKind::InvalidArgument => f.write_str("invalid argument"),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests;
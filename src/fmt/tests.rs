@@ -0,0 +1,122 @@
+use super::{parse, Arg, FmtErrorKind, Piece};
+use crate::StringLit;
+
+
+// ===== Utility functions =======================================================================
+
+#[track_caller]
+fn pieces(input: &str) -> Vec<Piece> {
+    let lit = StringLit::parse(input).unwrap();
+    parse(&lit).unwrap_or_else(|e| panic!("failed to parse `{input}`: {e}"))
+}
+
+#[track_caller]
+fn error(input: &str) -> (FmtErrorKind, std::ops::Range<usize>) {
+    let lit = StringLit::parse(input).unwrap();
+    let e = parse(&lit).expect_err("expected error");
+    (e.kind(), e.span())
+}
+
+fn lit(text: &str, span: std::ops::Range<usize>) -> Piece {
+    Piece::Literal { text: text.into(), span }
+}
+
+fn arg(arg: Arg, format_spec: &str, span: std::ops::Range<usize>) -> Piece {
+    Piece::Argument { arg, format_spec: format_spec.into(), span }
+}
+
+fn name(name: &str) -> Arg {
+    Arg::Name(name.into())
+}
+
+
+// ===== Actual tests ============================================================================
+
+#[test]
+fn text_only() {
+    assert_eq!(pieces(r#""""#), []);
+    assert_eq!(pieces(r#""abc""#), [lit("abc", 1..4)]);
+    assert_eq!(pieces(r#""{{""#), [lit("{", 1..3)]);
+    assert_eq!(pieces(r#""a}}b{{""#), [lit("a}b{", 1..7)]);
+    assert_eq!(pieces(r#""ä\n""#), [lit("ä\n", 1..5)]);
+    assert_eq!(pieces(r#""x"suffix"#), [lit("x", 1..2)]);
+}
+
+#[test]
+fn arguments() {
+    assert_eq!(pieces(r#""{}""#), [arg(Arg::Next, "", 1..3)]);
+    assert_eq!(pieces(r#""{0}{12}""#), [
+        arg(Arg::Index(0), "", 1..4),
+        arg(Arg::Index(12), "", 4..8),
+    ]);
+    assert_eq!(pieces(r#""{a}{_b}{löwe}""#), [
+        arg(name("a"), "", 1..4),
+        arg(name("_b"), "", 4..8),
+        arg(name("löwe"), "", 8..15),
+    ]);
+    assert_eq!(pieces(r#""x={x:?} y""#), [
+        lit("x=", 1..3),
+        arg(name("x"), "?", 3..8),
+        lit(" y", 8..10),
+    ]);
+}
+
+#[test]
+fn format_specs() {
+    assert_eq!(pieces(r#""{:}""#), [arg(Arg::Next, "", 1..4)]);
+    assert_eq!(pieces(r#""{0:>8.3}""#), [arg(Arg::Index(0), ">8.3", 1..9)]);
+    assert_eq!(pieces(r#""{:#010x}""#), [arg(Arg::Next, "#010x", 1..9)]);
+    assert_eq!(pieces(r#""{:1$.prec$}""#), [arg(Arg::Next, "1$.prec$", 1..12)]);
+    assert_eq!(pieces(r#""{:{<5}""#), [arg(Arg::Next, "{<5", 1..7)]);
+    assert_eq!(pieces(r#""{:}^5}""#), [arg(Arg::Next, "}^5", 1..7)]);
+    assert_eq!(pieces(r#""{:*>}""#), [arg(Arg::Next, "*>", 1..6)]);
+}
+
+#[test]
+fn spans_with_escapes() {
+    // Spans are in the raw literal, so escapes are longer than the value.
+    assert_eq!(pieces(r#""\x41{}\u{1F980}""#), [
+        lit("A", 1..5),
+        arg(Arg::Next, "", 5..7),
+        lit("🦀", 7..16),
+    ]);
+    assert_eq!(pieces(r#""\u{7B}\u{7B}{a\u{7D}""#), [
+        lit("{", 1..13),
+        arg(name("a"), "", 13..21),
+    ]);
+    assert_eq!(pieces("\"a\\\n    {}\""), [lit("a", 1..2), arg(Arg::Next, "", 8..10)]);
+    assert_eq!(pieces("\"{}\\\n    \""), [arg(Arg::Next, "", 1..3)]);
+
+    // Raw strings
+    assert_eq!(pieces(r#"r"\{}""#), [lit("\\", 2..3), arg(Arg::Next, "", 3..5)]);
+    assert_eq!(pieces(r###"r##"{x}"#"##"###), [arg(name("x"), "", 4..7), lit("\"#", 7..9)]);
+}
+
+#[test]
+fn errors() {
+    use FmtErrorKind::*;
+
+    assert_eq!(error(r#""{""#), (UnclosedPlaceholder, 1..2));
+    assert_eq!(error(r#""a {b""#), (UnclosedPlaceholder, 3..5));
+    assert_eq!(error(r#""a {b:>5""#), (UnclosedPlaceholder, 3..8));
+    assert_eq!(error(r##"r#"a {b"#"##), (UnclosedPlaceholder, 5..7));
+    assert_eq!(error(r#""a {:\n""#), (UnclosedPlaceholder, 3..7));
+    assert_eq!(error(r#""a {b\n""#), (UnexpectedChar('\n'), 5..7));
+    assert_eq!(error(r#""}""#), (UnmatchedClosingBrace, 1..2));
+    assert_eq!(error(r#""{}}""#), (UnmatchedClosingBrace, 3..4));
+    assert_eq!(error(r#""a\u{7D}b""#), (UnmatchedClosingBrace, 2..8));
+    assert_eq!(error(r#""{a-b}""#), (UnexpectedChar('-'), 3..4));
+    assert_eq!(error(r#""{ }""#), (UnexpectedChar(' '), 2..3));
+    assert_eq!(error(r#""{0a}""#), (UnexpectedChar('a'), 3..4));
+    assert_eq!(error(r#""{_}""#), (InvalidArgument, 2..3));
+    assert_eq!(error(r#""{99999999999999999999999}""#), (InvalidArgument, 2..25));
+}
+
+#[test]
+fn error_display() {
+    let msg = |input: &str| parse(&StringLit::parse(input).unwrap()).unwrap_err().to_string();
+    assert_eq!(msg(r#""{""#), "invalid format string: expected `}` but string was terminated");
+    assert_eq!(msg(r#""}""#), "invalid format string: unmatched `}` found");
+    assert_eq!(msg(r#""{a b}""#), "invalid format string: expected `}`, found ` `");
+    assert_eq!(msg(r#""{_}""#), "invalid format string: invalid argument");
+}
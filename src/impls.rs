@@ -29,6 +29,65 @@ macro_rules! helper_no_refs {
 }
 
 
+/// Helper methods for `proc_macro::TokenTree` and `proc_macro2::TokenTree`.
+pub(crate) trait TokenTreeExt: Sized {
+    /// Returns the kind of this token. Literals are parsed to determine their
+    /// kind, `true` and `false` are reported as bool literals.
+    fn kind(&self) -> TokenKind;
+
+    /// If this is an invisible group (with `Delimiter::None`), returns the
+    /// tokens inside it. `macro_rules!` macros produce these groups when
+    /// forwarding fragments like `$x:literal` or `$x:expr`.
+    fn invisible_group_tokens(&self) -> Option<Vec<Self>>;
+
+    /// If this is an invisible group containing exactly one token, returns
+    /// that token.
+    fn single_token_in_invisible_group(&self) -> Option<Self> {
+        let mut tokens = self.invisible_group_tokens()?;
+        if tokens.len() == 1 {
+            tokens.pop()
+        } else {
+            None
+        }
+    }
+}
+
+macro_rules! impl_token_tree_ext {
+    ([$($prefix:tt)*] => ) => {
+        impl TokenTreeExt for $($prefix)* TokenTree {
+            fn kind(&self) -> TokenKind {
+                match self {
+                    $($prefix)* TokenTree::Group(_) => TokenKind::Group,
+                    $($prefix)* TokenTree::Punct(_) => TokenKind::Punct,
+                    $($prefix)* TokenTree::Ident(ident) => match &*ident.to_string() {
+                        "true" | "false" => TokenKind::BoolLit,
+                        _ => TokenKind::Ident,
+                    },
+                    $($prefix)* TokenTree::Literal(lit) => kind_of(&Literal::from(lit)),
+                }
+            }
+
+            fn invisible_group_tokens(&self) -> Option<Vec<Self>> {
+                match self {
+                    $($prefix)* TokenTree::Group(g)
+                        if g.delimiter() == $($prefix)* Delimiter::None
+                        => Some(g.stream().into_iter().collect()),
+                    _ => None,
+                }
+            }
+        }
+
+        impl From<&$($prefix)* TokenTree> for TokenKind {
+            fn from(src: &$($prefix)* TokenTree) -> Self {
+                src.kind()
+            }
+        }
+    };
+}
+
+helper_no_refs!(impl_token_tree_ext,);
+
+
 // ==============================================================================================
 // ===== `From<*Lit> for Literal`
 // ==============================================================================================
@@ -85,6 +144,10 @@ macro_rules! impl_tt_to_lit {
         impl TryFrom<$($prefix)* TokenTree> for Literal<String> {
             type Error = InvalidToken;
             fn try_from(tt: $($prefix)* TokenTree) -> Result<Self, Self::Error> {
+                if let Some(inner) = tt.single_token_in_invisible_group() {
+                    return Self::try_from(inner);
+                }
+
                 let span = tt.span();
                 let res = match tt {
                     $($prefix)* TokenTree::Group(_) => Err(TokenKind::Group),
@@ -101,7 +164,7 @@ macro_rules! impl_tt_to_lit {
                     Ok(lit) => Ok(From::from(lit)),
                     Err(actual) => Err(InvalidToken {
                         actual,
-                        expected: TokenKind::Literal,
+                        expected: TokenKind::Literal.into(),
                         span: span.into(),
                     }),
                 }
@@ -117,7 +180,7 @@ helper!(impl_tt_to_lit,);
 // ===== `TryFrom<pm::Literal>`, `TryFrom<pm::TokenTree>` for non-bool `*Lit`
 // ==============================================================================================
 
-fn kind_of(lit: &Literal<String>) -> TokenKind {
+pub(crate) fn kind_of(lit: &Literal<String>) -> TokenKind {
     match lit {
         Literal::String(_) => TokenKind::StringLit,
         Literal::Bool(_) => TokenKind::BoolLit,
@@ -140,7 +203,7 @@ macro_rules! impl_for_specific_lit {
                 match lit {
                     Literal::$variant(s) => Ok(s),
                     other => Err(InvalidToken {
-                        expected: TokenKind::$kind,
+                        expected: TokenKind::$kind.into(),
                         actual: kind_of(&other),
                         span: span.into(),
                     }),
@@ -151,6 +214,10 @@ macro_rules! impl_for_specific_lit {
         impl TryFrom<$($prefix)* TokenTree> for $ty {
             type Error = InvalidToken;
             fn try_from(tt: $($prefix)* TokenTree) -> Result<Self, Self::Error> {
+                if let Some(inner) = tt.single_token_in_invisible_group() {
+                    return Self::try_from(inner);
+                }
+
                 let span = tt.span();
                 let res = match tt {
                     $($prefix)* TokenTree::Group(_) => Err(TokenKind::Group),
@@ -163,7 +230,7 @@ macro_rules! impl_for_specific_lit {
                     Ok(lit) => <$ty>::try_from(lit),
                     Err(actual) => Err(InvalidToken {
                         actual,
-                        expected: TokenKind::$kind,
+                        expected: TokenKind::$kind.into(),
                         span: span.into(),
                     }),
                 }
@@ -223,6 +290,10 @@ macro_rules! impl_from_tt_for_bool {
         impl TryFrom<$($prefix)* TokenTree> for crate::BoolLit {
             type Error = InvalidToken;
             fn try_from(tt: $($prefix)* TokenTree) -> Result<Self, Self::Error> {
+                if let Some(inner) = tt.single_token_in_invisible_group() {
+                    return Self::try_from(inner);
+                }
+
                 let span = tt.span();
                 let actual = match tt {
                     $($prefix)* TokenTree::Ident(ref ident) if ident.to_string() == "true"
@@ -238,7 +309,7 @@ macro_rules! impl_from_tt_for_bool {
 
                 Err(InvalidToken {
                     actual,
-                    expected: TokenKind::BoolLit,
+                    expected: TokenKind::BoolLit.into(),
                     span: span.into(),
                 })
             }
@@ -265,6 +336,217 @@ macro_rules! impl_bool_lit_to_pm_lit {
 helper_no_refs!(impl_bool_lit_to_pm_lit,);
 
 
+// ==============================================================================================
+// ===== `TryFrom<pm::TokenStream>` for `Literal`, `BoolLit` and `*Lit`
+// ==============================================================================================
+
+macro_rules! impl_from_token_stream {
+    ([$($prefix:tt)*] => $ty:ty, $kind:ident) => {
+        impl TryFrom<$($prefix)* TokenStream> for $ty {
+            type Error = InvalidToken;
+            fn try_from(stream: $($prefix)* TokenStream) -> Result<Self, Self::Error> {
+                let mut tokens = stream.into_iter();
+                match (tokens.next(), tokens.next()) {
+                    (Some(tt), None) => Self::try_from(tt),
+                    (None, _) => Err(InvalidToken {
+                        expected: TokenKind::$kind.into(),
+                        actual: TokenKind::End,
+                        span: $($prefix)* Span::call_site().into(),
+                    }),
+                    (Some(_), Some(extra)) => Err(InvalidToken {
+                        expected: TokenKind::End.into(),
+                        actual: extra.kind(),
+                        span: extra.span().into(),
+                    }),
+                }
+            }
+        }
+    };
+}
+
+helper_no_refs!(impl_from_token_stream, Literal<String>, Literal);
+helper_no_refs!(impl_from_token_stream, crate::BoolLit, BoolLit);
+helper_no_refs!(impl_from_token_stream, crate::IntegerLit<String>, IntegerLit);
+helper_no_refs!(impl_from_token_stream, crate::FloatLit<String>, FloatLit);
+helper_no_refs!(impl_from_token_stream, crate::CharLit<String>, CharLit);
+helper_no_refs!(impl_from_token_stream, crate::StringLit<String>, StringLit);
+helper_no_refs!(impl_from_token_stream, crate::ByteLit<String>, ByteLit);
+helper_no_refs!(impl_from_token_stream, crate::ByteStringLit<String>, ByteStringLit);
+helper_no_refs!(impl_from_token_stream, crate::CStringLit<String>, CStringLit);
+
+
+// ==============================================================================================
+// ===== `TryFrom<pm::*>` for `Spanned<_>`
+// ==============================================================================================
+
+macro_rules! impl_spanned_from_tt {
+    ([$($prefix:tt)*] => $ty:ty) => {
+        impl TryFrom<$($prefix)* TokenTree> for crate::Spanned<$ty> {
+            type Error = InvalidToken;
+            fn try_from(tt: $($prefix)* TokenTree) -> Result<Self, Self::Error> {
+                let span = tt.span();
+                <$ty>::try_from(tt).map(|lit| crate::Spanned::with_span(lit, span))
+            }
+        }
+    };
+}
+
+macro_rules! impl_spanned_from_lit {
+    ([$($prefix:tt)*] => $ty:ty) => {
+        impl TryFrom<$($prefix)* Literal> for crate::Spanned<$ty> {
+            type Error = InvalidToken;
+            fn try_from(lit: $($prefix)* Literal) -> Result<Self, Self::Error> {
+                let span = lit.span();
+                <$ty>::try_from(lit).map(|lit| crate::Spanned::with_span(lit, span))
+            }
+        }
+    };
+}
+
+macro_rules! impl_spanned_from_stream {
+    ([$($prefix:tt)*] => $ty:ty) => {
+        impl TryFrom<$($prefix)* TokenStream> for crate::Spanned<$ty> {
+            type Error = InvalidToken;
+            fn try_from(stream: $($prefix)* TokenStream) -> Result<Self, Self::Error> {
+                let mut tokens = stream.into_iter();
+                match (tokens.next(), tokens.next()) {
+                    (Some(tt), None) => Self::try_from(tt),
+
+                    // Let the non-spanned impl create the error.
+                    (first, second) => {
+                        let stream: $($prefix)* TokenStream
+                            = first.into_iter().chain(second).chain(tokens).collect();
+                        Err(<$ty>::try_from(stream).unwrap_err())
+                    }
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_spanned {
+    ($ty:ty) => {
+        helper!(impl_spanned_from_tt, $ty);
+        helper_no_refs!(impl_spanned_from_stream, $ty);
+    };
+    ($ty:ty, with_literal) => {
+        impl_spanned!($ty);
+        helper!(impl_spanned_from_lit, $ty);
+    };
+}
+
+impl_spanned!(Literal<String>);
+impl_spanned!(crate::BoolLit);
+impl_spanned!(crate::IntegerLit<String>, with_literal);
+impl_spanned!(crate::FloatLit<String>, with_literal);
+impl_spanned!(crate::CharLit<String>, with_literal);
+impl_spanned!(crate::StringLit<String>, with_literal);
+impl_spanned!(crate::ByteLit<String>, with_literal);
+impl_spanned!(crate::ByteStringLit<String>, with_literal);
+impl_spanned!(crate::CStringLit<String>, with_literal);
+
+macro_rules! impl_spanned_lit_from_pm_lit {
+    ([$($prefix:tt)*] => ) => {
+        impl From<$($prefix)* Literal> for crate::Spanned<Literal<String>> {
+            fn from(lit: $($prefix)* Literal) -> Self {
+                let span = lit.span();
+                crate::Spanned::with_span(Literal::from(lit), span)
+            }
+        }
+    };
+}
+
+helper!(impl_spanned_lit_from_pm_lit,);
+
+
+// ==============================================================================================
+// ===== `LiteralToken` for `pm::TokenTree`, `pm::Literal` and `pm::TokenStream`
+// ==============================================================================================
+
+macro_rules! impl_literal_token {
+    ([$($prefix:tt)*] => ) => {
+        impl crate::extract::sealed::Sealed for $($prefix)* TokenTree {}
+        impl crate::LiteralToken for $($prefix)* TokenTree {
+            fn extract_value<T: crate::FromLiteral>(self) -> Result<T, crate::ExtractError> {
+                let span = self.span();
+
+                // An invisible group can contain a negated literal: `$x:literal`
+                // and `$x:expr` fragments can be `-3`.
+                if let Some(tokens) = self.invisible_group_tokens() {
+                    match &*tokens {
+                        [$($prefix)* TokenTree::Punct(p), lit] if p.as_char() == '-' => {
+                            let lit = Literal::try_from(lit.clone())?;
+                            return T::from_negated_literal(lit)
+                                .map_err(|e| e.with_span(span.into()));
+                        }
+                        [single] => return single.clone().extract_value(),
+                        _ => {}
+                    }
+                }
+
+                let lit = Literal::try_from(self)?;
+                T::from_literal(lit).map_err(|e| e.with_span(span.into()))
+            }
+        }
+
+        impl crate::extract::sealed::Sealed for &$($prefix)* TokenTree {}
+        impl crate::LiteralToken for &$($prefix)* TokenTree {
+            fn extract_value<T: crate::FromLiteral>(self) -> Result<T, crate::ExtractError> {
+                self.clone().extract_value()
+            }
+        }
+
+        impl crate::extract::sealed::Sealed for $($prefix)* Literal {}
+        impl crate::LiteralToken for $($prefix)* Literal {
+            fn extract_value<T: crate::FromLiteral>(self) -> Result<T, crate::ExtractError> {
+                (&self).extract_value()
+            }
+        }
+
+        impl crate::extract::sealed::Sealed for &$($prefix)* Literal {}
+        impl crate::LiteralToken for &$($prefix)* Literal {
+            fn extract_value<T: crate::FromLiteral>(self) -> Result<T, crate::ExtractError> {
+                let span = self.span();
+
+                // Literals created via e.g. `Literal::i32_unsuffixed(-3)` can
+                // start with a minus sign.
+                let s = self.to_string();
+                let res = match s.strip_prefix('-') {
+                    Some(rest) => {
+                        let lit = Literal::parse(rest.to_owned())
+                            .expect("bug: failed to parse output of `Literal::to_string`");
+                        T::from_negated_literal(lit)
+                    }
+                    None => T::from_literal(Literal::from(self)),
+                };
+                res.map_err(|e| e.with_span(span.into()))
+            }
+        }
+
+        impl crate::extract::sealed::Sealed for $($prefix)* TokenStream {}
+        impl crate::LiteralToken for $($prefix)* TokenStream {
+            fn extract_value<T: crate::FromLiteral>(self) -> Result<T, crate::ExtractError> {
+                let mut tokens = self.into_iter().collect::<Vec<_>>();
+                match &*tokens {
+                    [$($prefix)* TokenTree::Punct(p), lit] if p.as_char() == '-' => {
+                        let span = p.span();
+                        let lit = Literal::try_from(lit.clone())?;
+                        T::from_negated_literal(lit).map_err(|e| e.with_span(span.into()))
+                    }
+                    [_] => tokens.remove(0).extract_value(),
+                    _ => {
+                        let stream = tokens.into_iter().collect::<$($prefix)* TokenStream>();
+                        Err(Literal::try_from(stream).unwrap_err().into())
+                    }
+                }
+            }
+        }
+    };
+}
+
+helper_no_refs!(impl_literal_token,);
+
+
 mod tests {
     //! # Tests
     //!
@@ -349,6 +631,22 @@ mod tests {
     //!
     //! let _ = litrs::CStringLit::try_from(give::<proc_macro::TokenTree>());
     //! let _ = litrs::CStringLit::try_from(give::<&proc_macro::TokenTree>());
+    //!
+    //!
+    //! use litrs::Spanned;
+    //!
+    //! let _ = Spanned::<litrs::Literal<String>>::from(give::<proc_macro::Literal>());
+    //! let _ = Spanned::<litrs::Literal<String>>::from(give::<&proc_macro::Literal>());
+    //! let _ = Spanned::<litrs::Literal<String>>::try_from(give::<proc_macro::TokenTree>());
+    //! let _ = Spanned::<litrs::Literal<String>>::try_from(give::<&proc_macro::TokenTree>());
+    //! let _ = Spanned::<litrs::Literal<String>>::try_from(give::<proc_macro::TokenStream>());
+    //! let _ = Spanned::<litrs::BoolLit>::try_from(give::<proc_macro::TokenTree>());
+    //! let _ = Spanned::<litrs::BoolLit>::try_from(give::<proc_macro::TokenStream>());
+    //! let _ = Spanned::<litrs::StringLit<String>>::try_from(give::<proc_macro::Literal>());
+    //! let _ = Spanned::<litrs::StringLit<String>>::try_from(give::<&proc_macro::Literal>());
+    //! let _ = Spanned::<litrs::StringLit<String>>::try_from(give::<proc_macro::TokenTree>());
+    //! let _ = Spanned::<litrs::StringLit<String>>::try_from(give::<&proc_macro::TokenTree>());
+    //! let _ = Spanned::<litrs::StringLit<String>>::try_from(give::<proc_macro::TokenStream>());
     //! ```
 }
 
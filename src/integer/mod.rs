@@ -115,6 +115,60 @@ impl<B: Buffer> IntegerLit<B> {
     pub fn into_raw_input(self) -> B {
         self.raw
     }
+
+    /// Returns a new literal with the given suffix, keeping the base prefix
+    /// and the main part as is. Pass `""` to remove the suffix (or use
+    /// [`strip_suffix`][Self::strip_suffix]). Returns an error if the suffix
+    /// is invalid or would change the main part, e.g. `f32` for `0x1`.
+    ///
+    /// ```
+    /// use litrs::IntegerLit;
+    ///
+    /// let lit = IntegerLit::parse("0xFF_FFu32").unwrap();
+    /// assert_eq!(lit.with_suffix("u64").unwrap().raw_input(), "0xFF_FFu64");
+    /// assert!(lit.with_suffix("f32").is_err());
+    /// ```
+    pub fn with_suffix(&self, suffix: &str) -> Result<IntegerLit<String>, ParseError> {
+        let raw = format!("{}{}", &(*self.raw)[..self.end_main_part], suffix);
+        let lit = IntegerLit::parse(raw)?;
+        if lit.end_main_part != self.end_main_part {
+            return Err(perr(self.end_main_part..lit.raw.len(), InvalidSuffix));
+        }
+        Ok(lit)
+    }
+
+    /// Returns a copy of this literal without suffix.
+    pub fn strip_suffix(&self) -> IntegerLit<String> {
+        IntegerLit {
+            raw: (*self.raw)[..self.end_main_part].to_owned(),
+            start_main_part: self.start_main_part,
+            end_main_part: self.end_main_part,
+            base: self.base,
+        }
+    }
+
+    /// Returns a copy of this literal with all `_` removed from the main
+    /// part. The base prefix and suffix are kept.
+    ///
+    /// ```
+    /// use litrs::IntegerLit;
+    ///
+    /// let lit = IntegerLit::parse("0b1111_0000_u8").unwrap();
+    /// assert_eq!(lit.without_underscores().raw_input(), "0b11110000u8");
+    /// ```
+    pub fn without_underscores(&self) -> IntegerLit<String> {
+        let mut raw = (*self.raw)[..self.start_main_part].to_owned();
+        raw.extend(self.raw_main_part().chars().filter(|&c| c != '_'));
+        let end_main_part = raw.len();
+        raw.push_str(self.suffix());
+
+        IntegerLit {
+            raw,
+            start_main_part: self.start_main_part,
+            end_main_part,
+            base: self.base,
+        }
+    }
 }
 
 impl IntegerLit<&str> {
@@ -354,3 +354,30 @@ fn non_standard_suffixes() {
 
     check_suffix("123_foo", 123, Decimal, "123_", "foo");
 }
+
+#[test]
+fn editing() {
+    #[track_caller]
+    fn check_eq(actual: IntegerLit<String>, expected: &str) {
+        assert_eq!(actual, IntegerLit::parse(expected.to_owned()).unwrap());
+    }
+
+    let lit = IntegerLit::parse("0xFF_FFu32").unwrap();
+    check_eq(lit.with_suffix("u64").unwrap(), "0xFF_FFu64");
+    check_eq(lit.with_suffix("").unwrap(), "0xFF_FF");
+    check_eq(lit.strip_suffix(), "0xFF_FF");
+    check_eq(lit.without_underscores(), "0xFFFFu32");
+    assert!(lit.with_suffix("f32").is_err());
+    assert!(lit.with_suffix("1x").is_err());
+
+    let lit = IntegerLit::parse("1_000_").unwrap();
+    check_eq(lit.with_suffix("i16").unwrap(), "1_000_i16");
+    assert!(lit.with_suffix("_u8").is_err());
+    check_eq(lit.without_underscores(), "1000");
+    assert!(lit.with_suffix("e3").is_err());
+    assert!(lit.with_suffix(".5").is_err());
+
+    check_eq(IntegerLit::parse("0o7_7").unwrap().without_underscores(), "0o77");
+    check_eq(IntegerLit::parse("0b1_u8").unwrap().strip_suffix(), "0b1_");
+    check_eq(IntegerLit::parse("7").unwrap().with_suffix("usize").unwrap(), "7usize");
+}
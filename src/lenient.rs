@@ -0,0 +1,267 @@
+use std::{fmt, ops::Range};
+
+use crate::{err::ParseErrorKind, Applicability, IntegerBase, Literal, ParseError};
+
+
+/// The result of [`Literal::parse_lenient`]: the repaired literal and a
+/// warning for each repair that was necessary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LenientLiteral {
+    literal: Literal<String>,
+    warnings: Vec<LenientWarning>,
+}
+
+impl LenientLiteral {
+    /// The repaired literal.
+    pub fn literal(&self) -> &Literal<String> {
+        &self.literal
+    }
+
+    /// Returns the repaired literal.
+    pub fn into_literal(self) -> Literal<String> {
+        self.literal
+    }
+
+    /// The corrected raw text, i.e. the input with all repairs applied. This is
+    /// a valid Rust literal.
+    pub fn raw(&self) -> &str {
+        self.literal.raw_input()
+    }
+
+    /// All repairs that were applied to the input, in the order they were
+    /// applied. Empty if the input was a valid literal.
+    pub fn warnings(&self) -> &[LenientWarning] {
+        &self.warnings
+    }
+}
+
+/// A repair done by [`Literal::parse_lenient`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LenientWarning {
+    kind: RepairKind,
+    original: String,
+    replacement: String,
+}
+
+/// The kind of mistake that was repaired by [`Literal::parse_lenient`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum RepairKind {
+    /// An escape that exists in C, but not in Rust, e.g. `\a`, `\e`, `\u00E9`
+    /// or `\U0001F980`. Replaced by the equivalent Rust escape.
+    CEscape,
+
+    /// A decimal integer with leading zero, e.g. `0755`, which is an octal
+    /// number in C. Replaced by `0o755`.
+    LeadingZeroOctal,
+
+    /// An uppercase base prefix like `0X1F`, which Rust parses as `0` with
+    /// suffix `X1F`. Replaced by `0x1F`.
+    UppercasePrefix,
+
+    /// A missing closing quote (or `"#` for raw strings) at the end of input.
+    MissingClosingQuote,
+
+    /// A string in single quotes, e.g. `'hello'`. Replaced by `"hello"`.
+    SingleQuotedString,
+}
+
+impl LenientWarning {
+    fn new(kind: RepairKind, original: &str, replacement: &str) -> Self {
+        Self {
+            kind,
+            original: original.to_owned(),
+            replacement: replacement.to_owned(),
+        }
+    }
+
+    /// What kind of mistake was repaired.
+    pub fn kind(&self) -> RepairKind {
+        self.kind
+    }
+
+    /// The part of the input that was replaced. Empty for insertions (e.g. a
+    /// missing closing quote).
+    pub fn original(&self) -> &str {
+        &self.original
+    }
+
+    /// The text it was replaced with.
+    pub fn replacement(&self) -> &str {
+        &self.replacement
+    }
+}
+
+impl fmt::Display for LenientWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let desc = match self.kind {
+            RepairKind::CEscape => "C-style escape",
+            RepairKind::LeadingZeroOctal => "integer with leading zero is treated as octal",
+            RepairKind::UppercasePrefix => "uppercase base prefix",
+            RepairKind::MissingClosingQuote => "missing closing quote",
+            RepairKind::SingleQuotedString => "string in single quotes",
+        };
+
+        if self.original.is_empty() {
+            write!(f, "{}: inserted `{}`", desc, self.replacement)
+        } else {
+            write!(f, "{}: replaced `{}` with `{}`", desc, self.original, self.replacement)
+        }
+    }
+}
+
+impl Literal<String> {
+    /// Parses the input like [`Literal::parse`], but repairs common mistakes
+    /// made by people not familiar with Rust. For each repair, a warning is
+    /// returned. The following mistakes are repaired (see [`RepairKind`]):
+    ///
+    /// - C-style escapes like `\a`, `\e`, `\u00E9` or `\U0001F980`
+    /// - Integers with leading zero like `0755`, which are octal numbers in C
+    /// - Uppercase base prefixes like `0X1F`
+    /// - A missing closing quote at the end of input
+    /// - Strings in single quotes like `'hello'`
+    ///
+    /// If the input cannot be repaired, the error of parsing the original
+    /// input is returned.
+    ///
+    /// ```
+    /// use litrs::{Literal, RepairKind};
+    ///
+    /// let lit = Literal::parse_lenient(r"'hello\a").unwrap();
+    /// assert_eq!(lit.raw(), r#""hello\x07""#);
+    ///
+    /// let kinds = lit.warnings().iter().map(|w| w.kind()).collect::<Vec<_>>();
+    /// assert_eq!(kinds, [
+    ///     RepairKind::MissingClosingQuote,
+    ///     RepairKind::SingleQuotedString,
+    ///     RepairKind::CEscape,
+    /// ]);
+    ///
+    /// let lit = Literal::parse_lenient("0755").unwrap();
+    /// assert_eq!(lit.raw(), "0o755");
+    /// assert_eq!(
+    ///     lit.warnings()[0].to_string(),
+    ///     "integer with leading zero is treated as octal: replaced `0755` with `0o755`",
+    /// );
+    /// ```
+    pub fn parse_lenient(input: &str) -> Result<LenientLiteral, ParseError> {
+        let mut raw = input.to_owned();
+        let mut warnings = Vec::new();
+        let mut original_error = None;
+
+        // Every repair fixes one error, so this terminates. The limit is just
+        // a safety net.
+        for _ in 0..input.len() + 2 {
+            let e = match Literal::parse(raw.clone()) {
+                Ok(lit) => {
+                    let literal = repair_integer(lit, &mut warnings);
+                    return Ok(LenientLiteral { literal, warnings });
+                }
+                Err(e) => e,
+            };
+
+            let (kind, range, replacement) = match repair_error(&e, &raw) {
+                Some(repair) => repair,
+                None => return Err(original_error.unwrap_or(e)),
+            };
+            warnings.push(LenientWarning::new(kind, &raw[range.clone()], &replacement));
+            raw.replace_range(range, &replacement);
+            original_error.get_or_insert(e);
+        }
+
+        Err(original_error.expect("loop runs at least once"))
+    }
+}
+
+/// Returns a repair (range and replacement) for the given error, if it is one
+/// of the mistakes we repair. Mostly based on the suggestions of the error.
+fn repair_error(e: &ParseError, raw: &str) -> Option<(RepairKind, Range<usize>, String)> {
+    use ParseErrorKind::*;
+
+    let from_suggestion = |kind| {
+        let s = e.suggestions(raw).into_iter().next()?;
+        Some((kind, s.range(), s.replacement().to_owned()))
+    };
+
+    match e.kind {
+        UnknownEscape => {
+            let span = e.span.clone()?;
+            let replacement = match &raw[span.clone()] {
+                r"\a" => r"\x07",
+                r"\b" => r"\x08",
+                r"\f" => r"\x0C",
+                r"\v" => r"\x0B",
+                r"\e" => r"\x1B",
+                r"\?" => "?",
+                r"\U" => {
+                    let digits = raw.get(span.end..span.end + 8)?;
+                    let value = u32::from_str_radix(digits, 16).ok()?;
+                    let replacement = format!("\\u{{{:X}}}", value);
+                    return Some((RepairKind::CEscape, span.start..span.end + 8, replacement));
+                }
+                _ => return None,
+            };
+            Some((RepairKind::CEscape, span, replacement.to_owned()))
+        }
+        UnicodeEscapeWithoutBrace => {
+            // Only `\uXXXX` with exactly four digits, as in C.
+            let s = e.suggestions(raw).into_iter().next()?;
+            if s.applicability() != Applicability::MachineApplicable {
+                return None;
+            }
+            Some((RepairKind::CEscape, s.range(), s.replacement().to_owned()))
+        }
+        UnterminatedString | UnterminatedRawString => {
+            from_suggestion(RepairKind::MissingClosingQuote)
+        }
+        UnterminatedCharLiteral | UnterminatedByteLiteral => {
+            Some((RepairKind::MissingClosingQuote, raw.len()..raw.len(), "'".to_owned()))
+        }
+        OverlongCharLiteral | OverlongByteLiteral => {
+            from_suggestion(RepairKind::SingleQuotedString)
+        }
+        _ => None,
+    }
+}
+
+/// Repairs `0755` and `0X1F`, which are valid Rust, but likely mean something
+/// else.
+fn repair_integer(lit: Literal<String>, warnings: &mut Vec<LenientWarning>) -> Literal<String> {
+    let int = match &lit {
+        Literal::Integer(int) if int.base() == IntegerBase::Decimal => int,
+        _ => return lit,
+    };
+
+    let main = int.raw_main_part();
+    let suffix = int.suffix();
+    let (kind, original, replacement, repaired) = if main == "0" && suffix.len() > 1 {
+        // `0X1F` is parsed as `0` with suffix `X1F`.
+        let prefix = match suffix.as_bytes()[0] {
+            b'X' => "0x",
+            b'O' => "0o",
+            b'B' => "0b",
+            _ => return lit,
+        };
+        let original = format!("0{}", &suffix[..1]);
+        let repaired = format!("{}{}", prefix, &suffix[1..]);
+        (RepairKind::UppercasePrefix, original, prefix.to_owned(), repaired)
+    } else if main.len() > 1
+        && main.starts_with('0')
+        && main.bytes().all(|b| matches!(b, b'0'..=b'7' | b'_'))
+    {
+        let replacement = format!("0o{}", &main[1..]);
+        let repaired = format!("{}{}", replacement, suffix);
+        (RepairKind::LeadingZeroOctal, main.to_owned(), replacement, repaired)
+    } else {
+        return lit;
+    };
+
+    // If the repaired literal is invalid (e.g. `0Xyz`), we keep the original.
+    match Literal::parse(repaired) {
+        Ok(repaired) => {
+            warnings.push(LenientWarning::new(kind, &original, &replacement));
+            repaired
+        }
+        Err(_) => lit,
+    }
+}
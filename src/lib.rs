@@ -62,17 +62,33 @@
 //!     not a literal, or if you are trying to turn it into a specific kind of
 //!     literal and the token tree is a different kind of literal.
 //!
+//! - **`TryFrom<proc_macro::TokenStream>`**: like the `TokenTree` impl, but
+//!   requires the token stream to consist of exactly one token.
+//!
+//! - **[`extract`]**: turns a token tree directly into a value like `u16` or `String`.
+//!
 //! All of the `From` and `TryFrom` conversions also work for reference to
 //! `proc_macro` types. Additionally, if the crate feature `proc-macro2` is
 //! enabled, all these `From` and `TryFrom` impls also exist for the
 //! corresponding `proc_macro2` types.
 //!
+//! To keep the span of the token around (e.g. to report errors about the
+//! value of a literal later), all `TryFrom` impls also exist for [`Spanned`],
+//! e.g. `Spanned::<StringLit<String>>::try_from(tt)`.
+//!
 //! **Note**: `true` and `false` are `Ident`s when passed to your proc macro.
 //! The `TryFrom<TokenTree>` impls check for those two special idents and
 //! return a [`BoolLit`] appropriately. For that reason, there is also no
 //! `TryFrom<proc_macro::Literal>` impl for [`BoolLit`]. The `proc_macro::Literal`
 //! simply cannot represent bool literals.
 //!
+//! **Note**: when a `macro_rules!` macro forwards a `$x:literal` or `$x:expr`
+//! fragment to your proc macro, the literal arrives wrapped in an invisible
+//! group (`Delimiter::None`). The `TryFrom<TokenTree>` impls look through
+//! such groups if they contain a single token. Negated literals like `-3`
+//! cannot be represented by the literal types of this library, but can be
+//! obtained as value via [`extract`].
+//!
 //!
 //! # Examples
 //!
@@ -144,6 +160,8 @@
 //!
 //! - `proc-macro2`: adds the dependency `proc_macro2`, a bunch of `From` and
 //!   `TryFrom` impls, and [`InvalidToken::to_compile_error2`].
+//! - `half`: adds the dependency `half` and allows converting float literals
+//!   to `half::f16` via [`FloatLit::value`].
 //! - `check_suffix`: if enabled, `parse` functions will exactly verify that the
 //!   literal suffix is valid. Adds the dependency `unicode-xid`. If disabled,
 //!   only an approximate check (only in ASCII range) is done. If you are
@@ -169,19 +187,35 @@ mod bool;
 mod byte;
 mod bytestr;
 mod char;
+mod chunks;
 mod cstr;
+mod dedent;
+mod emit;
 mod err;
-mod escape;
+mod explain;
+mod extract;
 mod float;
 mod impls;
 mod integer;
+mod lenient;
 mod parse;
+mod render;
+mod spanned;
 mod string;
+mod suggest;
+
+pub mod concat;
+pub mod const_parse;
+pub mod doc;
+pub mod escape;
+pub mod fmt;
+pub mod lint;
+pub mod meta;
+pub mod unescape;
 
 
 use std::{
     borrow::{Borrow, Cow},
-    fmt,
     ops::{Deref, Range},
 };
 
@@ -190,11 +224,21 @@ pub use self::{
     byte::ByteLit,
     bytestr::ByteStringLit,
     char::CharLit,
+    chunks::{ByteChunk, ByteChunks, Chunk, Chunks},
     cstr::CStringLit,
-    err::{InvalidToken, ParseError},
-    float::{FloatLit, FloatType},
+    dedent::DedentMode,
+    emit::EmitOptions,
+    err::{Expected, InvalidToken, ParseError, TokenKind},
+    explain::explain,
+    extract::{extract, ExtractError, FromLiteral, LiteralToken},
+    float::{FloatLit, FloatRounding, FloatType, FromFloatLiteral},
     integer::{FromIntegerLiteral, IntegerBase, IntegerLit, IntegerType},
+    lenient::{LenientLiteral, LenientWarning, RepairKind},
+    parse::{classify, ParseOptions},
+    render::RenderOptions,
+    spanned::Spanned,
     string::StringLit,
+    suggest::{Applicability, Suggestion},
 };
 
 
@@ -224,12 +268,71 @@ pub enum Literal<B: Buffer> {
     CString(CStringLit<B>),
 }
 
+/// The kind of a [`Literal`], i.e. its variant without any data. Obtained via
+/// [`Literal::kind`], [`Literal::validate`] or [`classify`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum LiteralKind {
+    Bool,
+    Integer,
+    Float,
+    Char,
+    String,
+    Byte,
+    ByteString,
+    CString,
+}
+
+/// Generates the `is_*`, `as_*` and `into_*` methods of `Literal`.
+macro_rules! variant_accessors {
+    ($($variant:ident($ty:ty), $name:literal => $is:ident, $as:ident, $into:ident;)*) => {
+        $(
+            #[doc = concat!("Returns `true` if this is ", $name, ".")]
+            pub fn $is(&self) -> bool {
+                matches!(self, Literal::$variant(_))
+            }
+
+            #[doc = concat!(
+                "Returns the inner literal if this is ",
+                $name,
+                ", or `None` otherwise."
+            )]
+            pub fn $as(&self) -> Option<&$ty> {
+                match self {
+                    Literal::$variant(l) => Some(l),
+                    _ => None,
+                }
+            }
+
+            #[doc = concat!(
+                "Returns the inner literal if this is ",
+                $name,
+                ", or `self` otherwise."
+            )]
+            pub fn $into(self) -> Result<$ty, Self> {
+                match self {
+                    Literal::$variant(l) => Ok(l),
+                    other => Err(other),
+                }
+            }
+        )*
+    };
+}
+
 impl<B: Buffer> Literal<B> {
     /// Parses the given input as a Rust literal.
     pub fn parse(input: B) -> Result<Self, ParseError> {
         parse::parse(input)
     }
 
+    /// Parses the given input as a Rust literal, additionally checking it
+    /// against the given options. See [`ParseOptions`] for more information.
+    pub fn parse_with(input: B, options: &ParseOptions) -> Result<Self, ParseError> {
+        let lit = parse::parse(input)?;
+        options.check(&lit)?;
+        Ok(lit)
+    }
+
     /// Returns the suffix of this literal or `""` if it doesn't have one.
     ///
     /// Rust token grammar actually allows suffixes for all kinds of tokens.
@@ -280,6 +383,39 @@ impl<B: Buffer> Literal<B> {
         }
     }
 
+    /// Returns the kind of this literal.
+    ///
+    /// ```
+    /// use litrs::{Literal, LiteralKind};
+    ///
+    /// assert_eq!(Literal::parse("'a'").unwrap().kind(), LiteralKind::Char);
+    /// assert_eq!(Literal::parse("1e3").unwrap().kind(), LiteralKind::Float);
+    /// ```
+    pub fn kind(&self) -> LiteralKind {
+        match self {
+            Literal::Bool(_) => LiteralKind::Bool,
+            Literal::Integer(_) => LiteralKind::Integer,
+            Literal::Float(_) => LiteralKind::Float,
+            Literal::Char(_) => LiteralKind::Char,
+            Literal::String(_) => LiteralKind::String,
+            Literal::Byte(_) => LiteralKind::Byte,
+            Literal::ByteString(_) => LiteralKind::ByteString,
+            Literal::CString(_) => LiteralKind::CString,
+        }
+    }
+
+    variant_accessors! {
+        Bool(BoolLit), "a bool literal" => is_bool, as_bool, into_bool;
+        Integer(IntegerLit<B>), "an integer literal" => is_integer, as_integer, into_integer;
+        Float(FloatLit<B>), "a float literal" => is_float, as_float, into_float;
+        Char(CharLit<B>), "a char literal" => is_char, as_char, into_char;
+        String(StringLit<B>), "a string literal" => is_string, as_string, into_string;
+        Byte(ByteLit<B>), "a byte literal" => is_byte, as_byte, into_byte;
+        ByteString(ByteStringLit<B>), "a byte string literal"
+            => is_byte_string, as_byte_string, into_byte_string;
+        CString(CStringLit<B>), "a C string literal" => is_c_string, as_c_string, into_c_string;
+    }
+
     /// Returns the raw input that was passed to `parse`.
     ///
     /// This can be used to compare literals with different `Buffer` types.
@@ -300,6 +436,24 @@ impl<B: Buffer> Literal<B> {
 }
 
 impl Literal<&str> {
+    /// Checks whether the input is a valid Rust literal and returns its kind,
+    /// without allocating. This accepts and rejects exactly the same inputs as
+    /// [`Literal::parse`] and returns the same errors, but is faster if you
+    /// don't need the value.
+    ///
+    /// ```
+    /// use litrs::{Literal, LiteralKind};
+    ///
+    /// assert_eq!(Literal::validate(r#""foo\n""#).unwrap(), LiteralKind::String);
+    /// assert_eq!(Literal::validate("0x1F_u8").unwrap(), LiteralKind::Integer);
+    ///
+    /// let e = Literal::validate(r#"b"a\xFF\q""#).unwrap_err();
+    /// assert_eq!(e.span(), Some(7..9));
+    /// ```
+    pub fn validate(input: &str) -> Result<LiteralKind, ParseError> {
+        parse::validate(input)
+    }
+
     /// Makes a copy of the underlying buffer and returns the owned version of
     /// `Self`.
     pub fn into_owned(self) -> Literal<String> {
@@ -316,8 +470,8 @@ impl Literal<&str> {
     }
 }
 
-impl<B: Buffer> fmt::Display for Literal<B> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl<B: Buffer> std::fmt::Display for Literal<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Literal::Bool(l) => l.fmt(f),
             Literal::Integer(l) => l.fmt(f),
@@ -0,0 +1,285 @@
+//! Readability lints for integer and float literals.
+//!
+//! These mirror some of Clippy's lints about literals (e.g.
+//! `inconsistent_digit_grouping` or `unreadable_literal`). Clippy never sees
+//! the tokens passed to proc macros or `macro_rules!` macros, so macros
+//! accepting number literals can use this module to warn about them.
+//!
+//! Only the raw input of a literal is inspected, so all findings are purely
+//! about style: they never affect the value of the literal.
+//!
+//! ```
+//! use litrs::{lint::{self, LintKind}, IntegerLit};
+//!
+//! let lit = IntegerLit::parse("1_0000_00").unwrap();
+//! let findings = lint::integer(&lit);
+//! assert_eq!(findings.len(), 1);
+//! assert_eq!(findings[0].kind(), LintKind::InconsistentDigitGrouping);
+//! assert_eq!(findings[0].span(), 0..9);
+//!
+//! let lit = IntegerLit::parse("0xffu8").unwrap();
+//! assert_eq!(lint::integer(&lit)[0].kind(), LintKind::UnseparatedHexSuffix);
+//! ```
+
+use std::{fmt, ops::Range};
+
+use crate::{Buffer, FloatLit, IntegerBase, IntegerLit, Literal};
+
+
+/// Number of digits above which an ungrouped decimal or octal number is
+/// considered unreadable.
+const MAX_UNGROUPED_DIGITS: usize = 5;
+
+/// Like `MAX_UNGROUPED_DIGITS`, but for binary and hexadecimal numbers, which
+/// are usually grouped in nibbles or bytes.
+const MAX_UNGROUPED_DIGITS_BIN_HEX: usize = 8;
+
+
+/// A readability issue found in a literal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    kind: LintKind,
+    span: Range<usize>,
+}
+
+impl Finding {
+    /// The kind of issue.
+    pub fn kind(&self) -> LintKind {
+        self.kind
+    }
+
+    /// The byte range within the raw input of the literal that this finding
+    /// is about.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (at {}..{})", self.kind.description(), self.span.start, self.span.end)
+    }
+}
+
+/// All kinds of readability issues that are detected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LintKind {
+    /// Groups of digits separated by `_` have different sizes, e.g.
+    /// `1_0000_00`. The first group of the integer part and the last group of
+    /// the fractional part may be shorter than the others.
+    InconsistentDigitGrouping,
+
+    /// A long number without any `_` separating the digits, e.g. `1000000`.
+    UnreadableLiteral,
+
+    /// A hexadecimal literal using both lowercase and uppercase digits, e.g.
+    /// `0xaBcD`.
+    MixedCaseHexDigits,
+
+    /// A decimal integer with leading zeros, e.g. `0755`. This looks like an
+    /// octal number in C, but is decimal in Rust (octal would be `0o755`).
+    ZeroPrefixedLiteral,
+
+    /// A number part ending in `_` without a following suffix, e.g. `1_000_`.
+    TrailingUnderscore,
+
+    /// Multiple consecutive underscores, e.g. `1__000`.
+    DoubleUnderscore,
+
+    /// A hexadecimal literal with a suffix not separated by `_`, e.g. `0xffu8`.
+    /// As `f` is a hex digit, these are easy to misread (is `0x1f32` a `f32`?).
+    UnseparatedHexSuffix,
+}
+
+impl LintKind {
+    /// Returns the name of this lint in snake case, e.g.
+    /// `"inconsistent_digit_grouping"`. Where Clippy has an equivalent lint,
+    /// the same name is used.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::InconsistentDigitGrouping => "inconsistent_digit_grouping",
+            Self::UnreadableLiteral => "unreadable_literal",
+            Self::MixedCaseHexDigits => "mixed_case_hex_literals",
+            Self::ZeroPrefixedLiteral => "zero_prefixed_literal",
+            Self::TrailingUnderscore => "trailing_underscore",
+            Self::DoubleUnderscore => "double_underscore",
+            Self::UnseparatedHexSuffix => "unseparated_hex_suffix",
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            Self::InconsistentDigitGrouping => "digits are grouped inconsistently by underscores",
+            Self::UnreadableLiteral => "long literal lacking separators",
+            Self::MixedCaseHexDigits => "hex digits use both lowercase and uppercase letters",
+            Self::ZeroPrefixedLiteral => "decimal literal with leading zero looks like \
+                a C-style octal literal (use `0o` for octal)",
+            Self::TrailingUnderscore => "number ends with an underscore",
+            Self::DoubleUnderscore => "multiple consecutive underscores",
+            Self::UnseparatedHexSuffix => "hex literal suffix is not separated by an underscore",
+        }
+    }
+}
+
+impl fmt::Display for LintKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.name().fmt(f)
+    }
+}
+
+
+/// Checks an integer literal for readability issues.
+pub fn integer<B: Buffer>(lit: &IntegerLit<B>) -> Vec<Finding> {
+    let mut out = Vec::new();
+    let main = lit.raw_main_part();
+    let start = lit.base().prefix().len();
+    let has_suffix = !lit.suffix().is_empty();
+
+    let max_ungrouped = match lit.base() {
+        IntegerBase::Binary | IntegerBase::Hexadecimal => MAX_UNGROUPED_DIGITS_BIN_HEX,
+        IntegerBase::Decimal | IntegerBase::Octal => MAX_UNGROUPED_DIGITS,
+    };
+    check_grouping(main, start, Align::Right, max_ungrouped, &mut out);
+    check_underscores(main, start, has_suffix, &mut out);
+
+    let span = start..start + main.len();
+    match lit.base() {
+        IntegerBase::Hexadecimal => {
+            let lower = main.bytes().any(|b| matches!(b, b'a'..=b'f'));
+            let upper = main.bytes().any(|b| matches!(b, b'A'..=b'F'));
+            if lower && upper {
+                out.push(Finding { kind: LintKind::MixedCaseHexDigits, span });
+            }
+
+            if has_suffix && !main.ends_with('_') {
+                let end = lit.raw_input().len();
+                let span = end - lit.suffix().len()..end;
+                out.push(Finding { kind: LintKind::UnseparatedHexSuffix, span });
+            }
+        }
+        IntegerBase::Decimal => {
+            let digits = main.bytes().filter(|&b| b != b'_').count();
+            if main.starts_with('0') && digits > 1 {
+                out.push(Finding { kind: LintKind::ZeroPrefixedLiteral, span });
+            }
+        }
+        _ => {}
+    }
+
+    out.sort_by_key(|f| f.span.start);
+    out
+}
+
+/// Checks a float literal for readability issues.
+pub fn float<B: Buffer>(lit: &FloatLit<B>) -> Vec<Finding> {
+    let mut out = Vec::new();
+    let has_suffix = !lit.suffix().is_empty();
+
+    // Each part is only followed by a "suffix" (something after the
+    // underscore) if it's not the last part.
+    let int = lit.integer_part();
+    check_grouping(int, 0, Align::Right, MAX_UNGROUPED_DIGITS, &mut out);
+    check_underscores(int, 0, true, &mut out);
+
+    let exp = lit.exponent_part();
+    if let Some(frac) = lit.fractional_part() {
+        let start = int.len() + 1;
+        check_grouping(frac, start, Align::Left, MAX_UNGROUPED_DIGITS, &mut out);
+        check_underscores(frac, start, has_suffix || !exp.is_empty(), &mut out);
+    }
+
+    if !exp.is_empty() {
+        // Skip `e` and the optional sign.
+        let sign_len = exp[1..].starts_with(|c| c == '+' || c == '-') as usize;
+        let digits = &exp[1 + sign_len..];
+        let start = lit.number_part().len() - digits.len();
+        check_underscores(digits, start, has_suffix, &mut out);
+    }
+
+    out.sort_by_key(|f| f.span.start);
+    out
+}
+
+/// Checks a literal for readability issues. Only integer and float literals
+/// are checked, for all other kinds, an empty list is returned.
+pub fn literal<B: Buffer>(lit: &Literal<B>) -> Vec<Finding> {
+    match lit {
+        Literal::Integer(l) => integer(l),
+        Literal::Float(l) => float(l),
+        _ => Vec::new(),
+    }
+}
+
+
+/// Which group is allowed to be shorter than the others.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Align {
+    /// Groups are aligned to the right, so the first group can be shorter
+    /// (integer parts, e.g. `12_345`).
+    Right,
+    /// Groups are aligned to the left, so the last group can be shorter
+    /// (fractional parts, e.g. `0.123_45`).
+    Left,
+}
+
+/// Checks the groups of digits in `digits`, which starts at `start` in the raw
+/// input.
+fn check_grouping(
+    digits: &str,
+    start: usize,
+    align: Align,
+    max_ungrouped: usize,
+    out: &mut Vec<Finding>,
+) {
+    let groups = digits.split('_').filter(|g| !g.is_empty()).map(str::len).collect::<Vec<_>>();
+    let span = start..start + digits.len();
+
+    match &*groups {
+        [] => {}
+        [single] => {
+            if *single > max_ungrouped {
+                out.push(Finding { kind: LintKind::UnreadableLiteral, span });
+            }
+        }
+        _ => {
+            let (exception, regular) = match align {
+                Align::Right => (groups[0], &groups[1..]),
+                Align::Left => (groups[groups.len() - 1], &groups[..groups.len() - 1]),
+            };
+            let size = regular[0];
+            if regular.iter().any(|&len| len != size) || exception > size {
+                out.push(Finding { kind: LintKind::InconsistentDigitGrouping, span });
+            }
+        }
+    }
+}
+
+/// Checks for double and trailing underscores in `digits`, which starts at
+/// `start` in the raw input. If `followed` is true, a single trailing
+/// underscore is fine as it separates the digits from what follows.
+fn check_underscores(digits: &str, start: usize, followed: bool, out: &mut Vec<Finding>) {
+    let bytes = digits.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'_' {
+            i += 1;
+            continue;
+        }
+
+        let run_start = i;
+        while i < bytes.len() && bytes[i] == b'_' {
+            i += 1;
+        }
+        let span = start + run_start..start + i;
+        if i - run_start > 1 {
+            out.push(Finding { kind: LintKind::DoubleUnderscore, span });
+        } else if i == bytes.len() && run_start > 0 && !followed {
+            out.push(Finding { kind: LintKind::TrailingUnderscore, span });
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests;
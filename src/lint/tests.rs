@@ -0,0 +1,140 @@
+use super::{float, integer, literal, LintKind::{self, *}};
+use crate::{FloatLit, IntegerLit, Literal};
+
+
+// ===== Utility functions =======================================================================
+
+#[track_caller]
+fn check_int(input: &str, expected: &[(LintKind, std::ops::Range<usize>)]) {
+    let lit = IntegerLit::parse(input).unwrap();
+    let actual = integer(&lit).into_iter().map(|f| (f.kind(), f.span())).collect::<Vec<_>>();
+    assert_eq!(actual, expected, "findings for `{input}`");
+}
+
+#[track_caller]
+fn check_float(input: &str, expected: &[(LintKind, std::ops::Range<usize>)]) {
+    let lit = FloatLit::parse(input).unwrap();
+    let actual = float(&lit).into_iter().map(|f| (f.kind(), f.span())).collect::<Vec<_>>();
+    assert_eq!(actual, expected, "findings for `{input}`");
+}
+
+
+// ===== Actual tests ============================================================================
+
+#[test]
+fn no_findings() {
+    for input in [
+        "0", "7", "12345", "1_000", "10_000_000", "1_000u32", "1_000_u32", "0x_ff", "0xff_u8",
+        "0xDEAD_BEEF", "0xdeadbeef", "0b1010_1010", "0b10101010", "0o755", "0o7_5_5", "123usize",
+    ] {
+        check_int(input, &[]);
+    }
+
+    for input in [
+        "0.0", "3.14", "1e10", "12_345.678_9", "1_000.0", "0.123_456_7", "1e1_0", "1.5f32",
+        "1.5_f32", "2.5e-3_f64", "99_999.999_99",
+    ] {
+        check_float(input, &[]);
+    }
+}
+
+#[test]
+fn inconsistent_grouping() {
+    check_int("1_0000_00", &[(InconsistentDigitGrouping, 0..9)]);
+    check_int("1000_000", &[(InconsistentDigitGrouping, 0..8)]);
+    check_int("1_00_000", &[(InconsistentDigitGrouping, 0..8)]);
+    check_int("0xdead_be_ef", &[(InconsistentDigitGrouping, 2..12)]);
+    check_int("0b1_0_10", &[(InconsistentDigitGrouping, 2..8)]);
+    check_int("1_0000_00u64", &[(InconsistentDigitGrouping, 0..9)]);
+
+    check_float("1_0000_00.5", &[(InconsistentDigitGrouping, 0..9)]);
+    check_float("1.000_0000_1", &[(InconsistentDigitGrouping, 2..12)]);
+    check_float("1.0_00", &[(InconsistentDigitGrouping, 2..6)]);
+    check_float(
+        "12_3456_789.1_23e5",
+        &[(InconsistentDigitGrouping, 0..11), (InconsistentDigitGrouping, 12..16)],
+    );
+}
+
+#[test]
+fn unreadable() {
+    check_int("123456", &[(UnreadableLiteral, 0..6)]);
+    check_int("123456u64", &[(UnreadableLiteral, 0..6)]);
+    check_int("0o1234567", &[(UnreadableLiteral, 2..9)]);
+    check_int("0x123456789", &[(UnreadableLiteral, 2..11)]);
+    check_int("0b101010101", &[(UnreadableLiteral, 2..11)]);
+
+    check_float("123456.0", &[(UnreadableLiteral, 0..6)]);
+    check_float("0.123456", &[(UnreadableLiteral, 2..8)]);
+    check_float("1e123456", &[]);
+}
+
+#[test]
+fn mixed_case_hex() {
+    check_int("0xaBcD", &[(MixedCaseHexDigits, 2..6)]);
+    check_int("0xDEAD_beef", &[(MixedCaseHexDigits, 2..11)]);
+    check_int("0xAb_u8", &[(MixedCaseHexDigits, 2..5)]);
+    check_int("0xab_u8", &[]);
+}
+
+#[test]
+fn zero_prefixed() {
+    check_int("0755", &[(ZeroPrefixedLiteral, 0..4)]);
+    check_int("00", &[(ZeroPrefixedLiteral, 0..2)]);
+    check_int("0_1u8", &[(ZeroPrefixedLiteral, 0..3)]);
+    check_int("0u8", &[]);
+    check_int("0_", &[(TrailingUnderscore, 1..2)]);
+    check_int("0o755", &[]);
+    check_float("00.5", &[]);
+}
+
+#[test]
+fn underscores() {
+    check_int("1_000_", &[(TrailingUnderscore, 5..6)]);
+    check_int("1_000_u32", &[]);
+    check_int("1__000", &[(DoubleUnderscore, 1..3)]);
+    check_int("1_000__u32", &[(DoubleUnderscore, 5..7)]);
+    check_int("0x__ff", &[(DoubleUnderscore, 2..4)]);
+
+    check_float("1.0_", &[(TrailingUnderscore, 3..4)]);
+    check_float("1.0_f32", &[]);
+    check_float("1.0_e3", &[]);
+    check_float("1e3_", &[(TrailingUnderscore, 3..4)]);
+    check_float("1e-3__", &[(DoubleUnderscore, 4..6)]);
+    check_float("1e+3_f64", &[]);
+    check_float("1__2.0", &[(DoubleUnderscore, 1..3)]);
+    check_float("1.2__3", &[(DoubleUnderscore, 3..5)]);
+}
+
+#[test]
+fn unseparated_hex_suffix() {
+    check_int("0xffu8", &[(UnseparatedHexSuffix, 4..6)]);
+    check_int("0xFF_FFusize", &[(UnseparatedHexSuffix, 7..12)]);
+    check_int("0xff_u8", &[]);
+    check_int("0b11u8", &[]);
+    check_int("255u8", &[]);
+}
+
+#[test]
+fn multiple() {
+    check_int(
+        "0xaBcDeF123i64",
+        &[(UnreadableLiteral, 2..11), (MixedCaseHexDigits, 2..11), (UnseparatedHexSuffix, 11..14)],
+    );
+    check_int("0755__", &[(ZeroPrefixedLiteral, 0..6), (DoubleUnderscore, 4..6)]);
+}
+
+#[test]
+fn other_literals() {
+    assert!(literal(&Literal::parse(r#""123456""#).unwrap()).is_empty());
+    assert!(literal(&Literal::parse("true").unwrap()).is_empty());
+    assert_eq!(literal(&Literal::parse("0755").unwrap())[0].kind(), ZeroPrefixedLiteral);
+    assert_eq!(literal(&Literal::parse("0.1234567").unwrap())[0].kind(), UnreadableLiteral);
+}
+
+#[test]
+fn display() {
+    let lit = IntegerLit::parse("1__0").unwrap();
+    assert_eq!(integer(&lit)[0].to_string(), "multiple consecutive underscores (at 1..3)");
+    assert_eq!(ZeroPrefixedLiteral.to_string(), "zero_prefixed_literal");
+}
@@ -0,0 +1,457 @@
+//! Parsing attribute arguments like `#[foo(name = "x", retries = 3, verbose)]`.
+//!
+//! Many attribute macros accept a comma separated list of arguments. This
+//! module parses such a token stream into a [`MetaList`], an ordered list of
+//! [`MetaItem`]s. Each item has a name and one of three forms:
+//!
+//! - `name`: a flag ([`MetaValue::Flag`])
+//! - `name = <literal>`: a name-value pair ([`MetaValue::Literal`]), or
+//!   `name = -<literal>` for negative numbers ([`MetaValue::NegatedLiteral`])
+//! - `name(<nested>)`: a nested list ([`MetaValue::List`])
+//!
+//! A trailing comma is allowed. Raw identifiers are stored without the `r#`
+//! prefix, so `r#type = "x"` has the name `type`.
+//!
+//! A [`MetaList`] is obtained via `TryFrom<proc_macro::TokenStream>` (or
+//! `TryFrom<proc_macro2::TokenStream>` if the `proc-macro2` feature is
+//! enabled). All errors are [`MetaError`]s that remember the span of the
+//! offending token and can be turned into a `compile_error!` invocation.
+//!
+//! ```
+//! # #[cfg(feature = "proc-macro2")]
+//! # fn main() -> Result<(), litrs::meta::MetaError> {
+//! use std::convert::TryFrom;
+//! use litrs::{meta::MetaList, StringLit};
+//!
+//! let input: proc_macro2::TokenStream = r#"name = "x", retries = 3, verbose"#.parse().unwrap();
+//! let args = MetaList::try_from(input)?;
+//! args.check_duplicates()?;
+//! args.check_known(&["name", "retries", "verbose", "timeout"])?;
+//!
+//! let name = args.get::<StringLit<_>>("name")?.unwrap();
+//! assert_eq!(name.value(), "x");
+//! assert_eq!(args.get::<u32>("retries")?, Some(3));
+//! assert_eq!(args.get::<u32>("timeout")?, None);
+//! assert!(args.flag("verbose")?);
+//!
+//! // Using the wrong type results in an error.
+//! let e = args.get::<String>("retries").unwrap_err();
+//! assert_eq!(
+//!     e.to_string(),
+//!     r#"expected a string literal (e.g. "Ferris"), but found an integer literal (e.g. `27`)"#,
+//! );
+//! # Ok(())
+//! # }
+//! # #[cfg(not(feature = "proc-macro2"))]
+//! # fn main() {}
+//! ```
+
+use std::{convert::TryFrom, fmt};
+
+use crate::{
+    err::{self, Expected, InvalidToken, Span, TokenKind},
+    extract::ExtractErrorKind,
+    impls::kind_of,
+    ExtractError, FromLiteral, Literal,
+};
+
+
+/// An ordered list of attribute arguments, e.g. `name = "x", retries = 3,
+/// verbose`. See [the module documentation][self] for more information.
+#[derive(Debug, Clone)]
+pub struct MetaList {
+    items: Vec<MetaItem>,
+}
+
+impl MetaList {
+    /// Returns all items in the order they appear in the input.
+    pub fn items(&self) -> &[MetaItem] {
+        &self.items
+    }
+
+    /// Returns an iterator over all items in the order they appear in the
+    /// input.
+    pub fn iter(&self) -> std::slice::Iter<'_, MetaItem> {
+        self.items.iter()
+    }
+
+    /// Returns the number of items.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if there are no items.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns the first item with the given name, if any.
+    pub fn find(&self, name: &str) -> Option<&MetaItem> {
+        self.items.iter().find(|item| item.name == name)
+    }
+
+    /// Returns the literal value of the item `name` converted to `T`, or
+    /// `Ok(None)` if there is no such item. Returns an error if the item is
+    /// not of the form `name = <literal>` or if the literal cannot be
+    /// converted to `T`.
+    ///
+    /// `T` can be [`Literal<String>`][Literal] to accept any kind of literal,
+    /// one of the specific literal types like [`StringLit<String>`][crate::StringLit]
+    /// or any other type implementing [`FromLiteral`], like `u32` or `String`.
+    pub fn get<T: FromLiteral>(&self, name: &str) -> Result<Option<T>, MetaError> {
+        self.find(name).map(|item| item.literal()).transpose()
+    }
+
+    /// Returns `true` if the flag `name` is present and `false` if there is no
+    /// item called `name`. Returns an error if `name` is not a flag, e.g. if
+    /// it is given a value via `name = <literal>`.
+    pub fn flag(&self, name: &str) -> Result<bool, MetaError> {
+        match self.find(name) {
+            None => Ok(false),
+            Some(item) => match item.value {
+                MetaValue::Flag => Ok(true),
+                _ => Err(item.error(MetaErrorKind::ExpectedFlag(item.name.clone()))),
+            },
+        }
+    }
+
+    /// Returns the nested list of the item `name`, or `Ok(None)` if there is
+    /// no such item. Returns an error if the item is not of the form
+    /// `name(...)`.
+    pub fn list(&self, name: &str) -> Result<Option<&MetaList>, MetaError> {
+        match self.find(name) {
+            None => Ok(None),
+            Some(item) => match &item.value {
+                MetaValue::List(list) => Ok(Some(list)),
+                _ => Err(item.error(MetaErrorKind::ExpectedList(item.name.clone()))),
+            },
+        }
+    }
+
+    /// Returns an error pointing to the second occurence of the first name
+    /// that appears more than once. Nested lists are not checked.
+    pub fn check_duplicates(&self) -> Result<(), MetaError> {
+        for (i, item) in self.items.iter().enumerate() {
+            if self.items[..i].iter().any(|prev| prev.name == item.name) {
+                return Err(item.error(MetaErrorKind::DuplicateKey(item.name.clone())));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns an error pointing to the first item whose name is not in
+    /// `known`. Nested lists are not checked.
+    pub fn check_known(&self, known: &[&str]) -> Result<(), MetaError> {
+        match self.items.iter().find(|item| !known.contains(&&*item.name)) {
+            None => Ok(()),
+            Some(item) => Err(item.error(MetaErrorKind::UnknownKey {
+                name: item.name.clone(),
+                known: known.iter().map(|&s| s.to_owned()).collect(),
+            })),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a MetaList {
+    type Item = &'a MetaItem;
+    type IntoIter = std::slice::Iter<'a, MetaItem>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
+impl IntoIterator for MetaList {
+    type Item = MetaItem;
+    type IntoIter = std::vec::IntoIter<MetaItem>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+/// A single item of a [`MetaList`], e.g. `name = "x"`.
+#[derive(Debug, Clone)]
+pub struct MetaItem {
+    name: String,
+    value: MetaValue,
+
+    /// Span of the name.
+    span: Span,
+
+    /// Span of the literal or the group, or the span of the name for flags.
+    value_span: Span,
+}
+
+impl MetaItem {
+    /// The name of this item (without `r#` prefix).
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The value of this item.
+    pub fn value(&self) -> &MetaValue {
+        &self.value
+    }
+
+    /// Converts the literal value of this item to `T`. Returns an error if
+    /// this item is not of the form `name = <literal>` or if the literal
+    /// cannot be converted to `T`. Negated literals are converted via
+    /// [`FromLiteral::from_negated_literal`].
+    pub fn literal<T: FromLiteral>(&self) -> Result<T, MetaError> {
+        let res = match &self.value {
+            MetaValue::Literal(lit) => T::from_literal(lit.clone()),
+            MetaValue::NegatedLiteral(lit) => T::from_negated_literal(lit.clone()),
+            _ => return Err(self.error(MetaErrorKind::ExpectedValue(self.name.clone()))),
+        };
+
+        res.map_err(|e| {
+            let kind = match e.kind {
+                ExtractErrorKind::WrongKind { expected, actual } => {
+                    MetaErrorKind::InvalidToken(InvalidToken {
+                        expected,
+                        actual,
+                        span: self.value_span,
+                    })
+                }
+                _ => MetaErrorKind::InvalidValue(e),
+            };
+            self.value_error(kind)
+        })
+    }
+
+    /// Returns an error pointing to the name of this item. Useful for
+    /// reporting custom errors, e.g. about the value being out of range.
+    pub fn error(&self, kind: MetaErrorKind) -> MetaError {
+        MetaError { kind, span: self.span }
+    }
+
+    /// Returns an error pointing to the value of this item (or to the name,
+    /// for flags).
+    pub fn value_error(&self, kind: MetaErrorKind) -> MetaError {
+        MetaError { kind, span: self.value_span }
+    }
+}
+
+/// The value of a [`MetaItem`].
+#[derive(Debug, Clone)]
+pub enum MetaValue {
+    /// Just a name without value, e.g. `verbose`.
+    Flag,
+
+    /// A name-value pair, e.g. `name = "x"`.
+    Literal(Literal<String>),
+
+    /// A name-value pair with a negated literal, e.g. `offset = -3`. Stores
+    /// the literal without the minus sign.
+    NegatedLiteral(Literal<String>),
+
+    /// A nested list in parenthesis, e.g. `retry(count = 3, backoff)`.
+    List(MetaList),
+}
+
+
+// ==============================================================================================
+// ===== Errors
+// ==============================================================================================
+
+/// An error while parsing or inspecting attribute arguments. Remembers the
+/// span of the token it is about.
+#[derive(Debug, Clone)]
+pub struct MetaError {
+    kind: MetaErrorKind,
+    span: Span,
+}
+
+/// The different kinds of [`MetaError`]s.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum MetaErrorKind {
+    /// A different kind of token was expected, e.g. an identifier instead of
+    /// a literal as name, or a string literal instead of an integer literal
+    /// as value.
+    InvalidToken(InvalidToken),
+
+    /// The literal value could not be converted to the requested type, e.g.
+    /// because it's out of range.
+    InvalidValue(ExtractError),
+
+    /// The item with this name appears more than once.
+    DuplicateKey(String),
+
+    /// The item with this name is not in the list of known names.
+    UnknownKey {
+        name: String,
+        known: Vec<String>,
+    },
+
+    /// The item with this name was expected to be of the form `name = <literal>`.
+    ExpectedValue(String),
+
+    /// The item with this name was expected to be a flag without value.
+    ExpectedFlag(String),
+
+    /// The item with this name was expected to be of the form `name(...)`.
+    ExpectedList(String),
+
+    /// A custom error message.
+    Custom(String),
+}
+
+impl MetaError {
+    /// The kind of this error.
+    pub fn kind(&self) -> &MetaErrorKind {
+        &self.kind
+    }
+
+    /// Returns a token stream representing `compile_error!("msg");` where
+    /// `"msg"` is the output of `self.to_string()`. **Panics if called outside
+    /// of a proc-macro context!**
+    pub fn to_compile_error(&self) -> proc_macro::TokenStream {
+        err::compile_error(self.span, &self.to_string())
+    }
+
+    /// Like [`to_compile_error`][Self::to_compile_error], but returns a token
+    /// stream from `proc_macro2` and does not panic outside of a proc-macro
+    /// context.
+    #[cfg(feature = "proc-macro2")]
+    pub fn to_compile_error2(&self) -> proc_macro2::TokenStream {
+        err::compile_error2(self.span, &self.to_string())
+    }
+}
+
+impl From<InvalidToken> for MetaError {
+    fn from(src: InvalidToken) -> Self {
+        Self {
+            span: src.span,
+            kind: MetaErrorKind::InvalidToken(src),
+        }
+    }
+}
+
+impl std::error::Error for MetaError {}
+
+impl fmt::Display for MetaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            MetaErrorKind::InvalidToken(e) => e.fmt(f),
+            MetaErrorKind::InvalidValue(e) => e.fmt(f),
+            MetaErrorKind::DuplicateKey(name) => write!(f, "duplicate argument `{}`", name),
+            MetaErrorKind::UnknownKey { name, known } => {
+                write!(f, "unknown argument `{}`", name)?;
+                for (i, k) in known.iter().enumerate() {
+                    let sep = if i == 0 { ", expected one of " } else { ", " };
+                    write!(f, "{}`{}`", sep, k)?;
+                }
+                Ok(())
+            }
+            MetaErrorKind::ExpectedValue(name) => {
+                write!(f, "expected a value for `{0}` (e.g. `{0} = ...`)", name)
+            }
+            MetaErrorKind::ExpectedFlag(name) => write!(f, "`{}` does not take a value", name),
+            MetaErrorKind::ExpectedList(name) => {
+                write!(f, "expected a list for `{0}` (e.g. `{0}(...)`)", name)
+            }
+            MetaErrorKind::Custom(msg) => msg.fmt(f),
+        }
+    }
+}
+
+
+// ==============================================================================================
+// ===== `TryFrom<pm::TokenStream> for MetaList`
+// ==============================================================================================
+
+macro_rules! impl_from_token_stream {
+    ([$($prefix:tt)*] => ) => {
+        impl TryFrom<$($prefix)* TokenStream> for MetaList {
+            type Error = MetaError;
+            fn try_from(stream: $($prefix)* TokenStream) -> Result<Self, Self::Error> {
+                use $($prefix)* {Delimiter, TokenTree};
+
+                fn invalid(tt: &TokenTree, expected: impl Into<Expected>) -> MetaError {
+                    let actual = match tt {
+                        TokenTree::Group(_) => TokenKind::Group,
+                        TokenTree::Punct(_) => TokenKind::Punct,
+                        TokenTree::Ident(_) => TokenKind::Ident,
+                        TokenTree::Literal(lit) => kind_of(&Literal::from(lit)),
+                    };
+                    let span = tt.span().into();
+                    InvalidToken { expected: expected.into(), actual, span }.into()
+                }
+
+                let mut items = Vec::new();
+                let mut tokens = stream.into_iter().peekable();
+                while let Some(tt) = tokens.next() {
+                    let (name, span) = match tt {
+                        TokenTree::Ident(ident) => {
+                            let name = ident.to_string();
+                            let name = match name.strip_prefix("r#") {
+                                Some(stripped) => stripped.to_owned(),
+                                None => name,
+                            };
+                            (name, Span::from(ident.span()))
+                        }
+                        other => return Err(invalid(&other, TokenKind::Ident)),
+                    };
+
+                    let (value, value_span) = match tokens.peek() {
+                        Some(TokenTree::Punct(p)) if p.as_char() == '=' => {
+                            let mut last_span = p.span();
+                            tokens.next();
+                            let negated = match tokens.peek() {
+                                Some(TokenTree::Punct(p)) if p.as_char() == '-' => {
+                                    last_span = p.span();
+                                    tokens.next();
+                                    true
+                                }
+                                _ => false,
+                            };
+                            match tokens.next() {
+                                Some(tt) => {
+                                    let span = tt.span().into();
+                                    let lit = Literal::try_from(tt)?;
+                                    let value = if negated {
+                                        MetaValue::NegatedLiteral(lit)
+                                    } else {
+                                        MetaValue::Literal(lit)
+                                    };
+                                    (value, span)
+                                }
+                                None => return Err(MetaError {
+                                    kind: MetaErrorKind::ExpectedValue(name),
+                                    span: last_span.into(),
+                                }),
+                            }
+                        }
+                        Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis => {
+                            let span = g.span().into();
+                            let nested = Self::try_from(g.stream())?;
+                            tokens.next();
+                            (MetaValue::List(nested), span)
+                        }
+                        _ => (MetaValue::Flag, span),
+                    };
+                    items.push(MetaItem { name, value, span, value_span });
+
+                    match tokens.next() {
+                        None => break,
+                        Some(TokenTree::Punct(p)) if p.as_char() == ',' => {}
+                        Some(other) => return Err(invalid(&other, Expected::Custom("`,`"))),
+                    }
+                }
+
+                Ok(Self { items })
+            }
+        }
+    };
+}
+
+impl_from_token_stream!([proc_macro::] => );
+#[cfg(feature = "proc-macro2")]
+impl_from_token_stream!([proc_macro2::] => );
+
+
+#[cfg(all(test, feature = "proc-macro2"))]
+mod tests;
@@ -0,0 +1,197 @@
+use std::convert::TryFrom;
+
+use super::{MetaError, MetaErrorKind, MetaList, MetaValue};
+use crate::{BoolLit, FloatLit, IntegerLit, Literal, StringLit};
+
+
+// ===== Utility functions =======================================================================
+
+#[track_caller]
+fn parse(input: &str) -> Result<MetaList, MetaError> {
+    let stream: proc_macro2::TokenStream = input.parse().expect("invalid token stream");
+    MetaList::try_from(stream)
+}
+
+#[track_caller]
+fn names(list: &MetaList) -> Vec<&str> {
+    list.iter().map(|item| item.name()).collect()
+}
+
+
+// ===== Actual tests ============================================================================
+
+#[test]
+fn empty() {
+    let list = parse("").unwrap();
+    assert!(list.is_empty());
+    assert_eq!(list.len(), 0);
+    assert_eq!(list.get::<StringLit<_>>("foo").unwrap(), None);
+    assert!(!list.flag("foo").unwrap());
+}
+
+#[test]
+fn forms() {
+    let list = parse(r#"name = "x", retries = 3, verbose, retry(count = 2, backoff)"#).unwrap();
+    assert_eq!(names(&list), ["name", "retries", "verbose", "retry"]);
+
+    let items = list.items();
+    assert!(matches!(items[0].value(), MetaValue::Literal(Literal::String(s)) if s.value() == "x"));
+    assert!(matches!(items[1].value(), MetaValue::Literal(Literal::Integer(_))));
+    assert!(matches!(items[2].value(), MetaValue::Flag));
+    assert!(matches!(items[3].value(), MetaValue::List(_)));
+
+    let nested = list.list("retry").unwrap().unwrap();
+    assert_eq!(names(nested), ["count", "backoff"]);
+    assert_eq!(nested.get::<IntegerLit<_>>("count").unwrap().unwrap().value::<u8>(), Some(2));
+    assert!(nested.flag("backoff").unwrap());
+    assert!(list.list("other").unwrap().is_none());
+}
+
+#[test]
+fn trailing_comma_and_raw_idents() {
+    let list = parse("a, b = 1,").unwrap();
+    assert_eq!(names(&list), ["a", "b"]);
+
+    let list = parse(r#"r#type = "x", r#async"#).unwrap();
+    assert_eq!(names(&list), ["type", "async"]);
+    assert!(list.flag("async").unwrap());
+}
+
+#[test]
+fn typed_getters() {
+    let list = parse(r#"s = "hi", i = 0xff, f = 2.5, b = true, c = 'x', any = b"yo""#).unwrap();
+    assert_eq!(list.get::<StringLit<_>>("s").unwrap().unwrap().value(), "hi");
+    assert_eq!(list.get::<IntegerLit<_>>("i").unwrap().unwrap().value::<u8>(), Some(255));
+    assert_eq!(list.get::<FloatLit<_>>("f").unwrap().unwrap().number_part(), "2.5");
+    assert_eq!(list.get::<BoolLit>("b").unwrap(), Some(BoolLit::True));
+    assert_eq!(list.get::<crate::CharLit<_>>("c").unwrap().unwrap().value(), 'x');
+    assert!(matches!(list.get::<Literal<_>>("any").unwrap(), Some(Literal::ByteString(_))));
+
+    let e = list.get::<StringLit<_>>("i").unwrap_err();
+    assert!(matches!(e.kind(), MetaErrorKind::InvalidToken(_)));
+
+    assert_eq!(list.get::<String>("s").unwrap().unwrap(), "hi");
+    assert_eq!(list.get::<u16>("i").unwrap(), Some(255));
+    assert_eq!(list.get::<f32>("f").unwrap(), Some(2.5));
+    assert_eq!(list.get::<bool>("b").unwrap(), Some(true));
+    assert_eq!(list.get::<Vec<u8>>("any").unwrap().unwrap(), b"yo");
+
+    let e = list.get::<i8>("i").unwrap_err();
+    assert!(matches!(e.kind(), MetaErrorKind::InvalidValue(_)));
+    assert_eq!(e.to_string(), "literal is out of range for type `i8`");
+
+    let e = list.get::<BoolLit>("s").unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        r#"expected a bool literal (`true` or `false`), but found a string literal (e.g. "Ferris")"#,
+    );
+}
+
+#[test]
+fn negated_literals() {
+    let list = parse("offset = -3, min = -128, scale = -2.5, max = 3").unwrap();
+    assert!(matches!(list.items()[0].value(), MetaValue::NegatedLiteral(Literal::Integer(_))));
+    assert_eq!(list.get::<i32>("offset").unwrap(), Some(-3));
+    assert_eq!(list.get::<i8>("min").unwrap(), Some(-128));
+    assert_eq!(list.get::<f64>("scale").unwrap(), Some(-2.5));
+    assert_eq!(list.get::<i32>("max").unwrap(), Some(3));
+
+    let e = list.get::<u32>("offset").unwrap_err();
+    assert!(matches!(e.kind(), MetaErrorKind::InvalidValue(_)));
+    assert_eq!(e.to_string(), "literal cannot be negated");
+    assert_eq!(list.get::<Literal<_>>("offset").unwrap_err().to_string(), e.to_string());
+
+    let e = parse("n = -129").unwrap().get::<i8>("n").unwrap_err();
+    assert_eq!(e.to_string(), "literal is out of range for type `i8`");
+}
+
+#[test]
+fn wrong_forms() {
+    let list = parse(r#"flag, value = 1, nested(a)"#).unwrap();
+
+    let e = list.get::<IntegerLit<_>>("flag").unwrap_err();
+    assert!(matches!(e.kind(), MetaErrorKind::ExpectedValue(n) if n == "flag"));
+    assert_eq!(e.to_string(), "expected a value for `flag` (e.g. `flag = ...`)");
+    assert!(list.get::<Literal<_>>("nested").is_err());
+
+    let e = list.flag("value").unwrap_err();
+    assert!(matches!(e.kind(), MetaErrorKind::ExpectedFlag(n) if n == "value"));
+    assert_eq!(e.to_string(), "`value` does not take a value");
+    assert!(list.flag("nested").is_err());
+
+    let e = list.list("flag").unwrap_err();
+    assert!(matches!(e.kind(), MetaErrorKind::ExpectedList(n) if n == "flag"));
+    assert_eq!(e.to_string(), "expected a list for `flag` (e.g. `flag(...)`)");
+}
+
+#[test]
+fn duplicates() {
+    assert!(parse("a, b = 1, c(a, a)").unwrap().check_duplicates().is_ok());
+
+    let list = parse("a, b = 1, a = 2").unwrap();
+    let e = list.check_duplicates().unwrap_err();
+    assert!(matches!(e.kind(), MetaErrorKind::DuplicateKey(n) if n == "a"));
+    assert_eq!(e.to_string(), "duplicate argument `a`");
+
+    // The first occurence wins for getters.
+    assert!(list.flag("a").unwrap());
+}
+
+#[test]
+fn unknown() {
+    let list = parse("a, b = 1, c(x)").unwrap();
+    assert!(list.check_known(&["a", "b", "c"]).is_ok());
+    assert!(list.check_known(&["c", "b", "a", "d"]).is_ok());
+
+    let e = list.check_known(&["a", "c"]).unwrap_err();
+    assert!(matches!(e.kind(), MetaErrorKind::UnknownKey { name, .. } if name == "b"));
+    assert_eq!(e.to_string(), "unknown argument `b`, expected one of `a`, `c`");
+
+    let e = list.check_known(&[]).unwrap_err();
+    assert_eq!(e.to_string(), "unknown argument `a`");
+}
+
+#[test]
+fn syntax_errors() {
+    #[track_caller]
+    fn check(input: &str, expected: &str) {
+        match parse(input) {
+            Ok(list) => panic!("expected error for `{input}`, but got {list:?}"),
+            Err(e) => {
+                assert!(matches!(e.kind(), MetaErrorKind::InvalidToken(_)), "{e:?}");
+                assert_eq!(e.to_string(), expected, "error for `{input}`");
+            }
+        }
+    }
+
+    check("3", "expected an identifier, but found an integer literal (e.g. `27`)");
+    check(r#""a" = 3"#, r#"expected an identifier, but found a string literal (e.g. "Ferris")"#);
+    check("a, , b", "expected an identifier, but found a punctuation character");
+    check("a = b", "expected a literal, but found an identifier");
+    check("a = (3)", "expected a literal, but found a group");
+    check("a = --3", "expected a literal, but found a punctuation character");
+    check("a = -b", "expected a literal, but found an identifier");
+    check("a b", "expected `,`, but found an identifier");
+    check("a = 1 2", "expected `,`, but found an integer literal (e.g. `27`)");
+    check("a [b]", "expected `,`, but found a group");
+    check("a(b c)", "expected `,`, but found an identifier");
+    check("a::b", "expected `,`, but found a punctuation character");
+    check("a; b", "expected `,`, but found a punctuation character");
+
+    let e = parse("a =").unwrap_err();
+    assert!(matches!(e.kind(), MetaErrorKind::ExpectedValue(n) if n == "a"));
+    let e = parse("a = -").unwrap_err();
+    assert!(matches!(e.kind(), MetaErrorKind::ExpectedValue(n) if n == "a"));
+}
+
+#[test]
+fn custom_errors() {
+    let list = parse("retries = 300").unwrap();
+    let item = list.find("retries").unwrap();
+    let e = item.value_error(MetaErrorKind::Custom("at most 10 retries are allowed".into()));
+    assert_eq!(e.to_string(), "at most 10 retries are allowed");
+
+    let stream = e.to_compile_error2().to_string();
+    assert!(stream.starts_with("compile_error !"), "{stream}");
+    assert!(stream.contains("at most 10 retries are allowed"), "{stream}");
+}
@@ -1,20 +1,82 @@
+use std::fmt;
+
 use crate::{
     err::{
         perr,
         ParseErrorKind::{self, *},
     },
-    BoolLit, Buffer, ByteLit, ByteStringLit, CStringLit, CharLit, FloatLit, IntegerLit, Literal,
+    bytestr, cstr, float, string, BoolLit, Buffer, ByteLit, ByteStringLit, CStringLit, CharLit,
+    FloatLit, FloatRounding, FloatType, IntegerBase, IntegerLit, IntegerType, Literal, LiteralKind,
     ParseError, StringLit,
 };
 
 
 pub fn parse<B: Buffer>(input: B) -> Result<Literal<B>, ParseError> {
+    match dispatch(&input)? {
+        LiteralKind::Bool => BoolLit::parse(&input).map(Literal::Bool),
+        LiteralKind::Integer => IntegerLit::parse(input).map(Literal::Integer),
+        LiteralKind::Float => FloatLit::parse(input).map(Literal::Float),
+        LiteralKind::Char => CharLit::parse(input).map(Literal::Char),
+        LiteralKind::String => StringLit::parse(input).map(Literal::String),
+        LiteralKind::Byte => ByteLit::parse(input).map(Literal::Byte),
+        LiteralKind::ByteString => ByteStringLit::parse(input).map(Literal::ByteString),
+        LiteralKind::CString => CStringLit::parse(input).map(Literal::CString),
+    }
+}
+
+/// Checks whether `input` is a valid literal without allocating. Returns the
+/// same errors as `parse`.
+pub(crate) fn validate(input: &str) -> Result<LiteralKind, ParseError> {
+    let kind = dispatch(input)?;
+    match kind {
+        LiteralKind::Bool => BoolLit::parse(input).map(drop),
+        LiteralKind::Integer => IntegerLit::parse(input).map(drop),
+        LiteralKind::Float => FloatLit::parse(input).map(drop),
+        LiteralKind::Char => CharLit::parse(input).map(drop),
+        LiteralKind::String => string::validate(input),
+        LiteralKind::Byte => ByteLit::parse(input).map(drop),
+        LiteralKind::ByteString => bytestr::validate(input),
+        LiteralKind::CString => cstr::validate(input),
+    }?;
+    Ok(kind)
+}
+
+/// Returns which kind of literal `input` would be, by only looking at its
+/// start. This is the same check [`Literal::parse`] uses to decide which
+/// parser to use, so if the input is a valid literal, the returned kind is
+/// its kind. But the input is not checked any further: if `Some(_)` is
+/// returned, the input might still be invalid. Use [`Literal::validate`] to
+/// check the whole input.
+///
+/// Returns `None` if the input cannot be a literal at all, e.g. because it's
+/// empty or starts with a letter (other than `b`, `c` and `r` of byte string,
+/// C string and raw string literals).
+///
+/// ```
+/// use litrs::{classify, LiteralKind};
+///
+/// assert_eq!(classify("27u8"), Some(LiteralKind::Integer));
+/// assert_eq!(classify("2.7"), Some(LiteralKind::Float));
+/// assert_eq!(classify(r#"br"foo""#), Some(LiteralKind::ByteString));
+/// assert_eq!(classify("true"), Some(LiteralKind::Bool));
+/// assert_eq!(classify("foo"), None);
+///
+/// // Only the start is checked.
+/// assert_eq!(classify(r#""unterminated"#), Some(LiteralKind::String));
+/// ```
+pub fn classify(input: &str) -> Option<LiteralKind> {
+    dispatch(input).ok()
+}
+
+/// Decides which kind of literal `input` is by only looking at its start.
+/// Errors if it cannot be any literal.
+fn dispatch(input: &str) -> Result<LiteralKind, ParseError> {
     let (first, rest) = input.as_bytes().split_first().ok_or(perr(None, Empty))?;
     let second = input.as_bytes().get(1).copied();
 
     match first {
-        b'f' if &*input == "false" => Ok(Literal::Bool(BoolLit::False)),
-        b't' if &*input == "true" => Ok(Literal::Bool(BoolLit::True)),
+        b'f' if input == "false" => Ok(LiteralKind::Bool),
+        b't' if input == "true" => Ok(LiteralKind::Bool),
 
         // A number literal (integer or float).
         b'0'..=b'9' => {
@@ -27,27 +89,209 @@ pub fn parse<B: Buffer>(input: B) -> Result<Literal<B>, ParseError> {
             // The first non-decimal char in a float literal must
             // be '.', 'e' or 'E'.
             match input.as_bytes().get(1 + end_dec_digits(rest)) {
-                Some(b'.') | Some(b'e') | Some(b'E') => FloatLit::parse(input).map(Literal::Float),
-
-                _ => IntegerLit::parse(input).map(Literal::Integer),
+                Some(b'.') | Some(b'e') | Some(b'E') => Ok(LiteralKind::Float),
+                _ => Ok(LiteralKind::Integer),
             }
         }
 
-        b'\'' => CharLit::parse(input).map(Literal::Char),
-        b'"' | b'r' => StringLit::parse(input).map(Literal::String),
+        b'\'' => Ok(LiteralKind::Char),
+        b'"' | b'r' => Ok(LiteralKind::String),
 
-        b'b' if second == Some(b'\'') => ByteLit::parse(input).map(Literal::Byte),
-        b'b' if second == Some(b'r') || second == Some(b'"') => {
-            ByteStringLit::parse(input).map(Literal::ByteString)
-        }
+        b'b' if second == Some(b'\'') => Ok(LiteralKind::Byte),
+        b'b' if second == Some(b'r') || second == Some(b'"') => Ok(LiteralKind::ByteString),
 
-        b'c' => CStringLit::parse(input).map(Literal::CString),
+        b'c' => Ok(LiteralKind::CString),
 
         _ => Err(perr(None, InvalidLiteral)),
     }
 }
 
 
+/// Options to configure how literals are parsed. Used by
+/// [`Literal::parse_with`].
+///
+/// By default, all options are off and parsing behaves exactly like
+/// [`Literal::parse`], i.e. it accepts everything that is a valid literal
+/// *token*. That's what proc macros see, and those are allowed to give
+/// meaning to arbitrary suffixes like `23px` or `"foo"bar`.
+///
+/// With [`strict`][Self::strict], only literals that are also valid in
+/// ordinary expression position are accepted. This means:
+/// - Integer literals may only have integer type suffixes (e.g. `u8`) or, if
+///   written in decimal, float type suffixes (e.g. `3f32`).
+/// - Float literals may only have float type suffixes (e.g. `f64`).
+/// - All other literals must not have a suffix.
+/// - The value must be in range for the type given by the suffix (e.g. `256u8`
+///   is rejected). Unsuffixed integers must fit into `u128` and unsuffixed
+///   floats must not overflow `f64`.
+///
+/// Note that the minus sign is not part of the literal, so `128i8` is
+/// rejected in strict mode even though `-128i8` is a valid expression.
+///
+/// Suffixes that are not type suffixes valid for the literal kind are called
+/// *custom suffixes*. These can be allowed via [`allow_suffix`][Self::allow_suffix]
+/// and [`suffix_predicate`][Self::suffix_predicate]. Once either of those is
+/// configured (or strict mode is enabled), a custom suffix is only accepted if
+/// it's in the allow list or the predicate returns `true` for it.
+///
+/// ```
+/// use litrs::{Literal, ParseOptions};
+///
+/// let strict = ParseOptions::new().strict(true);
+/// assert!(Literal::parse_with("27u8", &strict).is_ok());
+/// assert!(Literal::parse_with("3.14", &strict).is_ok());
+/// assert!(Literal::parse_with("256u8", &strict).is_err());
+/// assert!(Literal::parse_with("1.0u32", &strict).is_err());
+/// assert!(Literal::parse_with(r#""foo"bar"#, &strict).is_err());
+///
+/// let css = ParseOptions::new().strict(true).allow_suffix("px").allow_suffix("pt");
+/// assert!(Literal::parse_with("16px", &css).is_ok());
+/// assert!(Literal::parse_with("1.5pt", &css).is_ok());
+/// assert!(Literal::parse_with("3cm", &css).is_err());
+/// ```
+#[derive(Clone, Default)]
+pub struct ParseOptions {
+    strict: bool,
+    allowed_suffixes: Vec<String>,
+    suffix_predicate: Option<fn(&str) -> bool>,
+}
+
+// Not derived, as old compilers cannot derive `Debug` for `fn(&str) -> bool`.
+impl fmt::Debug for ParseOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParseOptions")
+            .field("strict", &self.strict)
+            .field("allowed_suffixes", &self.allowed_suffixes)
+            .field("suffix_predicate", &self.suffix_predicate.map(|p| p as *const ()))
+            .finish()
+    }
+}
+
+impl ParseOptions {
+    /// Creates the default options, which accept the same inputs as
+    /// [`Literal::parse`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enables or disables strict mode, where only literals are accepted that
+    /// `rustc` accepts in expression position. See the type documentation for
+    /// more information.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Adds `suffix` to the list of allowed custom suffixes. These are
+    /// accepted on all kinds of literals (except `bool`).
+    pub fn allow_suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.allowed_suffixes.push(suffix.into());
+        self
+    }
+
+    /// Sets a predicate that decides whether a custom suffix is accepted. It
+    /// is only called for suffixes that are valid identifiers, and that are
+    /// neither type suffixes valid for the literal kind nor in the allow list.
+    pub fn suffix_predicate(mut self, predicate: fn(&str) -> bool) -> Self {
+        self.suffix_predicate = Some(predicate);
+        self
+    }
+
+    /// Returns whether strict mode is enabled.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Returns whether `suffix` is accepted as custom suffix.
+    fn accepts_custom_suffix(&self, suffix: &str) -> bool {
+        if self.allowed_suffixes.iter().any(|s| s == suffix) {
+            return true;
+        }
+
+        match self.suffix_predicate {
+            Some(predicate) => predicate(suffix),
+            None => !self.strict && self.allowed_suffixes.is_empty(),
+        }
+    }
+
+    /// Checks an already parsed literal against these options.
+    pub(crate) fn check<B: Buffer>(&self, lit: &Literal<B>) -> Result<(), ParseError> {
+        let suffix = lit.suffix();
+        let start_suffix = lit.raw_input().len() - suffix.len();
+        let full = 0..lit.raw_input().len();
+
+        match lit {
+            Literal::Integer(i) => {
+                // Float suffixes are only valid on decimal integers: `0b1f32`
+                // is rejected by rustc.
+                let float_ty = FloatType::from_suffix(suffix)
+                    .filter(|_| i.base() == IntegerBase::Decimal);
+
+                if let Some(ty) = IntegerType::from_suffix(suffix) {
+                    if self.strict && !integer_in_range(i, ty) {
+                        return Err(perr(full, IntegerOutOfRange));
+                    }
+                } else if let Some(ty) = float_ty {
+                    let rounding = float::integer_rounding(i.raw_main_part(), ty);
+                    if self.strict && rounding == FloatRounding::Infinity {
+                        return Err(perr(full, FloatOutOfRange));
+                    }
+                } else {
+                    self.check_custom_suffix(suffix, start_suffix)?;
+                    if self.strict && i.value::<u128>().is_none() {
+                        return Err(perr(full, IntegerOutOfRange));
+                    }
+                }
+            }
+            Literal::Float(f) => {
+                let ty = match FloatType::from_suffix(suffix) {
+                    Some(ty) => ty,
+                    None => {
+                        self.check_custom_suffix(suffix, start_suffix)?;
+                        FloatType::F64
+                    }
+                };
+                if self.strict && f.rounding(ty) == FloatRounding::Infinity {
+                    return Err(perr(full, FloatOutOfRange));
+                }
+            }
+            Literal::Bool(_) => {}
+            _ => self.check_custom_suffix(suffix, start_suffix)?,
+        }
+
+        Ok(())
+    }
+
+    fn check_custom_suffix(&self, suffix: &str, start: usize) -> Result<(), ParseError> {
+        if suffix.is_empty() || self.accepts_custom_suffix(suffix) {
+            Ok(())
+        } else {
+            Err(perr(start..start + suffix.len(), DisallowedSuffix))
+        }
+    }
+}
+
+/// Returns whether the value of `lit` fits into `ty`. For signed types, only
+/// the positive range is considered, as the literal itself is never negative.
+fn integer_in_range<B: Buffer>(lit: &IntegerLit<B>, ty: IntegerType) -> bool {
+    let max = match ty {
+        IntegerType::U8 => u8::MAX as u128,
+        IntegerType::U16 => u16::MAX as u128,
+        IntegerType::U32 => u32::MAX as u128,
+        IntegerType::U64 => u64::MAX as u128,
+        IntegerType::U128 => u128::MAX,
+        IntegerType::Usize => usize::MAX as u128,
+        IntegerType::I8 => i8::MAX as u128,
+        IntegerType::I16 => i16::MAX as u128,
+        IntegerType::I32 => i32::MAX as u128,
+        IntegerType::I64 => i64::MAX as u128,
+        IntegerType::I128 => i128::MAX as u128,
+        IntegerType::Isize => isize::MAX as u128,
+    };
+
+    lit.value::<u128>().map_or(false, |v| v <= max)
+}
+
 pub(crate) fn first_byte_or_empty(s: &str) -> Result<u8, ParseError> {
     s.as_bytes().first().copied().ok_or(perr(None, Empty))
 }
@@ -60,7 +304,7 @@ pub(crate) fn end_dec_digits(input: &[u8]) -> usize {
         .unwrap_or(input.len())
 }
 
-pub(crate) fn hex_digit_value(digit: u8) -> Option<u8> {
+pub(crate) const fn hex_digit_value(digit: u8) -> Option<u8> {
     match digit {
         b'0'..=b'9' => Some(digit - b'0'),
         b'a'..=b'f' => Some(digit - b'a' + 10),
@@ -0,0 +1,297 @@
+use std::fmt::Write;
+
+use crate::{err::ParseErrorKind, ParseError};
+
+
+/// Options to configure how a [`ParseError`] is rendered by
+/// [`ParseError::render_with`].
+///
+/// ```
+/// use litrs::{Literal, RenderOptions};
+///
+/// let source = "let s = \"a\\qb\";";
+/// let e = Literal::parse(&source[8..14]).unwrap_err();
+/// let options = RenderOptions::new().offset(8).path("src/main.rs");
+/// assert_eq!(e.render_with(source, &options), "\
+/// error[LIT0008]: unknown escape
+///  --> src/main.rs:1:11
+///   |
+/// 1 | let s = \"a\\qb\";
+///   |           ^^
+///   |
+///   = help: valid escapes are `\\n`, `\\r`, `\\t`, `\\\\`, `\\0`, `\\'`, `\\\"`, \
+///     `\\x7F` and `\\u{7FFF}`");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RenderOptions {
+    color: bool,
+    offset: usize,
+    path: Option<String>,
+}
+
+impl RenderOptions {
+    /// Creates the default options: no colors, the source is just the
+    /// literal, and no path is shown.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enables or disables colored output via ANSI escape codes, using the
+    /// same colors as `rustc`.
+    pub fn color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Sets the byte offset of the literal within the source passed to
+    /// `render_with`. Use this if the literal is part of a larger source
+    /// file. The default is 0.
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Sets the file path that is shown in the location line
+    /// (` --> path:line:col`). Without a path, no location line is shown.
+    pub fn path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+}
+
+/// ANSI escape codes used for the colored output.
+struct Style {
+    error: &'static str,
+    bold: &'static str,
+    gutter: &'static str,
+    reset: &'static str,
+}
+
+const COLORED: Style = Style {
+    error: "\x1b[1;31m",
+    bold: "\x1b[1m",
+    gutter: "\x1b[1;34m",
+    reset: "\x1b[0m",
+};
+
+const PLAIN: Style = Style { error: "", bold: "", gutter: "", reset: "" };
+
+impl ParseError {
+    /// Renders this error like `rustc` does: the source line(s) with the span
+    /// of the error underlined, plus a help line if available. Like `rustc`,
+    /// the header contains the [error code][Self::code]. `source` has to
+    /// be the string that was passed to the `parse` function. The output does
+    /// not end with a newline.
+    ///
+    /// ```
+    /// use litrs::StringLit;
+    ///
+    /// let e = StringLit::parse(r#""a\x80b""#).unwrap_err();
+    /// assert_eq!(e.render(r#""a\x80b""#), r#"
+    /// error[LIT0011]: `\x` escape in char/string literal exceed ASCII range
+    ///   |
+    /// 1 | "a\x80b"
+    ///   |   ^^^^
+    ///   |
+    ///   = help: use a `\u{...}` escape for non-ASCII characters, e.g. `\u{80}`
+    /// "#.trim());
+    /// ```
+    ///
+    /// Use [`render_with`][Self::render_with] for colors or to render a
+    /// literal inside a larger source.
+    pub fn render(&self, source: &str) -> String {
+        self.render_with(source, &RenderOptions::new())
+    }
+
+    /// Like [`render`][Self::render], but configured by the given options.
+    /// See [`RenderOptions`].
+    pub fn render_with(&self, source: &str, options: &RenderOptions) -> String {
+        let style = if options.color { &COLORED } else { &PLAIN };
+        let mut out = String::new();
+
+        let _ = write!(
+            out,
+            "{}error[{}]{}{}: {}{}",
+            style.error,
+            self.code(),
+            style.reset,
+            style.bold,
+            self.kind.description(),
+            style.reset,
+        );
+
+        // Clamp span to the source, in case the wrong source was passed.
+        let span = self.span.as_ref().map(|span| {
+            let clamp = |i: usize| {
+                floor_char_boundary(source, (options.offset + i).min(source.len()))
+            };
+            clamp(span.start)..clamp(span.end)
+        });
+
+        // Figure out all lines touched by the span.
+        let lines = match &span {
+            Some(span) => lines_of(source, span.start, span.end),
+            None => vec![],
+        };
+        let last_line_number = lines.last().map(|l| l.number).unwrap_or(0);
+        let gutter_width = last_line_number.to_string().len();
+        let empty_gutter = format!("{}{} |{}", style.gutter, " ".repeat(gutter_width), style.reset);
+
+        if let Some(path) = &options.path {
+            let pos = span.as_ref().map(|s| s.start).unwrap_or(options.offset.min(source.len()));
+            let pos = floor_char_boundary(source, pos);
+            let line = source[..pos].matches('\n').count() + 1;
+            let line_start = source[..pos].rfind('\n').map(|i| i + 1).unwrap_or(0);
+            let col = source[line_start..pos].chars().count() + 1;
+            let _ = write!(
+                out,
+                "\n{}{}-->{} {}:{}:{}",
+                style.gutter,
+                " ".repeat(gutter_width),
+                style.reset,
+                path,
+                line,
+                col,
+            );
+        }
+
+        if let Some(span) = &span {
+            let _ = write!(out, "\n{}", empty_gutter);
+            for line in &lines {
+                let text = &source[line.start..line.end];
+                let _ = write!(
+                    out,
+                    "\n{}{:>width$} |{} {}",
+                    style.gutter,
+                    line.number,
+                    style.reset,
+                    expand_tabs(text),
+                    width = gutter_width,
+                );
+
+                // The part of the line that is covered by the span. Empty
+                // spans (e.g. at the end of input) get one caret. The span
+                // might start in the line break, which is not part of `text`.
+                let from = (span.start.max(line.start) - line.start).min(text.len());
+                let to = (span.end.min(line.end) - line.start).max(from);
+                let indent = display_width(&text[..from]);
+                let carets = display_width(&text[from..to]).max(1);
+                let _ = write!(
+                    out,
+                    "\n{} {}{}{}",
+                    empty_gutter,
+                    " ".repeat(indent),
+                    style.error,
+                    "^".repeat(carets),
+                );
+                out.push_str(style.reset);
+            }
+        }
+
+        if let Some(help) = help(self.kind) {
+            if span.is_some() {
+                let _ = write!(out, "\n{}", empty_gutter);
+            }
+            let _ = write!(
+                out,
+                "\n{}{} ={} {}help{}: {}",
+                style.gutter,
+                " ".repeat(gutter_width),
+                style.reset,
+                style.bold,
+                style.reset,
+                help,
+            );
+        }
+
+        out
+    }
+}
+
+struct Line {
+    /// 1-based line number.
+    number: usize,
+    /// Byte range of the line, without line break.
+    start: usize,
+    end: usize,
+}
+
+/// Returns all lines that overlap with the byte range `start..end`. At least
+/// one line is returned.
+fn lines_of(source: &str, start: usize, end: usize) -> Vec<Line> {
+    let mut out = Vec::new();
+    let mut number = source[..start].matches('\n').count() + 1;
+    let mut line_start = source[..start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    loop {
+        let line_end = source[line_start..].find('\n')
+            .map(|i| line_start + i)
+            .unwrap_or(source.len());
+        let line_end = if source[..line_end].ends_with('\r') { line_end - 1 } else { line_end };
+        out.push(Line { number, start: line_start, end: line_end });
+
+        // Continue if the span continues after the line break.
+        let next = source[line_end..].find('\n').map(|i| line_end + i + 1);
+        match next {
+            Some(next) if next < end => {
+                line_start = next;
+                number += 1;
+            }
+            _ => break,
+        }
+    }
+    out
+}
+
+fn floor_char_boundary(s: &str, mut i: usize) -> usize {
+    while !s.is_char_boundary(i) {
+        i -= 1;
+    }
+    i
+}
+
+/// Like `rustc`, we render tabs as four spaces.
+fn expand_tabs(s: &str) -> String {
+    s.replace('\t', "    ")
+}
+
+fn display_width(s: &str) -> usize {
+    s.chars().map(|c| if c == '\t' { 4 } else { 1 }).sum()
+}
+
+/// Returns a help message for the given error kind, if there is one.
+fn help(kind: ParseErrorKind) -> Option<&'static str> {
+    use ParseErrorKind::*;
+
+    let help = match kind {
+        UnknownEscape => {
+            r#"valid escapes are `\n`, `\r`, `\t`, `\\`, `\0`, `\'`, `\"`, `\x7F` and `\u{7FFF}`"#
+        }
+        InvalidXEscape => r"a `\x` escape must be followed by exactly two hex digits, e.g. `\x7F`",
+        NonAsciiXEscape => r"use a `\u{...}` escape for non-ASCII characters, e.g. `\u{80}`",
+        UnicodeEscapeInByteLiteral => r"use `\x` escapes for bytes, e.g. `\xFF`",
+        UnicodeEscapeWithoutBrace => r"unicode escapes are written with braces, e.g. `\u{1F980}`",
+        TooManyDigitInUnicodeEscape => r"a `\u{...}` escape has at most 6 hex digits",
+        InvalidUnicodeEscapeChar => "the value must be at most `10FFFF` and not a surrogate \
+            (`D800` to `DFFF`)",
+        UnterminatedUnicodeEscape => r"add the closing brace, e.g. `\u{1F980}`",
+        OverlongCharLiteral => "if you meant to write a string literal, use double quotes",
+        OverlongByteLiteral => "if you meant to write a byte string literal, use double quotes",
+        EmptyCharLiteral | EmptyByteLiteral => r"to write a quote character, escape it: `'\''`",
+        NonAsciiInByteLiteral => r"use a `\x` escape for the byte, e.g. `\xFF`",
+        UnescapedSingleQuote => r"escape the quote: `\'`",
+        UnescapedSpecialWhitespace => r"use an escape instead: `\n`, `\t` or `\r`",
+        UnterminatedRawString => {
+            r##"raw strings end with `"` followed by as many `#` as they start with"##
+        }
+        UnterminatedString => r#"add the closing `"`"#,
+        UnterminatedCharLiteral | UnterminatedByteLiteral => "add the closing `'`",
+        DisallowedNulEscape | NulByte => "C strings are terminated by a nul byte and cannot \
+            contain one",
+        CarriageReturn => r"use the `\r` escape instead",
+        InvalidSuffix => "suffixes must be valid identifiers",
+        InvalidDigit => "binary literals only contain `0` and `1`, octal literals only `0` to `7`",
+        NoExponentDigits => "add digits after the `e`, e.g. `1e3`",
+        _ => return None,
+    };
+    Some(help)
+}
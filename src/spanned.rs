@@ -0,0 +1,128 @@
+use std::{fmt, ops::Deref};
+
+use crate::err::{self, Span};
+
+
+/// A value together with the span of the token it was obtained from.
+///
+/// After converting a token into a literal (e.g. via `StringLit::try_from`),
+/// the span of the token is lost. But oftentimes, the literal's value is
+/// validated later (e.g. "unknown mode 'fsat'") and the error should point to
+/// the literal. For that, all `TryFrom` impls that convert tokens into
+/// literals also exist for `Spanned<_>`, for example
+/// `Spanned<StringLit<String>>: TryFrom<proc_macro::TokenTree>`.
+///
+/// `Spanned<T>` derefs to `T`, so you can call all methods of `T` directly.
+/// Use [`compile_error`][Self::compile_error] to emit an error at the span.
+///
+/// ```ignore
+/// use std::convert::TryFrom;
+/// use proc_macro::TokenStream;
+/// use litrs::{Spanned, StringLit};
+///
+/// #[proc_macro]
+/// pub fn foo(input: TokenStream) -> TokenStream {
+///     let first_token = input.into_iter().next().unwrap(); // Do proper error handling!
+///     let mode = match Spanned::<StringLit<String>>::try_from(first_token) {
+///         Ok(lit) => lit,
+///         Err(e) => return e.to_compile_error(),
+///     };
+///
+///     match mode.value() {
+///         "fast" | "slow" => { /* ... */ }
+///         other => return mode.compile_error(format!("unknown mode '{}'", other)),
+///     }
+///
+///     todo!()
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Spanned<T> {
+    inner: T,
+    span: Span,
+}
+
+impl<T> Spanned<T> {
+    /// Creates a new instance from the given value and `proc_macro` span.
+    pub fn new(inner: T, span: proc_macro::Span) -> Self {
+        Self { inner, span: span.into() }
+    }
+
+    /// Creates a new instance from the given value and `proc_macro2` span.
+    #[cfg(feature = "proc-macro2")]
+    pub fn new2(inner: T, span: proc_macro2::Span) -> Self {
+        Self { inner, span: span.into() }
+    }
+
+    pub(crate) fn with_span(inner: T, span: impl Into<Span>) -> Self {
+        Self { inner, span: span.into() }
+    }
+
+    /// Returns a reference to the inner value.
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns the inner value, discarding the span.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Returns a `Spanned` with a reference to the inner value and the same
+    /// span.
+    pub fn as_ref(&self) -> Spanned<&T> {
+        Spanned { inner: &self.inner, span: self.span }
+    }
+
+    /// Applies `f` to the inner value, keeping the span.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Spanned<U> {
+        Spanned { inner: f(self.inner), span: self.span }
+    }
+
+    /// Returns the span as `proc_macro::Span`. **Panics if called outside of
+    /// a proc-macro context!**
+    pub fn span(&self) -> proc_macro::Span {
+        match self.span {
+            Span::One(s) => s,
+            #[cfg(feature = "proc-macro2")]
+            Span::Two(s) => s.unwrap(),
+        }
+    }
+
+    /// Returns the span as `proc_macro2::Span`.
+    #[cfg(feature = "proc-macro2")]
+    pub fn span2(&self) -> proc_macro2::Span {
+        match self.span {
+            Span::One(s) => s.into(),
+            Span::Two(s) => s,
+        }
+    }
+
+    /// Returns a token stream representing `compile_error!("msg");` with the
+    /// span of this value. **Panics if called outside of a proc-macro
+    /// context!**
+    pub fn compile_error(&self, msg: impl fmt::Display) -> proc_macro::TokenStream {
+        err::compile_error(self.span, &msg.to_string())
+    }
+
+    /// Like [`compile_error`][Self::compile_error], but returns a token
+    /// stream from `proc_macro2` and does not panic outside of a proc-macro
+    /// context.
+    #[cfg(feature = "proc-macro2")]
+    pub fn compile_error2(&self, msg: impl fmt::Display) -> proc_macro2::TokenStream {
+        err::compile_error2(self.span, &msg.to_string())
+    }
+}
+
+impl<T> Deref for Spanned<T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Spanned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
@@ -2,9 +2,13 @@ use std::{fmt, ops::Range};
 
 use crate::{
     err::{perr, ParseErrorKind::*},
-    escape::{scan_raw_string, unescape_string},
+    escape::{
+        escape_str_into, raw_string_hashes, scan_raw_string, unescape_string, EscapeContainer,
+        RawChunks,
+    },
+    dedent::dedent,
     parse::first_byte_or_empty,
-    Buffer, ParseError,
+    Buffer, Chunks, DedentMode, ParseError,
 };
 
 
@@ -63,6 +67,55 @@ impl<B: Buffer> StringLit<B> {
             .unwrap_or_else(|| raw.cut(inner_range).into_cow())
     }
 
+    /// Returns the value with indentation removed like the `indoc!` macro
+    /// does: the common leading whitespace of all lines after the first is
+    /// stripped, and the first line is removed if it is empty. Shorthand for
+    /// `value_dedented_with(DedentMode::Indoc)`.
+    ///
+    /// ```
+    /// use litrs::StringLit;
+    ///
+    /// let lit = StringLit::parse("\"
+    ///     SELECT *
+    ///       FROM t\\n\\
+    ///     \"").unwrap();
+    /// assert_eq!(lit.value_dedented(), "SELECT *\n  FROM t\n");
+    /// ```
+    pub fn value_dedented(&self) -> String {
+        self.value_dedented_with(DedentMode::Indoc)
+    }
+
+    /// Returns the value with indentation removed as described by `mode`. See
+    /// [`DedentMode`].
+    pub fn value_dedented_with(&self, mode: DedentMode) -> String {
+        String::from_utf8(dedent(self.value().as_bytes(), mode))
+            .expect("bug: dedenting made string invalid UTF-8")
+    }
+
+    /// Returns an iterator over the value of this literal, in chunks taken
+    /// directly from the raw input: verbatim parts and single unescaped
+    /// characters. Concatenating all chunks results in [`value`][Self::value].
+    /// Unlike `value`, this never requires the unescaped value to be stored.
+    /// To not build the unescaped value in the first place, use
+    /// [`parse_chunks`][StringLit::parse_chunks].
+    ///
+    /// ```
+    /// use litrs::{Chunk, StringLit};
+    ///
+    /// let lit = StringLit::parse(r#""foo\nbar\u{1F980}""#).unwrap();
+    /// let chunks = lit.chunks().collect::<Vec<_>>();
+    /// assert_eq!(chunks, [
+    ///     Chunk::Verbatim("foo"),
+    ///     Chunk::Char('\n'),
+    ///     Chunk::Verbatim("bar"),
+    ///     Chunk::Char('🦀'),
+    /// ]);
+    /// ```
+    pub fn chunks(&self) -> Chunks<'_> {
+        let content = &self.raw[self.inner_range()];
+        Chunks::new(RawChunks::new(content, self.is_raw_string(), true, false))
+    }
+
     /// The optional suffix. Returns `""` if the suffix is empty/does not exist.
     pub fn suffix(&self) -> &str {
         &(*self.raw)[self.start_suffix..]
@@ -84,16 +137,85 @@ impl<B: Buffer> StringLit<B> {
         self.raw
     }
 
-    /// The range within `self.raw` that excludes the quotes and potential `r#`.
-    fn inner_range(&self) -> Range<usize> {
-        match self.num_hashes {
-            None => 1..self.start_suffix - 1,
-            Some(n) => 1 + n as usize + 1..self.start_suffix - n as usize - 1,
+    /// Returns a new literal with the given value, keeping the style of this
+    /// literal: raw string literals stay raw (with more `#` if required by
+    /// the value) and the suffix is kept. Non-raw literals escape only quotes,
+    /// backslashes and control characters.
+    ///
+    /// Raw string literals cannot contain `\r`. If the value contains one, a
+    /// non-raw literal is returned.
+    ///
+    /// ```
+    /// use litrs::StringLit;
+    ///
+    /// let lit = StringLit::parse(r#"r"foo""#).unwrap();
+    /// assert_eq!(lit.with_value(r#"say "hi""#).raw_input(), r##"r#"say "hi""#"##);
+    ///
+    /// let lit = StringLit::parse(r#""foo"_x"#).unwrap();
+    /// assert_eq!(lit.with_value("a\nb").raw_input(), r#""a\nb"_x"#);
+    /// ```
+    pub fn with_value(&self, value: &str) -> StringLit<String> {
+        let hashes = self.num_hashes.and_then(|current| {
+            let needed = raw_string_hashes(value)?;
+            let hashes = needed.max(current as usize);
+            if hashes <= u8::MAX as usize { Some(hashes) } else { None }
+        });
+
+        let mut raw = String::with_capacity(value.len() + 2 + self.suffix().len());
+        match hashes {
+            Some(n) => {
+                raw.push('r');
+                raw.extend(std::iter::repeat('#').take(n));
+                raw.push('"');
+                raw.push_str(value);
+                raw.push('"');
+                raw.extend(std::iter::repeat('#').take(n));
+            }
+            None => {
+                raw.push('"');
+                escape_str_into(value, &mut raw);
+                raw.push('"');
+            }
         }
+        raw.push_str(self.suffix());
+
+        StringLit::parse(raw)
+            .unwrap_or_else(|e| unreachable!("bug: `with_value` created invalid literal: {}", e))
+    }
+
+    /// The range within `self.raw` that excludes the quotes and potential `r#`.
+    pub(crate) fn inner_range(&self) -> Range<usize> {
+        inner_range(self.num_hashes, self.start_suffix)
     }
 }
 
-impl StringLit<&str> {
+impl<'a> StringLit<&'a str> {
+    /// Checks that `input` is a valid (raw) string literal exactly like
+    /// [`parse`][Self::parse] and returns an iterator over its value like
+    /// [`chunks`][Self::chunks] does. Unlike `parse`, this never allocates,
+    /// even if the literal contains escapes.
+    ///
+    /// ```
+    /// use litrs::{Chunk, StringLit};
+    ///
+    /// let chunks = StringLit::parse_chunks(r#""a\tb""#).unwrap();
+    /// assert_eq!(chunks.collect::<Vec<_>>(), [
+    ///     Chunk::Verbatim("a"),
+    ///     Chunk::Char('\t'),
+    ///     Chunk::Verbatim("b"),
+    /// ]);
+    /// ```
+    pub fn parse_chunks(input: &'a str) -> Result<Chunks<'a>, ParseError> {
+        match first_byte_or_empty(input)? {
+            b'r' | b'"' => {
+                let (_, num_hashes, start_suffix) = parse_impl::<()>(input)?;
+                let content = &input[inner_range(num_hashes, start_suffix)];
+                Ok(Chunks::new(RawChunks::new(content, num_hashes.is_some(), true, false)))
+            }
+            _ => Err(perr(0, InvalidStringLiteralStart)),
+        }
+    }
+
     /// Makes a copy of the underlying buffer and returns the owned version of
     /// `Self`.
     pub fn into_owned(self) -> StringLit<String> {
@@ -112,18 +234,37 @@ impl<B: Buffer> fmt::Display for StringLit<B> {
     }
 }
 
+/// The range within the raw input that excludes the quotes and potential `r#`.
+fn inner_range(num_hashes: Option<u8>, start_suffix: usize) -> Range<usize> {
+    match num_hashes {
+        None => 1..start_suffix - 1,
+        Some(n) => 1 + n as usize + 1..start_suffix - n as usize - 1,
+    }
+}
+
 /// Precondition: input has to start with either `"` or `r`.
 #[inline(never)]
-pub(crate) fn parse_impl(input: &str) -> Result<(Option<String>, Option<u8>, usize), ParseError> {
+pub(crate) fn parse_impl<C: EscapeContainer>(
+    input: &str,
+) -> Result<(Option<C>, Option<u8>, usize), ParseError> {
     if input.starts_with('r') {
         scan_raw_string(input, 1, true, true)
             .map(|(hashes, start_suffix)| (None, Some(hashes), start_suffix))
     } else {
-        unescape_string::<String>(input, 1, true, false, true)
+        unescape_string::<C>(input, 1, true, false, true)
             .map(|(v, start_suffix)| (v, None, start_suffix))
     }
 }
 
+/// Checks whether `input` is a valid string literal, exactly like
+/// [`StringLit::parse`], but without allocating.
+pub(crate) fn validate(input: &str) -> Result<(), ParseError> {
+    match first_byte_or_empty(input)? {
+        b'r' | b'"' => parse_impl::<()>(input).map(|_| ()),
+        _ => Err(perr(0, InvalidStringLiteralStart)),
+    }
+}
+
 
 #[cfg(test)]
 mod tests;
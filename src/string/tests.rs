@@ -1,6 +1,6 @@
 use crate::{
-    test_util::{assert_parse_ok_eq, assert_roundtrip},
-    Literal, StringLit,
+    test_util::{assert_parse_ok_eq, assert_roundtrip, count_allocations},
+    DedentMode, Literal, StringLit,
 };
 
 // ===== Utility functions =======================================================================
@@ -139,6 +139,10 @@ fn string_continue() {
     let lit = StringLit::parse("\"foo\\\n\u{a0}bar\"").expect("failed to parse");
     assert_eq!(lit.value(), "foo\u{a0}bar");
 
+    // Only a string continue
+    let lit = StringLit::parse("\"\\\n  \"").expect("failed to parse");
+    assert_eq!(lit.value(), "");
+
     // Raw strings do not handle "string continues"
     check!(r"foo\
         bar", false, Some(0));
@@ -260,3 +264,123 @@ fn invalid_unicode_escapes() {
 
     assert_err!(StringLit, r#""\u{110000}fox""#, InvalidUnicodeEscapeChar, 1..11);
 }
+
+#[test]
+fn with_value() {
+    #[track_caller]
+    fn check(input: &str, value: &str, expected: &str) {
+        let lit = StringLit::parse(input).unwrap();
+        let new = lit.with_value(value);
+        assert_eq!(new.raw_input(), expected, "for `{input}` with value {value:?}");
+        assert_eq!(new.value(), value);
+    }
+
+    check(r#""foo""#, "bar", r#""bar""#);
+    check(r#""foo"_x"#, "bar", r#""bar"_x"#);
+    check(r#""foo""#, "a\"b\\c", r#""a\"b\\c""#);
+    check(r#""foo""#, "a\nb\tc\r\0", r#""a\nb\tc\r\0""#);
+    check(r#""foo""#, "bell\u{7}🦊", r#""bell\u{7}🦊""#);
+
+    check(r#"r"foo""#, "bar", r#"r"bar""#);
+    check(r#"r"foo""#, r"\n", r#"r"\n""#);
+    check(r#"r"foo""#, r#"say "hi""#, r##"r#"say "hi""#"##);
+    check(r#"r"foo""#, r###"a"##b"#"###, r####"r###"a"##b"#"###"####);
+    check(r###"r##"foo"##"###, "bar", r###"r##"bar"##"###);
+    check(r###"r##"foo"##suffix"###, "\"#", r###"r##""#"##suffix"###);
+
+    // Raw strings cannot contain `\r`.
+    check(r#"r"foo""#, "a\rb", r#""a\rb""#);
+}
+
+#[test]
+fn chunks() {
+    use crate::Chunk;
+
+    #[track_caller]
+    fn check(input: &str, expected: &[Chunk<'_>]) {
+        let lit = StringLit::parse(input).unwrap();
+        let chunks = lit.chunks().collect::<Vec<_>>();
+        assert_eq!(chunks, expected, "for `{input}`");
+
+        // `parse_chunks` yields the same chunks without allocating
+        let (same, allocations) = count_allocations(|| {
+            StringLit::parse_chunks(input).unwrap().eq(expected.iter().copied())
+        });
+        assert!(same, "`parse_chunks` differs from `chunks` for `{input}`");
+        assert_eq!(allocations, 0, "`parse_chunks` allocated for `{input}`");
+
+        let mut value = String::new();
+        for chunk in chunks {
+            match chunk {
+                Chunk::Verbatim(s) => value.push_str(s),
+                Chunk::Char(c) => value.push(c),
+            }
+        }
+        assert_eq!(value, lit.value());
+    }
+
+    check(r#""""#, &[]);
+    check(r#""foo""#, &[Chunk::Verbatim("foo")]);
+    check(r#""foo"_suffix"#, &[Chunk::Verbatim("foo")]);
+    check(r#""\n""#, &[Chunk::Char('\n')]);
+    check(r#""a\tb\x41\u{1F980}""#, &[
+        Chunk::Verbatim("a"),
+        Chunk::Char('\t'),
+        Chunk::Verbatim("b"),
+        Chunk::Char('A'),
+        Chunk::Char('🦀'),
+    ]);
+    check("\"foo\\\n    bar\\\n\"", &[Chunk::Verbatim("foo"), Chunk::Verbatim("bar")]);
+    check(r#""\"\\""#, &[Chunk::Char('"'), Chunk::Char('\\')]);
+
+    check(r#"r"""#, &[]);
+    check(r#"r"a\nb""#, &[Chunk::Verbatim(r"a\nb")]);
+    check(r##"r#"say "hi""#"##, &[Chunk::Verbatim(r#"say "hi""#)]);
+}
+
+#[test]
+fn parse_chunks() {
+    // `parse` builds the unescaped value, which `parse_chunks` never does (see
+    // `chunks` test). This makes sure allocations are actually counted.
+    let (_, allocations) = count_allocations(|| StringLit::parse(r#""a\nb""#).unwrap());
+    assert!(allocations > 0);
+
+    // Errors are the same as for `parse`
+    for input in ["", "\"abc", r#""\q""#, r#""a"1"#, r#"b"a""#, "r#\"a\""] {
+        let expected = StringLit::parse(input).unwrap_err();
+        let actual = StringLit::parse_chunks(input).unwrap_err();
+        assert_eq!((actual.kind, actual.span), (expected.kind, expected.span), "for {input:?}");
+    }
+}
+
+#[test]
+fn value_dedented() {
+    #[track_caller]
+    fn check(input: &str, indoc: &str, unindent: &str) {
+        let lit = StringLit::parse(input).unwrap();
+        assert_eq!(lit.value_dedented(), indoc, "indoc mode for `{input}`");
+        assert_eq!(lit.value_dedented_with(DedentMode::Indoc), indoc);
+        assert_eq!(lit.value_dedented_with(DedentMode::Unindent), unindent, "for `{input}`");
+    }
+
+    check(r#""""#, "", "");
+    check(r#""abc""#, "abc", "abc");
+    check(r#""  abc""#, "  abc", "abc");
+    check("\"\n\"", "", "\n");
+    check("\"\n    a\n    b\n\"", "a\nb\n", "\na\nb\n");
+    check("\"\n    a\n      b\n    \"", "a\n  b\n", "\na\n  b\n");
+    check("\"  x\n    a\n      b\"", "  x\na\n  b", "x\n  a\n    b");
+
+    // Tabs count as one character, whitespace-only lines do not count.
+    check("\"\n\t\ta\n\t\t\tb\"", "a\n\tb", "\na\n\tb");
+    check("\"\n    a\n\n  \n      \n    b\"", "a\n\n\n  \nb", "\na\n\n\n  \nb");
+
+    // Escapes and string continues are processed first.
+    check(r#""\n    a\n    b""#, "a\nb", "\na\nb");
+    check(r#""\n    \x20 a\n    b""#, "  a\nb", "\n  a\nb");
+    check("\"\n    a \\\n    b\n    c\"", "a b\nc", "\na b\nc");
+    check(r#""\r\n    a\r\n    b""#, "a\r\nb", "\r\n    a\r\n    b");
+
+    // Raw strings
+    check("r#\"\n    \"a\"\n    b\n\"#", "\"a\"\nb\n", "\n\"a\"\nb\n");
+}
@@ -0,0 +1,260 @@
+use std::ops::Range;
+
+use crate::{err::ParseErrorKind, ParseError};
+
+
+/// A suggested fix for a [`ParseError`]: replace a range of the input by a
+/// new text. Obtained via [`ParseError::suggestions`].
+///
+/// ```
+/// use litrs::{Applicability, Literal};
+///
+/// let input = r#""a\x80b""#;
+/// let e = Literal::parse(input).unwrap_err();
+/// let suggestions = e.suggestions(input);
+///
+/// assert_eq!(suggestions[0].range(), 2..6);
+/// assert_eq!(suggestions[0].replacement(), r"\u{80}");
+/// assert_eq!(suggestions[0].applicability(), Applicability::MachineApplicable);
+/// assert_eq!(suggestions[0].apply(input), r#""a\u{80}b""#);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    range: Range<usize>,
+    replacement: String,
+    applicability: Applicability,
+    message: &'static str,
+}
+
+/// How confident we are that a [`Suggestion`] is what the user wants. Same
+/// meaning as in `rustc`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Applicability {
+    /// The suggestion is definitely what the user intended and can be applied
+    /// automatically.
+    MachineApplicable,
+
+    /// The suggestion results in a valid literal, but might not be what the
+    /// user intended, e.g. turning `'ab'` into `"ab"`.
+    MaybeIncorrect,
+
+    /// The suggestion contains placeholders like `...` that the user has to
+    /// fill in.
+    HasPlaceholders,
+}
+
+impl Suggestion {
+    fn new(
+        range: Range<usize>,
+        replacement: impl Into<String>,
+        applicability: Applicability,
+        message: &'static str,
+    ) -> Self {
+        Self { range, replacement: replacement.into(), applicability, message }
+    }
+
+    /// The byte range of the input that should be replaced. Empty for
+    /// insertions.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// The text that should replace the range.
+    pub fn replacement(&self) -> &str {
+        &self.replacement
+    }
+
+    /// How confident we are about this suggestion.
+    pub fn applicability(&self) -> Applicability {
+        self.applicability
+    }
+
+    /// A short description of the suggestion, e.g. "add the closing quote".
+    pub fn message(&self) -> &'static str {
+        self.message
+    }
+
+    /// Returns `source` with this suggestion applied. `source` has to be the
+    /// same string that was passed to [`ParseError::suggestions`].
+    pub fn apply(&self, source: &str) -> String {
+        let mut out = source.to_owned();
+        out.replace_range(self.range(), &self.replacement);
+        out
+    }
+}
+
+impl ParseError {
+    /// Returns suggestions to fix this error. `source` has to be the string
+    /// that was passed to the `parse` function, the ranges of the returned
+    /// suggestions refer to it. Returns an empty vector if there is no
+    /// suggestion for this error.
+    ///
+    /// ```
+    /// use litrs::Literal;
+    ///
+    /// let fix = |input: &str| {
+    ///     let e = Literal::parse(input).unwrap_err();
+    ///     e.suggestions(input)[0].apply(input)
+    /// };
+    ///
+    /// assert_eq!(fix("1.e3"), "1.0e3");
+    /// assert_eq!(fix("'ab'"), r#""ab""#);
+    /// assert_eq!(fix(r##"r#"foo"##), r##"r#"foo"#"##);
+    /// assert_eq!(fix(r"'\u41'"), r"'\u{41}'");
+    /// ```
+    pub fn suggestions(&self, source: &str) -> Vec<Suggestion> {
+        // The span might not fit the source if the wrong source was passed.
+        let span = match &self.span {
+            Some(span) if source.get(span.clone()).is_some() => Some(span.clone()),
+            Some(_) => return vec![],
+            None => None,
+        };
+
+        suggestion(self.kind, span, source).into_iter().collect()
+    }
+}
+
+fn suggestion(
+    kind: ParseErrorKind,
+    span: Option<Range<usize>>,
+    source: &str,
+) -> Option<Suggestion> {
+    use self::Applicability::*;
+    use ParseErrorKind::*;
+
+    let bytes = source.as_bytes();
+    let s = match kind {
+        NonAsciiXEscape => {
+            let span = span?;
+            let digits = source[span.clone()].strip_prefix(r"\x")?;
+            let replacement = format!("\\u{{{}}}", digits);
+            Suggestion::new(span, replacement, MachineApplicable, r"use a `\u{...}` escape")
+        }
+
+        OverlongCharLiteral | OverlongByteLiteral => {
+            // Turn it into a (byte) string literal. `"` have to be escaped
+            // then, escaped `'` can stay as they are. The suffix is kept.
+            let start = source.find('\'')?;
+            let end = source.rfind('\'')?;
+            if start == end {
+                return None;
+            }
+
+            let mut replacement = source[..start].to_owned();
+            replacement.push('"');
+            let mut chars = source[start + 1..end].chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => {
+                        replacement.push('\\');
+                        replacement.extend(chars.next());
+                    }
+                    '"' => replacement.push_str("\\\""),
+                    c => replacement.push(c),
+                }
+            }
+            replacement.push('"');
+
+            let message = if kind == OverlongCharLiteral {
+                "if you meant to write a string literal, use double quotes"
+            } else {
+                "if you meant to write a byte string literal, use double quotes"
+            };
+            Suggestion::new(0..end + 1, replacement, MaybeIncorrect, message)
+        }
+
+        InvalidDigit => {
+            // Switch to the smallest base that allows all digits.
+            let prefix = source.get(..2)?;
+            let max_digit = source[2..].bytes()
+                .take_while(|b| b.is_ascii_digit() || *b == b'_')
+                .filter(|b| b.is_ascii_digit())
+                .max()?;
+            let replacement = match (prefix, max_digit) {
+                ("0b", b'0'..=b'7') => "0o",
+                ("0b", _) | ("0o", _) => "",
+                _ => return None,
+            };
+            Suggestion::new(0..2, replacement, MaybeIncorrect, "change the base of the literal")
+        }
+
+        UnicodeEscapeWithoutBrace => {
+            // This is most likely a `\uXXXX` escape from another language,
+            // which always has four digits.
+            let span = span?;
+            let num_digits = bytes[span.end..].iter()
+                .take(4)
+                .take_while(|b| b.is_ascii_hexdigit())
+                .count();
+            let message = r"add braces around the digits of the `\u{...}` escape";
+            let applicability = match num_digits {
+                0 => return Some(Suggestion::new(span, r"\u{...}", HasPlaceholders, message)),
+                4 => MachineApplicable,
+                _ => MaybeIncorrect,
+            };
+            let end = span.end + num_digits;
+            let replacement = format!("\\u{{{}}}", &source[span.end..end]);
+            Suggestion::new(span.start..end, replacement, applicability, message)
+        }
+
+        UnterminatedUnicodeEscape => {
+            let span = span?;
+            let start = span.start + 3;
+            let end = start + bytes.get(start..)?.iter()
+                .take_while(|b| b.is_ascii_hexdigit() || **b == b'_')
+                .count();
+            if end == start {
+                return None;
+            }
+            Suggestion::new(end..end, "}", MachineApplicable, "add the closing brace")
+        }
+
+        UnterminatedRawString => {
+            // Check the number of hashes after the `r`.
+            let r = source.find('r')?;
+            let num_hashes = bytes[r + 1..].iter().take_while(|b| **b == b'#').count();
+            let open = r + 1 + num_hashes;
+
+            // If the literal ends in `"` followed by too few hashes, only the
+            // hashes are missing. But the `"` might also be part of the
+            // content.
+            let trailing_hashes = bytes.iter().rev().take_while(|b| **b == b'#').count();
+            let before_hashes = source.len() - trailing_hashes;
+            let end = source.len()..source.len();
+            if before_hashes > open + 1 && bytes[before_hashes - 1] == b'"' {
+                let missing = "#".repeat(num_hashes.checked_sub(trailing_hashes)?);
+                Suggestion::new(end, missing, MaybeIncorrect, "add the missing `#` symbols")
+            } else {
+                let closing = format!("\"{}", "#".repeat(num_hashes));
+                Suggestion::new(end, closing, MachineApplicable, "add the closing delimiter")
+            }
+        }
+
+        UnterminatedString => {
+            let end = source.len()..source.len();
+            Suggestion::new(end, "\"", MachineApplicable, "add the closing quote")
+        }
+
+        UnexpectedChar => {
+            // `1.e3` is not valid, but `1.0e3` is.
+            let span = span?;
+            let after_period = span.start > 0 && bytes[span.start - 1] == b'.';
+            if !after_period || !matches!(bytes[span.start], b'e' | b'E') {
+                return None;
+            }
+            let at = span.start..span.start;
+            Suggestion::new(at, "0", MachineApplicable, "add a `0` after the decimal point")
+        }
+
+        UnescapedSingleQuote => {
+            let span = span?;
+            let replacement = format!("\\{}", &source[span.clone()]);
+            Suggestion::new(span, replacement, MachineApplicable, "escape the quote")
+        }
+
+        _ => return None,
+    };
+
+    Some(s)
+}
@@ -1,8 +1,42 @@
-use std::fmt::{Debug, Display};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    fmt::{Debug, Display},
+};
 
 use crate::*;
 
 
+/// Allocator for the test binary that counts the allocations of each thread,
+/// to check that certain code paths don't allocate.
+struct CountingAlloc;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// Runs `f` and returns its result and the number of allocations it made.
+pub(crate) fn count_allocations<R>(f: impl FnOnce() -> R) -> (R, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let out = f();
+    (out, ALLOCATIONS.with(Cell::get) - before)
+}
+
+
 #[track_caller]
 pub(crate) fn assert_parse_ok_eq<T: PartialEq + Debug + Display>(
     input: &str,
@@ -37,7 +71,7 @@ pub(crate) fn assert_roundtrip<T>(_: T, _: &str) {}
 #[track_caller]
 pub(crate) fn assert_roundtrip<T>(ours: T, input: &str)
 where
-    T: std::convert::TryFrom<proc_macro2::Literal> + fmt::Debug + PartialEq + Clone,
+    T: std::convert::TryFrom<proc_macro2::Literal> + Debug + PartialEq + Clone,
     proc_macro2::Literal: From<T>,
     <T as std::convert::TryFrom<proc_macro2::Literal>>::Error: std::fmt::Display,
 {
@@ -37,6 +37,108 @@ fn misc() {
     assert_err_single!(Literal::parse("B_123"), InvalidLiteral, None);
 }
 
+#[test]
+fn parse_with_default_options() {
+    use crate::ParseOptions;
+
+    let opts = ParseOptions::new();
+    for input in ["27", "23px", "256u8", "1.0u32", r#""foo"bar"#, "'a'x", "b'a'_y", "true"] {
+        assert_eq!(Literal::parse_with(input, &opts).unwrap(), Literal::parse(input).unwrap());
+    }
+}
+
+#[test]
+fn parse_with_strict() {
+    use crate::ParseOptions;
+
+    let opts = ParseOptions::new().strict(true);
+    let ok = [
+        "0", "27", "27u8", "255u8", "127i8", "0xFFu8", "0b1111_1111u8", "3f32", "3_000f64",
+        "340282366920938463463374607431768211455", "18446744073709551615usize",
+        "3.14", "1e3", "2.5f32", "1e38f32", "1e308", "1e-400", "1.5e1_0f64",
+        "1.0f16", "65504f16", "2.5f128", "1e4000f128",
+        "'a'", "b'a'", r#""foo""#, r##"r#"foo"#"##, r#"b"foo""#, r#"c"foo""#,
+        "true", "false",
+    ];
+    for input in ok {
+        if let Err(e) = Literal::parse_with(input, &opts) {
+            panic!("expected `{input}` to be accepted in strict mode, but got: {e}");
+        }
+    }
+
+    assert_err_single!(Literal::parse_with("23px", &opts), DisallowedSuffix, 2..4);
+    assert_err_single!(Literal::parse_with("1.0u32", &opts), DisallowedSuffix, 3..6);
+    assert_err_single!(Literal::parse_with("1e3i8", &opts), DisallowedSuffix, 3..5);
+    assert_err_single!(Literal::parse_with("0b1f32", &opts), DisallowedSuffix, 3..6);
+    assert_err_single!(Literal::parse_with("0o7f64", &opts), DisallowedSuffix, 3..6);
+    assert_err_single!(Literal::parse_with(r#""foo"bar"#, &opts), DisallowedSuffix, 5..8);
+    assert_err_single!(Literal::parse_with(r#""foo"u8"#, &opts), DisallowedSuffix, 5..7);
+    assert_err_single!(Literal::parse_with("'a'x", &opts), DisallowedSuffix, 3..4);
+    assert_err_single!(Literal::parse_with("b'a'_y", &opts), DisallowedSuffix, 4..6);
+    assert_err_single!(Literal::parse_with(r#"b"a"f32"#, &opts), DisallowedSuffix, 4..7);
+    assert_err_single!(Literal::parse_with(r#"c"a"suffix"#, &opts), DisallowedSuffix, 4..10);
+
+    assert_err_single!(Literal::parse_with("256u8", &opts), IntegerOutOfRange, 0..5);
+    assert_err_single!(Literal::parse_with("128i8", &opts), IntegerOutOfRange, 0..5);
+    assert_err_single!(Literal::parse_with("0x1_0000u16", &opts), IntegerOutOfRange, 0..11);
+    assert_err_single!(
+        Literal::parse_with("340282366920938463463374607431768211456", &opts),
+        IntegerOutOfRange,
+        0..39
+    );
+    assert_err_single!(Literal::parse_with("1e39f32", &opts), FloatOutOfRange, 0..7);
+    assert_err_single!(Literal::parse_with("1e309", &opts), FloatOutOfRange, 0..5);
+    assert_err_single!(Literal::parse_with("1e309f64", &opts), FloatOutOfRange, 0..8);
+    assert_err_single!(Literal::parse_with("7e4f16", &opts), FloatOutOfRange, 0..6);
+    assert_err_single!(Literal::parse_with("70000f16", &opts), FloatOutOfRange, 0..8);
+    assert_err_single!(Literal::parse_with("1e5000f128", &opts), FloatOutOfRange, 0..10);
+    assert_err_single!(
+        Literal::parse_with("1000000000000000000000000000000000000000f32", &opts),
+        FloatOutOfRange,
+        0..43
+    );
+}
+
+#[test]
+fn parse_with_custom_suffixes() {
+    use crate::ParseOptions;
+
+    // Allow list in strict mode.
+    let opts = ParseOptions::new().strict(true).allow_suffix("px").allow_suffix("pt");
+    assert!(Literal::parse_with("16px", &opts).is_ok());
+    assert!(Literal::parse_with("1.5pt", &opts).is_ok());
+    assert!(Literal::parse_with(r#""a"px"#, &opts).is_ok());
+    assert!(Literal::parse_with("16u8", &opts).is_ok());
+    assert_err_single!(Literal::parse_with("3cm", &opts), DisallowedSuffix, 1..3);
+    assert_err_single!(Literal::parse_with("256u8", &opts), IntegerOutOfRange, 0..5);
+
+    // Allow list without strict mode: type suffixes are still fine and no
+    // range checks are performed.
+    let opts = ParseOptions::new().allow_suffix("px");
+    assert!(Literal::parse_with("16px", &opts).is_ok());
+    assert!(Literal::parse_with("256u8", &opts).is_ok());
+    assert!(Literal::parse_with("1.0f32", &opts).is_ok());
+    assert_err_single!(Literal::parse_with("1.0u32", &opts), DisallowedSuffix, 3..6);
+    assert_err_single!(Literal::parse_with(r#""a"pt"#, &opts), DisallowedSuffix, 3..5);
+
+    // Predicate.
+    let opts = ParseOptions::new().suffix_predicate(|s| s.starts_with('_'));
+    assert!(Literal::parse_with("'a'_foo", &opts).is_ok());
+    assert!(Literal::parse_with("3u8", &opts).is_ok());
+    assert_err_single!(Literal::parse_with("'a'foo", &opts), DisallowedSuffix, 3..6);
+
+    let opts = ParseOptions::new()
+        .strict(true)
+        .allow_suffix("kb")
+        .suffix_predicate(|s| s == "mb");
+    assert!(Literal::parse_with("3kb", &opts).is_ok());
+    assert!(Literal::parse_with("3mb", &opts).is_ok());
+    assert_err_single!(Literal::parse_with("3gb", &opts), DisallowedSuffix, 1..3);
+
+    // The normal suffix grammar check still happens first.
+    assert_err_single!(Literal::parse_with("3k-b", &opts), InvalidSuffix, 1..4);
+}
+
 macro_rules! assert_no_panic {
     ($input:expr) => {
         let arr = $input;
@@ -98,14 +200,15 @@ fn proc_macro() {
     };
 
     use crate::{
-        err::TokenKind, BoolLit, ByteLit, ByteStringLit, CharLit, FloatLit, IntegerLit, StringLit,
+        err::{Expected, TokenKind},
+        BoolLit, ByteLit, ByteStringLit, CharLit, FloatLit, IntegerLit, StringLit,
     };
 
 
     macro_rules! assert_invalid_token {
         ($input:expr, expected: $expected:path, actual: $actual:path $(,)?) => {
             let err = $input.unwrap_err();
-            if err.expected != $expected {
+            if err.expected != Expected::from($expected) {
                 panic!(
                     "err.expected was expected to be {:?}, but is {:?}",
                     $expected, err.expected,
@@ -329,27 +432,473 @@ fn bool_try_from_tt() {
     assert!(Literal::try_from(TokenTree::Ident(ident("ltrue"))).is_err());
 }
 
+#[cfg(feature = "proc-macro2")]
+#[test]
+fn invisible_groups() {
+    use std::convert::TryFrom;
+
+    use proc_macro2::{Delimiter, Group, Ident, Punct, Spacing, Span, TokenStream, TokenTree};
+
+    use crate::{err::TokenKind, BoolLit, IntegerLit, StringLit};
+
+
+    let invisible = |tokens: Vec<TokenTree>| {
+        let stream = tokens.into_iter().collect::<TokenStream>();
+        TokenTree::from(Group::new(Delimiter::None, stream))
+    };
+    let lit = || TokenTree::from(proc_macro2::Literal::u8_unsuffixed(27));
+    let minus = || TokenTree::from(Punct::new('-', Spacing::Alone));
+
+    // Groups containing a single token are transparent.
+    let group = invisible(vec![lit()]);
+    assert_eq!(IntegerLit::try_from(&group).unwrap().value::<u8>(), Some(27));
+    assert_eq!(Literal::try_from(group.clone()).unwrap(), Literal::parse("27".to_owned()).unwrap());
+    assert_eq!(
+        BoolLit::try_from(invisible(vec![Ident::new("true", Span::call_site()).into()])).unwrap(),
+        BoolLit::True,
+    );
+    assert_eq!(IntegerLit::try_from(invisible(vec![group])).unwrap().value::<u8>(), Some(27));
+
+    let e = StringLit::try_from(invisible(vec![lit()])).unwrap_err();
+    assert_eq!((e.expected, e.actual), (TokenKind::StringLit.into(), TokenKind::IntegerLit));
+
+    // Other groups are not.
+    let e = Literal::try_from(invisible(vec![])).unwrap_err();
+    assert_eq!((e.expected, e.actual), (TokenKind::Literal.into(), TokenKind::Group));
+    let e = Literal::try_from(invisible(vec![minus(), lit()])).unwrap_err();
+    assert_eq!((e.expected, e.actual), (TokenKind::Literal.into(), TokenKind::Group));
+    let paren = TokenTree::from(Group::new(Delimiter::Parenthesis, lit().into()));
+    let e = IntegerLit::try_from(paren).unwrap_err();
+    assert_eq!((e.expected, e.actual), (TokenKind::IntegerLit.into(), TokenKind::Group));
+}
+
+#[cfg(feature = "proc-macro2")]
+#[test]
+fn try_from_token_stream() {
+    use std::convert::TryFrom;
+
+    use proc_macro2::TokenStream;
+
+    use crate::{err::TokenKind, BoolLit, CharLit, StringLit};
+
+    let stream = |s: &str| s.parse::<TokenStream>().unwrap();
+
+    assert_eq!(StringLit::try_from(stream(r#""foo""#)).unwrap().value(), "foo");
+    assert_eq!(CharLit::try_from(stream("'x'")).unwrap().value(), 'x');
+    assert_eq!(BoolLit::try_from(stream("false")).unwrap(), BoolLit::False);
+    assert!(matches!(Literal::try_from(stream("3.0")).unwrap(), Literal::Float(_)));
+
+    let e = Literal::try_from(stream("")).unwrap_err();
+    assert_eq!((e.expected, e.actual), (TokenKind::Literal.into(), TokenKind::End));
+    assert_eq!(e.to_string(), "expected a literal, but found the end of input");
+
+    let e = Literal::try_from(stream("1 2")).unwrap_err();
+    assert_eq!((e.expected, e.actual), (TokenKind::End.into(), TokenKind::IntegerLit));
+    let e = StringLit::try_from(stream(r#""a", true"#)).unwrap_err();
+    assert_eq!((e.expected, e.actual), (TokenKind::End.into(), TokenKind::Punct));
+    assert_eq!(e.to_string(), "expected the end of input, but found a punctuation character");
+    let e = Literal::try_from(stream("-1")).unwrap_err();
+    assert_eq!((e.expected, e.actual), (TokenKind::End.into(), TokenKind::IntegerLit));
+    let e = CharLit::try_from(stream("foo")).unwrap_err();
+    assert_eq!((e.expected, e.actual), (TokenKind::CharLit.into(), TokenKind::Ident));
+}
+
+#[cfg(feature = "proc-macro2")]
+#[test]
+fn spanned() {
+    use std::convert::TryFrom;
+
+    use proc_macro2::{Delimiter, Group, Ident, Span, TokenStream, TokenTree};
+
+    use crate::{err::TokenKind, BoolLit, IntegerLit, Spanned, StringLit};
+
+
+    let pm_lit = proc_macro2::Literal::string("fsat");
+    let tt = TokenTree::from(pm_lit.clone());
+
+    let lit = Spanned::<StringLit<String>>::try_from(&tt).unwrap();
+    assert_eq!(lit.value(), "fsat");
+    assert_eq!(lit.inner().suffix(), "");
+    assert_eq!(lit.to_string(), r#""fsat""#);
+    assert_eq!(Spanned::<StringLit<String>>::try_from(tt.clone()).unwrap().value(), "fsat");
+    assert_eq!(Spanned::<StringLit<String>>::try_from(&pm_lit).unwrap().value(), "fsat");
+    assert!(matches!(*Spanned::<Literal<String>>::from(pm_lit), Literal::String(_)));
+
+    let error = lit.compile_error2(format!("unknown mode '{}'", lit.value())).to_string();
+    assert!(error.starts_with("compile_error !"), "{error}");
+    assert!(error.contains("unknown mode 'fsat'"), "{error}");
+
+    let len = lit.clone().map(|l| l.value().len());
+    assert_eq!(*len, 4);
+    assert_eq!(len.into_inner(), 4);
+    assert_eq!(lit.as_ref().inner().value(), "fsat");
+    let _ = Spanned::new2(3, Span::call_site()).span2();
+
+    let e = Spanned::<IntegerLit<String>>::try_from(tt).unwrap_err();
+    assert_eq!((e.expected, e.actual), (TokenKind::IntegerLit.into(), TokenKind::StringLit));
+
+    let ident = TokenTree::from(Ident::new("true", Span::call_site()));
+    let group = TokenTree::from(Group::new(Delimiter::None, ident.into()));
+    assert_eq!(*Spanned::<BoolLit>::try_from(group).unwrap(), BoolLit::True);
+
+    let stream = |s: &str| s.parse::<TokenStream>().unwrap();
+    assert_eq!(Spanned::<IntegerLit<String>>::try_from(stream("7")).unwrap().suffix(), "");
+    let e = Spanned::<Literal<String>>::try_from(stream("7 8")).unwrap_err();
+    assert_eq!((e.expected, e.actual), (TokenKind::End.into(), TokenKind::IntegerLit));
+    let e = Spanned::<Literal<String>>::try_from(stream("")).unwrap_err();
+    assert_eq!((e.expected, e.actual), (TokenKind::Literal.into(), TokenKind::End));
+}
+
 #[cfg(feature = "proc-macro2")]
 #[test]
 fn invalid_token_display() {
-    use crate::{err::TokenKind, InvalidToken};
+    use proc_macro2::Span;
 
-    let span = crate::err::Span::Two(proc_macro2::Span::call_site());
+    use crate::{Expected, InvalidToken, TokenKind};
+
+    let span = Span::call_site();
     assert_eq!(
-        InvalidToken {
-            actual: TokenKind::StringLit,
-            expected: TokenKind::FloatLit,
-            span,
-        }.to_string(),
+        InvalidToken::new2(TokenKind::FloatLit, TokenKind::StringLit, span).to_string(),
         r#"expected a float literal (e.g. `3.14`), but found a string literal (e.g. "Ferris")"#,
     );
 
     assert_eq!(
-        InvalidToken {
-            actual: TokenKind::Punct,
-            expected: TokenKind::Literal,
-            span,
-        }.to_string(),
+        InvalidToken::new2(TokenKind::Literal, TokenKind::Punct, span).to_string(),
         r#"expected a literal, but found a punctuation character"#,
     );
+
+    let expected = &[TokenKind::StringLit, TokenKind::IntegerLit];
+    let e = InvalidToken::new2(expected, TokenKind::Group, span);
+    assert_eq!(e.to_string(), "expected a string or integer literal, but found a group");
+    assert_eq!(e.expected(), Expected::OneOf(&[TokenKind::StringLit, TokenKind::IntegerLit]));
+    assert_eq!(e.actual(), TokenKind::Group);
+
+    let check = |expected: &'static [TokenKind], s: &str| {
+        let e = InvalidToken::new2(expected, TokenKind::End, span);
+        assert_eq!(e.to_string(), format!("expected {s}, but found the end of input"));
+    };
+    check(&[], "nothing");
+    check(&[TokenKind::CharLit], "a character literal (e.g. `'P'`)");
+    check(
+        &[TokenKind::IntegerLit, TokenKind::FloatLit, TokenKind::ByteStringLit],
+        "an integer, float or byte string literal",
+    );
+    check(
+        &[TokenKind::Ident, TokenKind::IntegerLit, TokenKind::Group],
+        "an identifier, an integer literal or a group",
+    );
+
+    let e = InvalidToken::new2(Expected::Custom("a type"), TokenKind::IntegerLit, span);
+    assert_eq!(e.to_string(), "expected a type, but found an integer literal (e.g. `27`)");
+    let stream = e.to_compile_error2().to_string();
+    assert!(stream.contains("expected a type"), "{stream}");
+
+    // Token kinds of token trees
+    let stream: proc_macro2::TokenStream = "foo false 'x' (1) +".parse().unwrap();
+    let kinds = stream.into_iter().map(|tt| TokenKind::from(&tt)).collect::<Vec<_>>();
+    assert_eq!(kinds, [
+        TokenKind::Ident,
+        TokenKind::BoolLit,
+        TokenKind::CharLit,
+        TokenKind::Group,
+        TokenKind::Punct,
+    ]);
+}
+
+#[test]
+fn render() {
+    use crate::{ParseError, RenderOptions, StringLit};
+
+    let e = Literal::parse("0b102").unwrap_err();
+    assert_eq!(e.render("0b102"), "\
+error[LIT0005]: integer literal contains a digit invalid for its base
+  |
+1 | 0b102
+  |     ^
+  |
+  = help: binary literals only contain `0` and `1`, octal literals only `0` to `7`");
+
+    // Errors without span only render the header and help.
+    assert_eq!(Literal::parse("").unwrap_err().render(""), "error[LIT0001]: input is empty");
+    assert_eq!(
+        Literal::parse("'ab'").unwrap_err().render("'ab'"),
+        "error[LIT0020]: character literal contains more than one character\n  \
+            = help: if you meant to write a string literal, use double quotes",
+    );
+
+    // Literal inside a larger source, with tabs.
+    let source = "foo(\n  \"a\n\tb\\q\",\n)";
+    let e = Literal::parse(&source[7..15]).unwrap_err();
+    let rendered = e.render_with(source, &RenderOptions::new().offset(7).path("lib.rs"));
+    assert_eq!(rendered.lines().take(5).collect::<Vec<_>>(), [
+        "error[LIT0008]: unknown escape",
+        " --> lib.rs:3:3",
+        "  |",
+        "3 |     b\\q\",",
+        "  |      ^^",
+    ]);
+
+    // Colors
+    let colored = e.render_with(source, &RenderOptions::new().offset(7).color(true));
+    assert!(colored.starts_with("\x1b[1;31merror[LIT0008]\x1b[0m\x1b[1m: unknown escape\x1b[0m"));
+    assert!(colored.contains("\x1b[1;31m^^\x1b[0m"));
+
+    // Spans out of bounds of the wrong source are clamped.
+    let e: ParseError = Literal::parse(r#""abc\q""#).unwrap_err();
+    assert!(e.render("x").starts_with("error[LIT0008]: unknown escape\n  |\n1 | x\n  |  ^"));
+
+    // Spans starting in a CRLF line break point at its end.
+    let e = StringLit::parse("\"\\q\"").unwrap_err();
+    let rendered = e.render_with("ab\r\nXYZ", &RenderOptions::new().offset(2));
+    assert_eq!(rendered.lines().take(6).collect::<Vec<_>>(), [
+        "error[LIT0008]: unknown escape",
+        "  |",
+        "1 | ab",
+        "  |   ^",
+        "2 | XYZ",
+        "  | ^",
+    ]);
+}
+
+#[test]
+fn error_codes() {
+    use crate::explain::ALL_KINDS;
+
+    // Codes are unique, stable and sorted.
+    for (i, kind) in ALL_KINDS.iter().enumerate() {
+        assert_eq!(kind.code(), format!("LIT{:04}", i + 1));
+        let explanation = crate::explain(kind.code()).unwrap();
+        assert!(explanation.contains("Erroneous example"), "{}", kind.code());
+    }
+    assert_eq!(crate::explain("LIT0000"), None);
+    assert_eq!(crate::explain("lit0001"), None);
+    assert_eq!(crate::explain("E0001"), None);
+
+    // Make sure the erroneous examples actually produce that error. Examples
+    // that are not simply literals are skipped.
+    for kind in ALL_KINDS {
+        let explanation = crate::explain(kind.code()).unwrap();
+        let bad = explanation.split("```\n").nth(1).unwrap();
+        for line in bad.lines() {
+            if line.contains("::parse(") || line.contains('<') || line.contains("...") {
+                continue;
+            }
+
+            let options = crate::ParseOptions::new().strict(true);
+            let code = Literal::parse_with(line, &options).map(|_| ()).map_err(|e| e.code());
+            assert_eq!(code, Err(kind.code()), "wrong error for `{line}` ({kind:?})");
+        }
+    }
+}
+
+#[test]
+fn suggestions() {
+    use crate::Applicability::{self, *};
+
+    #[track_caller]
+    fn check(input: &str, fixed: &str, applicability: Applicability) {
+        let e = Literal::parse(input).unwrap_err();
+        let suggestions = e.suggestions(input);
+        assert_eq!(suggestions.len(), 1, "suggestions for `{input}`: {suggestions:?}");
+        assert_eq!(suggestions[0].apply(input), fixed, "wrong fix for `{input}`");
+        assert_eq!(suggestions[0].applicability(), applicability, "for `{input}`");
+        if applicability != HasPlaceholders {
+            assert!(Literal::parse(fixed).is_ok(), "fix `{fixed}` is not valid");
+        }
+    }
+
+    check(r#""a\x80b""#, r#""a\u{80}b""#, MachineApplicable);
+    check(r"'\xFF'", r"'\u{FF}'", MachineApplicable);
+
+    check("'ab'", r#""ab""#, MaybeIncorrect);
+    check("'ab'x", r#""ab"x"#, MaybeIncorrect);
+    check(r#"'a"\'b'"#, r#""a\"\'b""#, MaybeIncorrect);
+    check("b'ab'", r#"b"ab""#, MaybeIncorrect);
+
+    check("0b102", "0o102", MaybeIncorrect);
+    check("0b1_9u8", "1_9u8", MaybeIncorrect);
+    check("0o78", "78", MaybeIncorrect);
+
+    check(r"'\u41'", r"'\u{41}'", MaybeIncorrect);
+    check(r#""\u00e9z""#, r#""\u{00e9}z""#, MachineApplicable);
+    check(r#""\u1F980""#, r#""\u{1F98}0""#, MachineApplicable);
+    check(r#""\u""#, r#""\u{...}""#, HasPlaceholders);
+    check(r#""\u{41""#, r#""\u{41}""#, MachineApplicable);
+
+    check(r##"r#"foo"##, r##"r#"foo"#"##, MachineApplicable);
+    check(r##"br"foo"##, r##"br"foo""##, MachineApplicable);
+    check(r###"r##"foo"#"###, r###"r##"foo"##"###, MaybeIncorrect);
+    check(r##"r#"foo""##, r##"r#"foo"#"##, MaybeIncorrect);
+    check(r#""abc"#, r#""abc""#, MachineApplicable);
+    check(r#"b"abc"#, r#"b"abc""#, MachineApplicable);
+
+    check("1.e3", "1.0e3", MachineApplicable);
+    check("1.E3f32", "1.0E3f32", MachineApplicable);
+    check("'''", r"'\''", MachineApplicable);
+
+    // No suggestions
+    for input in ["", "1.x", r#""\q""#, r#"c"\0""#, "'a"] {
+        let e = Literal::parse(input).unwrap_err();
+        assert_eq!(e.suggestions(input), [], "for `{input}`");
+    }
+
+    // Wrong source
+    let e = Literal::parse(r#""a\x80b""#).unwrap_err();
+    assert_eq!(e.suggestions("x"), []);
+}
+
+#[test]
+fn parse_lenient() {
+    use crate::RepairKind::{self, *};
+
+    #[track_caller]
+    fn check(input: &str, raw: &str, repairs: &[(RepairKind, &str, &str)]) {
+        let lit = Literal::parse_lenient(input).unwrap();
+        assert_eq!(lit.raw(), raw, "for `{input}`");
+        assert_eq!(*lit.literal(), Literal::parse(raw.to_owned()).unwrap());
+        let actual = lit.warnings().iter()
+            .map(|w| (w.kind(), w.original(), w.replacement()))
+            .collect::<Vec<_>>();
+        assert_eq!(actual, repairs, "for `{input}`");
+    }
+
+    // Valid literals are not changed.
+    check("27", "27", &[]);
+    check("0", "0", &[]);
+    check("0u8", "0u8", &[]);
+    check(r#""a\x7F""#, r#""a\x7F""#, &[]);
+    check("'a'", "'a'", &[]);
+
+    // C escapes
+    check(r#""\a\b\f\v\e\?""#, r#""\x07\x08\x0C\x0B\x1B?""#, &[
+        (CEscape, r"\a", r"\x07"),
+        (CEscape, r"\b", r"\x08"),
+        (CEscape, r"\f", r"\x0C"),
+        (CEscape, r"\v", r"\x0B"),
+        (CEscape, r"\e", r"\x1B"),
+        (CEscape, r"\?", "?"),
+    ]);
+    check(r#""caf\u00e9""#, r#""caf\u{00e9}""#, &[(CEscape, r"\u00e9", r"\u{00e9}")]);
+    check(r"'\U0001F980'", r"'\u{1F980}'", &[(CEscape, r"\U0001F980", r"\u{1F980}")]);
+    check(r#"b"\a""#, r#"b"\x07""#, &[(CEscape, r"\a", r"\x07")]);
+
+    // Integers
+    check("0755", "0o755", &[(LeadingZeroOctal, "0755", "0o755")]);
+    check("0_644u16", "0o_644u16", &[(LeadingZeroOctal, "0_644", "0o_644")]);
+    check("0X1F", "0x1F", &[(UppercasePrefix, "0X", "0x")]);
+    check("0B101", "0b101", &[(UppercasePrefix, "0B", "0b")]);
+    check("0O17u8", "0o17u8", &[(UppercasePrefix, "0O", "0o")]);
+    check("089", "089", &[]);
+    check("0Xyz", "0Xyz", &[]);
+
+    // Quotes
+    check(r#""abc"#, r#""abc""#, &[(MissingClosingQuote, "", "\"")]);
+    check(r##"r#"abc"##, r##"r#"abc"#"##, &[(MissingClosingQuote, "", "\"#")]);
+    check("'a", "'a'", &[(MissingClosingQuote, "", "'")]);
+    check("b'a", "b'a'", &[(MissingClosingQuote, "", "'")]);
+    check("'hello'", r#""hello""#, &[(SingleQuotedString, "'hello'", r#""hello""#)]);
+    check(r#"'say "hi"'"#, r#""say \"hi\"""#, &[
+        (SingleQuotedString, r#"'say "hi"'"#, r#""say \"hi\"""#),
+    ]);
+    check("b'ab", r#"b"ab""#, &[
+        (MissingClosingQuote, "", "'"),
+        (SingleQuotedString, "b'ab'", r#"b"ab""#),
+    ]);
+
+    // Unrepairable errors return the error of the original input.
+    let e = Literal::parse_lenient(r#""\q"#).unwrap_err();
+    assert_eq!(e.code(), Literal::parse(r#""\q"#).unwrap_err().code());
+    assert!(Literal::parse_lenient("").is_err());
+    assert!(Literal::parse_lenient("foo").is_err());
+    assert!(Literal::parse_lenient(r#""\x80"#).is_err());
+
+    let lit = Literal::parse_lenient("0X1F").unwrap();
+    assert_eq!(lit.warnings()[0].to_string(), "uppercase base prefix: replaced `0X` with `0x`");
+    let lit = Literal::parse_lenient("'a").unwrap();
+    assert_eq!(lit.warnings()[0].to_string(), "missing closing quote: inserted `'`");
+}
+
+#[test]
+fn validate_agrees_with_parse() {
+    #[track_caller]
+    fn check(input: &str) {
+        let expected = Literal::parse(input).map(|lit| lit.kind());
+        if let Ok(kind) = expected {
+            assert_eq!(crate::classify(input), Some(kind), "for input {input:?}");
+        }
+        let actual = Literal::validate(input);
+        let expected = expected.map_err(|e| (e.kind, e.span));
+        assert_eq!(actual.map_err(|e| (e.kind, e.span)), expected, "for input {input:?}");
+    }
+
+    // All short inputs made of characters relevant for literals.
+    let alphabet = [
+        "\"", "'", "\\", "r", "#", "b", "c", "x", "u", "{", "}", "0", "7", "F", "_", ".", "e",
+        "n", "\n", "\r", "\0", "é", " ",
+    ];
+    let mut input = String::new();
+    for a in alphabet {
+        for b in alphabet {
+            for c in alphabet {
+                input.clear();
+                input.extend([a, b, c]);
+                check(&input);
+                for d in alphabet {
+                    input.truncate(a.len() + b.len() + c.len());
+                    input.push_str(d);
+                    check(&input);
+                }
+            }
+        }
+    }
+
+    // Longer string literals, with special characters at every position.
+    let delimiters = [
+        ("\"", "\""), ("r\"", "\""), ("r#\"", "\"#"), ("b\"", "\""), ("br\"", "\""),
+        ("br##\"", "\"##"), ("c\"", "\""), ("cr\"", "\""),
+    ];
+    let specials = ["\"", "\\", "\\n", "\\x7F", "\\u{E9}", "\\\n  ", "\r", "\0", "é", "\"#", "'"];
+    for (start, end) in delimiters {
+        for special in specials {
+            for pos in 0..20 {
+                let content = format!("{}{}{}", "a".repeat(pos), special, "b".repeat(20 - pos));
+                check(&format!("{start}{content}{end}"));
+                check(&format!("{start}{content}{end}suffix"));
+                check(&format!("{start}{content}"));
+            }
+        }
+    }
+}
+
+#[test]
+fn kind_and_accessors() {
+    use crate::{classify, LiteralKind};
+
+    let lit = Literal::parse("27u8").unwrap();
+    assert_eq!(lit.kind(), LiteralKind::Integer);
+    assert!(lit.is_integer());
+    assert!(!lit.is_float());
+    assert_eq!(lit.as_integer().unwrap().suffix(), "u8");
+    assert!(lit.as_string().is_none());
+    let lit = lit.into_string().unwrap_err();
+    assert_eq!(lit.into_integer().unwrap().value::<u8>(), Some(27));
+
+    let lit = Literal::parse(r#"c"foo""#).unwrap();
+    assert_eq!(lit.kind(), LiteralKind::CString);
+    assert!(lit.is_c_string());
+    assert!(!lit.is_string() && !lit.is_byte_string());
+    assert_eq!(lit.into_c_string().unwrap().value().to_bytes(), b"foo");
+
+    assert!(Literal::parse("false").unwrap().as_bool().is_some());
+    assert!(Literal::parse("b'x'").unwrap().is_byte());
+    assert!(Literal::parse("'x'").unwrap().is_char());
+
+    assert_eq!(classify(""), None);
+    assert_eq!(classify("falsey"), None);
+    assert_eq!(classify("-1"), None);
+    assert_eq!(classify("'a"), Some(LiteralKind::Char));
+    assert_eq!(classify("b'a'"), Some(LiteralKind::Byte));
+    assert_eq!(classify("b"), None);
+    assert_eq!(classify("1.5"), Some(LiteralKind::Float));
+    assert_eq!(classify("1_f32"), Some(LiteralKind::Integer));
 }
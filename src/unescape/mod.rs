@@ -0,0 +1,86 @@
+//! Unescaping of bare Rust escape sequences, without surrounding quotes.
+//!
+//! This is useful for languages that reuse Rust's escape syntax inside other
+//! delimiters. The input is treated like the content of a string, byte string
+//! or C string literal, i.e. everything between the quotes. The same rules
+//! apply, including `\` followed by a newline skipping all following
+//! whitespace. As there are no delimiters, `"` does not need to be escaped.
+//!
+//! Spans of returned errors are relative to the input.
+//!
+//! ```
+//! use litrs::unescape::{self, Mode};
+//!
+//! let value = unescape::str(r"Hello\n\u{1F980}", Mode::Normal).unwrap();
+//! assert_eq!(value, "Hello\n🦀");
+//!
+//! let value = unescape::bytes(r#"say "\xFF""#, Mode::Normal).unwrap();
+//! assert_eq!(value, &b"say \"\xFF\""[..]);
+//!
+//! let e = unescape::str(r"abc\q", Mode::Normal).unwrap_err();
+//! assert_eq!(e.span(), Some(3..5));
+//! ```
+
+use std::{borrow::Cow, ffi::CString};
+
+use crate::{
+    escape::{scan_raw_content, unescape_content},
+    ParseError,
+};
+
+
+/// How the input is interpreted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mode {
+    /// Like the content of a normal literal (e.g. `"..."`): escapes and string
+    /// continues are processed.
+    Normal,
+
+    /// Like the content of a raw literal (e.g. `r"..."`): the input is the
+    /// value as is. It is still checked, e.g. for `\r` or, for byte strings,
+    /// non-ASCII characters.
+    Raw,
+}
+
+/// Unescapes the input following the rules of string literals. Returns a
+/// borrowed value if the input does not contain escapes.
+pub fn str(input: &str, mode: Mode) -> Result<Cow<'_, str>, ParseError> {
+    let value = match mode {
+        Mode::Normal => unescape_content::<String>(input, 0, false, true, false, true)?.0,
+        Mode::Raw => scan_raw_content(input, 0, None, true, true).map(|_| None)?,
+    };
+    Ok(value.map_or(Cow::Borrowed(input), Cow::Owned))
+}
+
+/// Unescapes the input following the rules of byte string literals. Returns a
+/// borrowed value if the input does not contain escapes.
+pub fn bytes(input: &str, mode: Mode) -> Result<Cow<'_, [u8]>, ParseError> {
+    let value = match mode {
+        Mode::Normal => unescape_content::<Vec<u8>>(input, 0, false, false, true, true)?.0,
+        Mode::Raw => scan_raw_content(input, 0, None, false, true).map(|_| None)?,
+    };
+    Ok(value.map_or(Cow::Borrowed(input.as_bytes()), Cow::Owned))
+}
+
+/// Unescapes the input following the rules of C string literals, in
+/// particular, nul bytes and `\0` escapes are not allowed.
+///
+/// ```
+/// use litrs::unescape::{self, Mode};
+///
+/// let value = unescape::cstr(r"a\xFF\u{E9}", Mode::Normal).unwrap();
+/// assert_eq!(value.as_bytes(), b"a\xFF\xC3\xA9");
+/// assert!(unescape::cstr(r"a\0", Mode::Normal).is_err());
+/// ```
+pub fn cstr(input: &str, mode: Mode) -> Result<CString, ParseError> {
+    let value = match mode {
+        Mode::Normal => unescape_content::<Vec<u8>>(input, 0, false, true, true, false)?.0,
+        Mode::Raw => scan_raw_content(input, 0, None, true, false).map(|_| None)?,
+    };
+    let value = value.unwrap_or_else(|| input.as_bytes().to_vec());
+    Ok(CString::new(value).expect("bug: nul byte in C string value"))
+}
+
+
+#[cfg(test)]
+mod tests;
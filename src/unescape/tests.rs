@@ -0,0 +1,88 @@
+use std::borrow::Cow;
+
+use crate::{err::ParseErrorKind::*, ByteStringLit, CStringLit, StringLit};
+use super::*;
+
+
+#[test]
+fn str_normal() {
+    assert_eq!(str("", Mode::Normal).unwrap(), Cow::Borrowed(""));
+    assert_eq!(str("abc", Mode::Normal).unwrap(), Cow::Borrowed("abc"));
+    assert_eq!(str(r#"a"b"#, Mode::Normal).unwrap(), r#"a"b"#);
+    assert_eq!(str(r#"\"\'\\\n\r\t\0"#, Mode::Normal).unwrap(), "\"'\\\n\r\t\0");
+    assert_eq!(str(r"\x41\u{1F980}é", Mode::Normal).unwrap(), "A🦀é");
+    assert_eq!(str("a\\\n    b", Mode::Normal).unwrap(), "ab");
+    assert_eq!(str("a\\\n  ", Mode::Normal).unwrap(), "a");
+    assert_eq!(str("\\\n", Mode::Normal).unwrap(), "");
+    assert_eq!(str(r"\\", Mode::Normal).unwrap(), "\\");
+    assert!(matches!(str(r"a\nb", Mode::Normal).unwrap(), Cow::Owned(_)));
+}
+
+#[test]
+fn str_raw() {
+    assert_eq!(str(r"a\nb", Mode::Raw).unwrap(), Cow::Borrowed(r"a\nb"));
+    assert_eq!(str(r##"a"#b"##, Mode::Raw).unwrap(), r##"a"#b"##);
+    assert_eq!(str("a\0é", Mode::Raw).unwrap(), "a\0é");
+}
+
+#[test]
+fn bytes_and_cstr() {
+    assert_eq!(bytes(r"a\xFF\0", Mode::Normal).unwrap(), &b"a\xFF\0"[..]);
+    assert_eq!(bytes("a\\\n  b", Mode::Normal).unwrap(), &b"ab"[..]);
+    assert_eq!(bytes(r"a\xFF", Mode::Raw).unwrap(), &br"a\xFF"[..]);
+
+    assert_eq!(cstr(r"a\xFF\u{E9}", Mode::Normal).unwrap().as_bytes(), b"a\xFF\xC3\xA9");
+    assert_eq!(cstr(r"a\n", Mode::Raw).unwrap().as_bytes(), br"a\n");
+    assert_eq!(cstr("", Mode::Normal).unwrap().as_bytes(), b"");
+}
+
+#[test]
+fn errors() {
+    #[track_caller]
+    fn check<T: std::fmt::Debug>(
+        result: Result<T, ParseError>,
+        kind: crate::err::ParseErrorKind,
+        span: std::ops::Range<usize>,
+    ) {
+        let e = result.unwrap_err();
+        assert_eq!(e.kind, kind);
+        assert_eq!(e.span, Some(span));
+    }
+
+    check(str(r"abc\q", Mode::Normal), UnknownEscape, 3..5);
+    check(str(r"ab\x80", Mode::Normal), NonAsciiXEscape, 2..6);
+    check(str(r"ab\u{110000}", Mode::Normal), InvalidUnicodeEscapeChar, 2..12);
+    check(str(r"ab\u{12", Mode::Normal), UnterminatedUnicodeEscape, 2..7);
+    check(str(r"ab\", Mode::Normal), UnterminatedEscape, 2..3);
+    check(str("ab\rc", Mode::Normal), CarriageReturn, 2..3);
+    check(str("ab\rc", Mode::Raw), CarriageReturn, 2..3);
+
+    check(bytes("aé", Mode::Normal), NonAsciiInByteLiteral, 1..2);
+    check(bytes("aé", Mode::Raw), NonAsciiInByteLiteral, 1..2);
+    check(bytes(r"a\u{41}", Mode::Normal), UnicodeEscapeInByteLiteral, 1..3);
+
+    check(cstr(r"a\0", Mode::Normal), DisallowedNulEscape, 1..3);
+    check(cstr(r"a\x00", Mode::Normal), DisallowedNulEscape, 1..5);
+    check(cstr("a\0", Mode::Normal), NulByte, 1..2);
+    check(cstr("a\0", Mode::Raw), NulByte, 1..2);
+}
+
+#[test]
+fn agrees_with_literals() {
+    let inputs = [
+        "", "abc", "a\\nb", "\\x41\\x7F", "\\u{E9}\\u{10FFFF}", "a\\\n  b", "🦊", "\\'\\\"",
+    ];
+    for input in inputs {
+        let lit = StringLit::parse(format!("\"{input}\"")).unwrap();
+        assert_eq!(str(input, Mode::Normal).unwrap(), lit.value());
+        let lit = StringLit::parse(format!("r#\"{input}\"#")).unwrap();
+        assert_eq!(str(input, Mode::Raw).unwrap(), lit.value());
+
+        if let Ok(lit) = ByteStringLit::parse(format!("b\"{input}\"")) {
+            assert_eq!(bytes(input, Mode::Normal).unwrap(), lit.value());
+        }
+        if let Ok(lit) = CStringLit::parse(format!("c\"{input}\"")) {
+            assert_eq!(cstr(input, Mode::Normal).unwrap().as_c_str(), lit.value());
+        }
+    }
+}